@@ -0,0 +1,33 @@
+use std::fmt::Display;
+use tauri::Emitter;
+
+use crate::payloads::AppErrorPayload;
+
+pub const APP_ERROR_EVENT: &str = "app-error";
+
+pub fn app_error_payload(source: &str, message: impl Display) -> AppErrorPayload {
+    AppErrorPayload {
+        source: source.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// Surfaces a failure from a background task that has no command caller to return it to.
+pub fn emit_app_error(app: &tauri::AppHandle, payload: AppErrorPayload) {
+    eprintln!("[{}] {}", payload.source, payload.message);
+    let _ = app.emit(APP_ERROR_EVENT, payload);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_error_payload_serializes_source_and_message() {
+        let payload = app_error_payload("terminal-output", "read failed");
+        let value = serde_json::to_value(&payload).expect("payload should serialize");
+
+        assert_eq!(value["source"], "terminal-output");
+        assert_eq!(value["message"], "read failed");
+    }
+}
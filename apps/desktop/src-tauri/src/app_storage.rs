@@ -0,0 +1,240 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use agentdock_core::db::{init_db, DbError};
+use rusqlite::Connection;
+use tauri::Manager;
+
+use crate::payloads::StorageStatusPayload;
+
+const DB_FILE_NAME: &str = "agentdock.db";
+// Shared cache so every `Connection::open` in the process sees the same in-memory database.
+const IN_MEMORY_DB_URI: &str = "file:agentdock-memory?mode=memory&cache=shared";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageMode {
+    AppData,
+    TempDir,
+    InMemory,
+}
+
+impl StorageMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StorageMode::AppData => "appData",
+            StorageMode::TempDir => "tempDir",
+            StorageMode::InMemory => "inMemory",
+        }
+    }
+}
+
+/// Where the app database lives for this run. Anything other than `AppData` means startup
+/// hit an error and the app is running degraded.
+pub struct AppStorage {
+    mode: StorageMode,
+    data_dir: PathBuf,
+    db_path: PathBuf,
+    errors: Vec<String>,
+    // Keeps the shared in-memory database alive; it is dropped with the last connection.
+    _memory_connection: Option<Mutex<Connection>>,
+}
+
+impl AppStorage {
+    pub fn status(&self) -> StorageStatusPayload {
+        StorageStatusPayload {
+            mode: self.mode.as_str().to_string(),
+            degraded: self.mode != StorageMode::AppData,
+            data_dir: self.data_dir.display().to_string(),
+            db_path: self.db_path.display().to_string(),
+            errors: self.errors.clone(),
+        }
+    }
+}
+
+/// Initializes the database under the app data dir, falling back to a temp dir and then
+/// to an in-memory database instead of failing startup. Errors are logged and kept for
+/// `get_storage_status`.
+pub fn init_app_storage(app: &tauri::AppHandle) {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|error| format!("Failed to get app data directory: {error}"));
+    let storage = select_storage(
+        app_data_dir,
+        std::env::temp_dir().join("agentdock"),
+        |path| init_db(path).map(|_| ()),
+    );
+    for error in &storage.errors {
+        eprintln!("[storage] {error}");
+    }
+    if storage.mode != StorageMode::AppData {
+        eprintln!(
+            "[storage] running in degraded mode ({}) with database {}",
+            storage.mode.as_str(),
+            storage.db_path.display()
+        );
+    }
+    app.manage(storage);
+}
+
+pub fn data_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(app_storage(app)?.data_dir.clone())
+}
+
+pub fn db_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(app_storage(app)?.db_path.clone())
+}
+
+pub fn open_db_connection(app: &tauri::AppHandle) -> Result<Connection, String> {
+    Connection::open(db_path(app)?).map_err(|error| format!("Failed to open database: {error}"))
+}
+
+pub fn storage_status(app: &tauri::AppHandle) -> Result<StorageStatusPayload, String> {
+    Ok(app_storage(app)?.status())
+}
+
+fn app_storage(app: &tauri::AppHandle) -> Result<tauri::State<'_, AppStorage>, String> {
+    app.try_state::<AppStorage>()
+        .ok_or_else(|| "App storage is not initialized".to_string())
+}
+
+fn select_storage<F>(
+    app_data_dir: Result<PathBuf, String>,
+    temp_dir: PathBuf,
+    init: F,
+) -> AppStorage
+where
+    F: Fn(&Path) -> Result<(), DbError>,
+{
+    let mut errors = Vec::new();
+    let candidates = [
+        (StorageMode::AppData, app_data_dir),
+        (StorageMode::TempDir, Ok(temp_dir.clone())),
+    ];
+    for (mode, dir) in candidates {
+        let dir = match dir {
+            Ok(dir) => dir,
+            Err(error) => {
+                errors.push(error);
+                continue;
+            }
+        };
+        let db_path = dir.join(DB_FILE_NAME);
+        let result = fs::create_dir_all(&dir)
+            .map_err(DbError::from)
+            .and_then(|_| init(&db_path));
+        match result {
+            Ok(()) => {
+                return AppStorage {
+                    mode,
+                    data_dir: dir,
+                    db_path,
+                    errors,
+                    _memory_connection: None,
+                }
+            }
+            Err(error) => errors.push(format!(
+                "Failed to initialize database at {}: {error}",
+                db_path.display()
+            )),
+        }
+    }
+
+    let db_path = PathBuf::from(IN_MEMORY_DB_URI);
+    let memory_connection = match open_in_memory_db(&db_path) {
+        Ok(connection) => Some(Mutex::new(connection)),
+        Err(error) => {
+            errors.push(format!("Failed to initialize in-memory database: {error}"));
+            None
+        }
+    };
+    AppStorage {
+        mode: StorageMode::InMemory,
+        data_dir: temp_dir,
+        db_path,
+        errors,
+        _memory_connection: memory_connection,
+    }
+}
+
+fn open_in_memory_db(uri: &Path) -> Result<Connection, DbError> {
+    let mut connection = Connection::open(uri)?;
+    agentdock_core::db::run_migrations(&mut connection)?;
+    Ok(connection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failing_init(_path: &Path) -> Result<(), DbError> {
+        Err(DbError::Io(std::io::Error::other("database is locked")))
+    }
+
+    #[test]
+    fn select_storage_uses_app_data_dir_when_init_succeeds() {
+        let root = tempfile::tempdir().expect("temp dir should be created");
+        let app_data_dir = root.path().join("app");
+
+        let storage = select_storage(
+            Ok(app_data_dir.clone()),
+            root.path().join("fallback"),
+            |path| init_db(path).map(|_| ()),
+        );
+
+        assert_eq!(storage.mode, StorageMode::AppData);
+        assert_eq!(storage.db_path, app_data_dir.join(DB_FILE_NAME));
+        assert!(storage.db_path.is_file());
+        let status = storage.status();
+        assert!(!status.degraded);
+        assert!(status.errors.is_empty());
+    }
+
+    #[test]
+    fn select_storage_falls_back_to_temp_dir_when_app_data_dir_fails() {
+        let root = tempfile::tempdir().expect("temp dir should be created");
+        let read_only_home = root.path().join("not-a-dir");
+        fs::write(&read_only_home, "").expect("file should be written");
+        let temp_dir = root.path().join("fallback");
+
+        let storage = select_storage(Ok(read_only_home.join("app")), temp_dir.clone(), |path| {
+            init_db(path).map(|_| ())
+        });
+
+        assert_eq!(storage.mode, StorageMode::TempDir);
+        assert_eq!(storage.db_path, temp_dir.join(DB_FILE_NAME));
+        let status = storage.status();
+        assert!(status.degraded);
+        assert_eq!(status.mode, "tempDir");
+        assert_eq!(status.errors.len(), 1);
+        assert!(status.errors[0].contains("not-a-dir"));
+    }
+
+    #[test]
+    fn select_storage_falls_back_to_shared_in_memory_db_when_every_dir_fails() {
+        let root = tempfile::tempdir().expect("temp dir should be created");
+        let storage = select_storage(
+            Err("Failed to get app data directory: no home".to_string()),
+            root.path().join("fallback"),
+            failing_init,
+        );
+
+        assert_eq!(storage.mode, StorageMode::InMemory);
+        let status = storage.status();
+        assert!(status.degraded);
+        assert_eq!(status.errors.len(), 2);
+        assert!(status.errors[0].contains("no home"));
+        assert!(status.errors[1].contains("database is locked"));
+
+        let connection = Connection::open(&storage.db_path).expect("memory db should open");
+        let tables: i64 = connection
+            .query_row(
+                "SELECT COUNT(1) FROM sqlite_master WHERE name = 'pinned_threads'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("table lookup should succeed");
+        assert_eq!(tables, 1);
+    }
+}
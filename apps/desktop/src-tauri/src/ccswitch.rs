@@ -1,7 +1,7 @@
-use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use provider_contract::default_home_dir;
 use rusqlite::{Connection, OpenFlags};
 use serde_json::Value;
 
@@ -62,23 +62,7 @@ fn default_ccswitch_config_dir() -> Result<PathBuf, String> {
 }
 
 fn resolve_home_dir() -> Result<PathBuf, String> {
-    if let Some(home) = env::var_os("HOME") {
-        return Ok(PathBuf::from(home));
-    }
-    if let Some(home) = env::var_os("USERPROFILE") {
-        return Ok(PathBuf::from(home));
-    }
-
-    let drive = env::var_os("HOMEDRIVE");
-    let path = env::var_os("HOMEPATH");
-    match (drive, path) {
-        (Some(drive), Some(path)) => {
-            let mut buffer = PathBuf::from(drive);
-            buffer.push(path);
-            Ok(buffer)
-        }
-        _ => Err("Unable to resolve home directory.".to_string()),
-    }
+    default_home_dir().ok_or_else(|| "Unable to resolve home directory.".to_string())
 }
 
 fn read_ccswitch_override_dir(default_config_dir: &Path) -> Option<PathBuf> {
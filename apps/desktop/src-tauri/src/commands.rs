@@ -1,51 +1,334 @@
-use provider_contract::ProviderId;
+use provider_contract::{ProviderId, ThreadOverviewOptions};
 use tauri::Emitter;
 
 use crate::payloads::{
     AddSkillRepoRequest, CcSwitchImportPayload, ClaudeThreadRuntimeStatePayload,
-    CloseEmbeddedTerminalRequest, CodexThreadRuntimeStatePayload, DeleteMcpServerRequest,
-    DiscoverSkillInstallProgressPayload, GetClaudeThreadRuntimeStateRequest,
-    GetCodexThreadRuntimeStateRequest, GetOpenCodeThreadRuntimeStateRequest,
-    GetProjectGitBranchRequest, InstallDiscoveredSkillRequest, InstallSkillFromGitRequest,
-    InstallSkillFromPathRequest, McpConnectionTestResultPayload, McpOperationLogPayload,
-    McpServerPayload, OpenCodeThreadRuntimeStatePayload, OpenNewThreadInTerminalRequest,
+    CloseEmbeddedTerminalRequest, CodexThreadRuntimeStatePayload, CodexThreadUsagePayload,
+    CompareThreadsRequest, CreateRemoteSessionRequest, DeleteMcpServerRequest,
+    DeleteTerminalProfileRequest, DiscoverSkillInstallProgressPayload,
+    GetClaudeThreadRuntimeStateRequest, GetCodexThreadRuntimeStateRequest,
+    GetCodexThreadUsageRequest, GetFinalAssistantMessageRequest, GetLatestMessagePreviewRequest,
+    GetOpenCodeThreadRuntimeStateRequest, GetProjectGitBranchRequest, GetProviderAuthModeRequest,
+    GetProviderConfigSummaryRequest, GetThreadEventLogRequest, InstallDiscoveredSkillRequest,
+    InstallSkillFromGitRequest, InstallSkillFromPathRequest, InterruptThreadTerminalRequest,
+    LaunchTemplatesPayload, LaunchTemplatesRequest, ListRemoteSessionsRequest,
+    McpConnectionTestResultPayload, McpOperationLogPayload, McpServerPayload,
+    OpenCodeThreadRuntimeStatePayload, OpenNewThreadInTerminalRequest,
     OpenProjectWithTargetRequest, OpenProjectWithTargetResponse, OpenTargetStatusPayload,
     OpenThreadInHappyRequest, OpenThreadInTerminalRequest, OpenThreadInTerminalResponse,
-    ProjectGitBranchPayload, ProviderInstallStatusPayload, RemoveSkillRepoRequest,
-    ResizeEmbeddedTerminalRequest, SaveMcpServerRequest, SaveMcpServerResponsePayload,
-    SkillPayload, SkillRepoPayload, StartEmbeddedTerminalRequest, StartEmbeddedTerminalResponse,
-    StartNewEmbeddedTerminalRequest, SyncMcpConfigsRequest, SyncMcpConfigsResponsePayload,
-    TestMcpConnectionRequest, ThreadSummaryPayload, ToggleMcpServerEnabledRequest,
-    ToggleSkillEnabledForProviderRequest, ToggleSkillEnabledRequest, UninstallSkillRequest,
-    WriteEmbeddedTerminalInputRequest,
+    PathHydrationReportPayload, PinnedThreadPayload, ProjectGitBranchPayload,
+    ProviderAuthModePayload, ProviderConfigSummaryPayload, ProviderInstallStatusPayload,
+    RecentProjectPayload, RegisterRemoteDeviceRequest, RemoteDevicePayload, RemoteSessionPayload,
+    RemoveSkillRepoRequest, ResizeEmbeddedTerminalRequest, RestartEmbeddedTerminalRequest,
+    SaveMcpServerRequest, SaveMcpServerResponsePayload, SkillPayload, SkillRepoPayload,
+    StartEmbeddedTerminalRequest, StartEmbeddedTerminalResponse, StartNewEmbeddedTerminalRequest,
+    StorageStatusPayload, SyncMcpConfigsRequest, SyncMcpConfigsResponsePayload,
+    TerminalProfilePayload, TestMcpConnectionRequest, ThreadComparisonPayload, ThreadCountPayload,
+    ThreadEventPayload, ThreadExistsRequest, ThreadListResultPayload, ThreadPinRequest,
+    ThreadSummaryPayload, ToggleMcpServerEnabledRequest, ToggleSkillEnabledForProviderRequest,
+    ToggleSkillEnabledRequest, UninstallSkillRequest, WriteEmbeddedTerminalInputRequest,
 };
 use crate::provider_id::parse_provider_id;
 use crate::skills::{DiscoverableSkill, SkillsContext};
+use crate::threads::ThreadScanEvent;
 use crate::{
-    ccswitch, mcp, open_targets, payloads::ImportProviderSkillsRequest,
-    payloads::ProviderSkillPayload, provider_health, skills, terminal, threads,
+    app_storage, ccswitch, mcp, open_targets, path_env, payloads::ImportProviderSkillsRequest,
+    payloads::ProviderSkillPayload, process_probe, provider_health, remote_devices, schema, skills,
+    terminal, threads,
 };
 
+const DEFAULT_RECENT_PROJECTS_LIMIT: usize = 10;
+
 #[tauri::command]
 pub async fn list_threads(
+    app: tauri::AppHandle,
     project_path: Option<String>,
+    include_runtime_state: Option<bool>,
+    worktree_path: Option<String>,
+    include_message_count: Option<bool>,
+    sort_by: Option<String>,
 ) -> Result<Vec<ThreadSummaryPayload>, String> {
-    tauri::async_runtime::spawn_blocking(move || threads::list_threads(project_path.as_deref()))
+    tauri::async_runtime::spawn_blocking(move || {
+        let sort_order = thread_sort_order(sort_by.as_deref())?;
+        let project_path = threads::project_path_or_default(&app, project_path.as_deref())?;
+        let pinned = threads::list_pinned(&app)?;
+        let mut result = threads::list_threads(
+            project_path.as_deref(),
+            &pinned,
+            thread_overview_options(include_runtime_state, include_message_count),
+        )?;
+        threads::retain_worktree_threads(&mut result, worktree_path.as_deref());
+        threads::sort_threads_by(&mut result, sort_order);
+        Ok(result)
+    })
+    .await
+    .map_err(|error| format!("Failed to scan thread list: {error}"))?
+}
+
+#[tauri::command]
+pub async fn list_threads_with_status(
+    app: tauri::AppHandle,
+    project_path: Option<String>,
+    include_runtime_state: Option<bool>,
+    worktree_path: Option<String>,
+    include_message_count: Option<bool>,
+    sort_by: Option<String>,
+) -> Result<ThreadListResultPayload, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let sort_order = thread_sort_order(sort_by.as_deref())?;
+        let project_path = threads::project_path_or_default(&app, project_path.as_deref())?;
+        let pinned = threads::list_pinned(&app)?;
+        let mut result = threads::list_threads_with_status(
+            project_path.as_deref(),
+            &pinned,
+            thread_overview_options(include_runtime_state, include_message_count),
+        )?;
+        threads::retain_worktree_threads(&mut result.threads, worktree_path.as_deref());
+        threads::sort_threads_by(&mut result.threads, sort_order);
+        Ok(result)
+    })
+    .await
+    .map_err(|error| format!("Failed to scan thread list: {error}"))?
+}
+
+/// `list_threads` that emits `thread-discovered` as each provider finishes scanning and
+/// `thread-scan-complete` at the end, so the sidebar fills in during a cold start.
+#[tauri::command]
+pub async fn stream_threads(
+    app: tauri::AppHandle,
+    project_path: Option<String>,
+    include_runtime_state: Option<bool>,
+    worktree_path: Option<String>,
+    include_message_count: Option<bool>,
+) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let project_path = threads::project_path_or_default(&app, project_path.as_deref())?;
+        let pinned = threads::list_pinned(&app)?;
+        threads::stream_threads(
+            project_path.as_deref(),
+            &pinned,
+            thread_overview_options(include_runtime_state, include_message_count),
+            worktree_path.as_deref(),
+            |event| {
+                let _ = match event {
+                    ThreadScanEvent::Discovered(payload) => {
+                        app.emit(threads::THREAD_DISCOVERED_EVENT, payload)
+                    }
+                    ThreadScanEvent::Complete(payload) => {
+                        app.emit(threads::THREAD_SCAN_COMPLETE_EVENT, payload)
+                    }
+                };
+            },
+        );
+        Ok(())
+    })
+    .await
+    .map_err(|error| format!("Failed to scan thread list: {error}"))?
+}
+
+fn thread_sort_order(sort_by: Option<&str>) -> Result<threads::ThreadSortOrder, String> {
+    sort_by
+        .map(threads::parse_thread_sort_order)
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+fn thread_overview_options(
+    include_runtime_state: Option<bool>,
+    include_message_count: Option<bool>,
+) -> ThreadOverviewOptions {
+    ThreadOverviewOptions {
+        include_runtime_state: include_runtime_state.unwrap_or(false),
+        include_message_count: include_message_count.unwrap_or(false),
+    }
+}
+
+#[tauri::command]
+pub async fn count_threads(
+    app: tauri::AppHandle,
+    project_path: Option<String>,
+) -> Result<ThreadCountPayload, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let project_path = threads::project_path_or_default(&app, project_path.as_deref())?;
+        threads::count_threads(project_path.as_deref())
+    })
+    .await
+    .map_err(|error| format!("Failed to count threads: {error}"))?
+}
+
+#[tauri::command]
+pub async fn list_recent_projects(
+    limit: Option<usize>,
+) -> Result<Vec<RecentProjectPayload>, String> {
+    let limit = limit.unwrap_or(DEFAULT_RECENT_PROJECTS_LIMIT);
+    tauri::async_runtime::spawn_blocking(move || threads::list_recent_projects(limit))
         .await
-        .map_err(|error| format!("Failed to scan thread list: {error}"))?
+        .map_err(|error| format!("Failed to list recent projects: {error}"))?
 }
 
 #[tauri::command]
 pub async fn list_provider_install_statuses(
+    app: tauri::AppHandle,
     project_path: Option<String>,
+    force: Option<bool>,
 ) -> Result<Vec<ProviderInstallStatusPayload>, String> {
     tauri::async_runtime::spawn_blocking(move || {
-        provider_health::list_provider_install_statuses(project_path.as_deref())
+        let project_path = threads::project_path_or_default(&app, project_path.as_deref())?;
+        provider_health::list_provider_install_statuses(
+            project_path.as_deref(),
+            force.unwrap_or(false),
+        )
     })
     .await
     .map_err(|error| format!("Failed to load provider install statuses: {error}"))?
 }
 
+#[tauri::command]
+pub async fn get_default_project(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || threads::get_default_project(&app))
+        .await
+        .map_err(|error| format!("Failed to load default project: {error}"))?
+}
+
+#[tauri::command]
+pub async fn set_default_project(
+    app: tauri::AppHandle,
+    project_path: Option<String>,
+) -> Result<Option<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        threads::set_default_project(&app, project_path.as_deref())
+    })
+    .await
+    .map_err(|error| format!("Failed to save default project: {error}"))?
+}
+
+#[tauri::command]
+pub async fn list_pinned_threads(
+    app: tauri::AppHandle,
+) -> Result<Vec<PinnedThreadPayload>, String> {
+    tauri::async_runtime::spawn_blocking(move || threads::list_pinned_threads(&app))
+        .await
+        .map_err(|error| format!("Failed to load pinned threads: {error}"))?
+}
+
+#[tauri::command]
+pub async fn pin_thread(app: tauri::AppHandle, request: ThreadPinRequest) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider_id = parse_provider_id(&request.provider_id)?;
+        threads::pin_thread(&app, provider_id, &request.thread_id)
+    })
+    .await
+    .map_err(|error| format!("Failed to pin thread: {error}"))?
+}
+
+#[tauri::command]
+pub async fn unpin_thread(
+    app: tauri::AppHandle,
+    request: ThreadPinRequest,
+) -> Result<bool, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider_id = parse_provider_id(&request.provider_id)?;
+        threads::unpin_thread(&app, provider_id, &request.thread_id)
+    })
+    .await
+    .map_err(|error| format!("Failed to unpin thread: {error}"))?
+}
+
+#[tauri::command]
+pub async fn register_remote_device(
+    app: tauri::AppHandle,
+    request: RegisterRemoteDeviceRequest,
+) -> Result<RemoteDevicePayload, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        remote_devices::register_remote_device(
+            &app,
+            &request.name,
+            &request.platform,
+            &request.public_key,
+        )
+    })
+    .await
+    .map_err(|error| format!("Failed to register remote device: {error}"))?
+}
+
+#[tauri::command]
+pub async fn list_remote_devices(
+    app: tauri::AppHandle,
+) -> Result<Vec<RemoteDevicePayload>, String> {
+    tauri::async_runtime::spawn_blocking(move || remote_devices::list_remote_devices(&app))
+        .await
+        .map_err(|error| format!("Failed to load remote devices: {error}"))?
+}
+
+#[tauri::command]
+pub async fn create_remote_session(
+    app: tauri::AppHandle,
+    request: CreateRemoteSessionRequest,
+) -> Result<RemoteSessionPayload, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider_id = parse_provider_id(&request.provider_id)?;
+        remote_devices::create_remote_session(
+            &app,
+            &request.device_id,
+            provider_id,
+            &request.thread_id,
+        )
+    })
+    .await
+    .map_err(|error| format!("Failed to create remote session: {error}"))?
+}
+
+#[tauri::command]
+pub async fn list_remote_sessions(
+    app: tauri::AppHandle,
+    request: ListRemoteSessionsRequest,
+) -> Result<Vec<RemoteSessionPayload>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        remote_devices::list_remote_sessions(&app, request.device_id.as_deref())
+    })
+    .await
+    .map_err(|error| format!("Failed to load remote sessions: {error}"))?
+}
+
+#[tauri::command]
+pub async fn get_provider_auth_mode(
+    request: GetProviderAuthModeRequest,
+) -> Result<ProviderAuthModePayload, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider_id = parse_provider_id(&request.provider_id)?;
+        provider_health::get_provider_auth_mode(provider_id)
+    })
+    .await
+    .map_err(|error| format!("Failed to detect provider auth mode: {error}"))?
+}
+
+#[tauri::command]
+pub async fn get_provider_config_summary(
+    request: GetProviderConfigSummaryRequest,
+) -> Result<ProviderConfigSummaryPayload, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider_id = parse_provider_id(&request.provider_id)?;
+        provider_health::get_provider_config_summary(provider_id)
+    })
+    .await
+    .map_err(|error| format!("Failed to load provider config summary: {error}"))?
+}
+
+#[tauri::command]
+pub async fn get_path_hydration_report() -> Result<PathHydrationReportPayload, String> {
+    Ok(path_env::path_hydration_report())
+}
+
+#[tauri::command]
+pub async fn get_storage_status(app: tauri::AppHandle) -> Result<StorageStatusPayload, String> {
+    app_storage::storage_status(&app)
+}
+
+#[tauri::command]
+pub async fn get_payload_schema() -> Result<serde_json::Value, String> {
+    Ok(schema::payload_schema_document())
+}
+
 #[tauri::command]
 pub async fn import_ccswitch_suppliers() -> Result<CcSwitchImportPayload, String> {
     tauri::async_runtime::spawn_blocking(ccswitch::import_suppliers_from_ccswitch)
@@ -58,18 +341,45 @@ pub async fn get_codex_thread_runtime_state(
     request: GetCodexThreadRuntimeStateRequest,
 ) -> Result<CodexThreadRuntimeStatePayload, String> {
     tauri::async_runtime::spawn_blocking(move || {
-        threads::get_codex_thread_runtime_state(&request.thread_id)
+        let mut state = threads::get_codex_thread_runtime_state(&request.thread_id)?;
+        if request.check_process {
+            state.process_running = Some(process_probe::is_thread_process_running(
+                ProviderId::Codex,
+                &request.thread_id,
+                request.project_path.as_deref(),
+            ));
+        }
+        Ok(state)
     })
     .await
     .map_err(|error| format!("Failed to load Codex runtime state: {error}"))?
 }
 
+#[tauri::command]
+pub async fn get_codex_thread_usage(
+    request: GetCodexThreadUsageRequest,
+) -> Result<CodexThreadUsagePayload, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        threads::get_codex_thread_usage(&request.thread_id)
+    })
+    .await
+    .map_err(|error| format!("Failed to load Codex thread usage: {error}"))?
+}
+
 #[tauri::command]
 pub async fn get_claude_thread_runtime_state(
     request: GetClaudeThreadRuntimeStateRequest,
 ) -> Result<ClaudeThreadRuntimeStatePayload, String> {
     tauri::async_runtime::spawn_blocking(move || {
-        threads::get_claude_thread_runtime_state(&request.thread_id)
+        let mut state = threads::get_claude_thread_runtime_state(&request.thread_id)?;
+        if request.check_process {
+            state.process_running = Some(process_probe::is_thread_process_running(
+                ProviderId::ClaudeCode,
+                &request.thread_id,
+                request.project_path.as_deref(),
+            ));
+        }
+        Ok(state)
     })
     .await
     .map_err(|error| format!("Failed to load Claude runtime state: {error}"))?
@@ -80,14 +390,85 @@ pub async fn get_opencode_thread_runtime_state(
     request: GetOpenCodeThreadRuntimeStateRequest,
 ) -> Result<OpenCodeThreadRuntimeStatePayload, String> {
     tauri::async_runtime::spawn_blocking(move || {
-        threads::get_opencode_thread_runtime_state(&request.thread_id)
+        let mut state = threads::get_opencode_thread_runtime_state(&request.thread_id)?;
+        if request.check_process {
+            state.process_running = Some(process_probe::is_thread_process_running(
+                ProviderId::OpenCode,
+                &request.thread_id,
+                request.project_path.as_deref(),
+            ));
+        }
+        Ok(state)
     })
     .await
     .map_err(|error| format!("Failed to load OpenCode runtime state: {error}"))?
 }
 
+#[tauri::command]
+pub async fn get_latest_message_preview(
+    request: GetLatestMessagePreviewRequest,
+) -> Result<Option<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider_id = parse_provider_id(&request.provider_id)?;
+        threads::get_latest_message_preview(provider_id, &request.thread_id)
+    })
+    .await
+    .map_err(|error| format!("Failed to load latest message preview: {error}"))?
+}
+
+#[tauri::command]
+pub async fn get_final_assistant_message(
+    request: GetFinalAssistantMessageRequest,
+) -> Result<Option<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider_id = parse_provider_id(&request.provider_id)?;
+        threads::get_final_assistant_message(provider_id, &request.thread_id)
+    })
+    .await
+    .map_err(|error| format!("Failed to load final assistant message: {error}"))?
+}
+
+#[tauri::command]
+pub async fn thread_exists(request: ThreadExistsRequest) -> Result<bool, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider_id = parse_provider_id(&request.provider_id)?;
+        threads::thread_exists(provider_id, &request.thread_id)
+    })
+    .await
+    .map_err(|error| format!("Failed to check thread existence: {error}"))?
+}
+
+#[tauri::command]
+pub async fn compare_threads(
+    request: CompareThreadsRequest,
+) -> Result<ThreadComparisonPayload, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let left_provider_id = parse_provider_id(&request.left.provider_id)?;
+        let right_provider_id = parse_provider_id(&request.right.provider_id)?;
+        threads::compare_threads(
+            (left_provider_id, &request.left.thread_id),
+            (right_provider_id, &request.right.thread_id),
+        )
+    })
+    .await
+    .map_err(|error| format!("Failed to compare threads: {error}"))?
+}
+
+#[tauri::command]
+pub async fn get_thread_event_log(
+    request: GetThreadEventLogRequest,
+) -> Result<Vec<ThreadEventPayload>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider_id = parse_provider_id(&request.provider_id)?;
+        threads::get_thread_event_log(provider_id, &request.thread_id, request.limit)
+    })
+    .await
+    .map_err(|error| format!("Failed to load thread event log: {error}"))?
+}
+
 #[tauri::command]
 pub async fn open_thread_in_terminal(
+    app: tauri::AppHandle,
     request: OpenThreadInTerminalRequest,
 ) -> Result<OpenThreadInTerminalResponse, String> {
     tauri::async_runtime::spawn_blocking(move || {
@@ -100,6 +481,7 @@ pub async fn open_thread_in_terminal(
         } = request;
         let provider_id = parse_provider_for_terminal_launch(&provider_id)?;
         terminal::open_thread_in_terminal(
+            &app,
             provider_id,
             &thread_id,
             profile_name.as_deref(),
@@ -165,6 +547,7 @@ pub async fn get_project_git_branch(
 
 #[tauri::command]
 pub async fn open_new_thread_in_terminal(
+    app: tauri::AppHandle,
     request: OpenNewThreadInTerminalRequest,
 ) -> Result<OpenThreadInTerminalResponse, String> {
     tauri::async_runtime::spawn_blocking(move || {
@@ -176,6 +559,7 @@ pub async fn open_new_thread_in_terminal(
         } = request;
         let provider_id = parse_provider_for_new_thread_launch(&provider_id)?;
         terminal::open_new_thread_in_terminal(
+            &app,
             provider_id,
             profile_name.as_deref(),
             env,
@@ -196,11 +580,13 @@ pub async fn start_embedded_terminal(
             thread_id,
             provider_id,
             profile_name,
+            terminal_profile,
             env,
             project_path,
             terminal_theme,
             cols,
             rows,
+            output_encoding,
         } = request;
         let provider_id = parse_provider_for_terminal_launch(&provider_id)?;
         terminal::start_embedded_terminal(
@@ -208,17 +594,89 @@ pub async fn start_embedded_terminal(
             provider_id,
             &thread_id,
             profile_name.as_deref(),
+            terminal_profile.as_deref(),
             env,
             project_path.as_deref(),
             terminal_theme.as_deref(),
             cols,
             rows,
+            output_encoding,
         )
     })
     .await
     .map_err(|error| format!("Failed to start embedded terminal: {error}"))?
 }
 
+#[tauri::command]
+pub async fn list_terminal_profiles(
+    app: tauri::AppHandle,
+) -> Result<Vec<TerminalProfilePayload>, String> {
+    tauri::async_runtime::spawn_blocking(move || terminal::list_terminal_profiles(&app))
+        .await
+        .map_err(|error| format!("Failed to list terminal profiles: {error}"))?
+}
+
+#[tauri::command]
+pub async fn save_terminal_profile(
+    app: tauri::AppHandle,
+    request: TerminalProfilePayload,
+) -> Result<TerminalProfilePayload, String> {
+    tauri::async_runtime::spawn_blocking(move || terminal::save_terminal_profile(&app, request))
+        .await
+        .map_err(|error| format!("Failed to save terminal profile: {error}"))?
+}
+
+#[tauri::command]
+pub async fn delete_terminal_profile(
+    app: tauri::AppHandle,
+    request: DeleteTerminalProfileRequest,
+) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        terminal::delete_terminal_profile(&app, &request.name)
+    })
+    .await
+    .map_err(|error| format!("Failed to delete terminal profile: {error}"))?
+}
+
+#[tauri::command]
+pub async fn get_launch_templates(
+    app: tauri::AppHandle,
+    request: LaunchTemplatesRequest,
+) -> Result<LaunchTemplatesPayload, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider_id = parse_provider_id(&request.provider_id)?;
+        terminal::get_launch_templates(&app, provider_id)
+    })
+    .await
+    .map_err(|error| format!("Failed to load launch templates: {error}"))?
+}
+
+#[tauri::command]
+pub async fn save_launch_templates(
+    app: tauri::AppHandle,
+    request: LaunchTemplatesPayload,
+) -> Result<LaunchTemplatesPayload, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider_id = parse_provider_id(&request.provider_id)?;
+        terminal::save_launch_templates(&app, provider_id, request)
+    })
+    .await
+    .map_err(|error| format!("Failed to save launch templates: {error}"))?
+}
+
+#[tauri::command]
+pub async fn reset_launch_templates(
+    app: tauri::AppHandle,
+    request: LaunchTemplatesRequest,
+) -> Result<LaunchTemplatesPayload, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider_id = parse_provider_id(&request.provider_id)?;
+        terminal::reset_launch_templates(&app, provider_id)
+    })
+    .await
+    .map_err(|error| format!("Failed to reset launch templates: {error}"))?
+}
+
 #[tauri::command]
 pub async fn start_new_embedded_terminal(
     app: tauri::AppHandle,
@@ -228,22 +686,26 @@ pub async fn start_new_embedded_terminal(
         let StartNewEmbeddedTerminalRequest {
             provider_id,
             profile_name,
+            terminal_profile,
             env,
             project_path,
             terminal_theme,
             cols,
             rows,
+            output_encoding,
         } = request;
         let provider_id = parse_provider_for_new_thread_launch(&provider_id)?;
         terminal::start_new_embedded_terminal(
             app,
             provider_id,
             profile_name.as_deref(),
+            terminal_profile.as_deref(),
             env,
             project_path.as_deref(),
             terminal_theme.as_deref(),
             cols,
             rows,
+            output_encoding,
         )
     })
     .await
@@ -261,6 +723,17 @@ pub async fn write_embedded_terminal_input(
     .map_err(|error| format!("Failed to write embedded terminal input: {error}"))?
 }
 
+#[tauri::command]
+pub async fn interrupt_thread_terminal(
+    request: InterruptThreadTerminalRequest,
+) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        terminal::interrupt_thread_terminal(&request.session_id, request.double)
+    })
+    .await
+    .map_err(|error| format!("Failed to interrupt embedded terminal: {error}"))?
+}
+
 #[tauri::command]
 pub async fn resize_embedded_terminal(
     request: ResizeEmbeddedTerminalRequest,
@@ -281,6 +754,18 @@ pub async fn close_embedded_terminal(request: CloseEmbeddedTerminalRequest) -> R
     .map_err(|error| format!("Failed to close embedded terminal: {error}"))?
 }
 
+#[tauri::command]
+pub async fn restart_embedded_terminal(
+    app: tauri::AppHandle,
+    request: RestartEmbeddedTerminalRequest,
+) -> Result<StartEmbeddedTerminalResponse, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        terminal::restart_embedded_terminal(app, &request.session_id)
+    })
+    .await
+    .map_err(|error| format!("Failed to restart embedded terminal: {error}"))?
+}
+
 fn parse_provider_for_terminal_launch(raw: &str) -> Result<ProviderId, String> {
     parse_provider_id(raw).map_err(|_| format!("Unsupported provider for terminal launch: {raw}"))
 }
@@ -0,0 +1,126 @@
+use std::path::Path;
+
+use crate::payloads::ThreadSummaryPayload;
+
+/// Set to `1`/`true` to show the bundled demo threads while no real sessions exist.
+pub const DEMO_MODE_ENV: &str = "AGENTDOCK_DEMO_MODE";
+pub const DEMO_THREAD_TAG: &str = "demo";
+
+const DEMO_THREAD_ID_PREFIX: &str = "demo-";
+const DEMO_THREADS_JSON: &str = include_str!("../demo/threads.json");
+
+pub fn demo_mode_from_env() -> bool {
+    std::env::var(DEMO_MODE_ENV)
+        .map(|value| matches!(value.trim(), "1" | "true" | "TRUE" | "yes"))
+        .unwrap_or(false)
+}
+
+/// Falls back to the demo threads when demo mode is on and the scan found nothing.
+pub fn with_demo_threads(
+    threads: Vec<ThreadSummaryPayload>,
+    demo_mode: bool,
+    project_path: Option<&str>,
+) -> Vec<ThreadSummaryPayload> {
+    if !demo_mode || !threads.is_empty() {
+        return threads;
+    }
+
+    demo_threads()
+        .into_iter()
+        .filter(|thread| {
+            project_path
+                .map(|filter| Path::new(&thread.project_path).starts_with(filter))
+                .unwrap_or(true)
+        })
+        .collect()
+}
+
+pub fn is_demo_thread_id(thread_id: &str) -> bool {
+    thread_id.starts_with(DEMO_THREAD_ID_PREFIX)
+}
+
+/// Demo threads are display-only; resuming one would hand a fake id to a real CLI.
+/// Outside demo mode no demo threads are listed, so a `demo-` id is a real session.
+pub fn ensure_not_demo_thread(thread_id: &str, demo_mode: bool) -> Result<(), String> {
+    if demo_mode && is_demo_thread_id(thread_id) {
+        return Err(format!("Demo thread cannot be resumed: {thread_id}"));
+    }
+    Ok(())
+}
+
+fn demo_threads() -> Vec<ThreadSummaryPayload> {
+    let mut threads: Vec<ThreadSummaryPayload> =
+        serde_json::from_str(DEMO_THREADS_JSON).expect("bundled demo threads must parse");
+    for thread in &mut threads {
+        if !thread.tags.iter().any(|tag| tag == DEMO_THREAD_TAG) {
+            thread.tags.push(DEMO_THREAD_TAG.to_string());
+        }
+    }
+    threads
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn real_thread() -> ThreadSummaryPayload {
+        ThreadSummaryPayload {
+            id: "session-1".to_string(),
+            provider_id: "claude_code".to_string(),
+            project_path: "/workspace/app".to_string(),
+            original_cwd: None,
+            model: None,
+            provider_backend: None,
+            title: "Real".to_string(),
+            tags: vec!["claude_code".to_string()],
+            last_active_at: 1_700_000_000_000,
+            last_message_preview: None,
+            is_pinned: false,
+            agent_answering: None,
+            last_event_kind: None,
+            message_count: None,
+        }
+    }
+
+    #[test]
+    fn bundled_demo_threads_parse() {
+        let threads: Vec<ThreadSummaryPayload> =
+            serde_json::from_str(DEMO_THREADS_JSON).expect("bundled demo threads must parse");
+        assert!(!threads.is_empty());
+    }
+
+    #[test]
+    fn demo_threads_only_appear_when_enabled_and_empty() {
+        assert!(with_demo_threads(Vec::new(), false, None).is_empty());
+
+        let real = with_demo_threads(vec![real_thread()], true, None);
+        assert_eq!(real.len(), 1);
+        assert_eq!(real[0].id, "session-1");
+
+        let demo = with_demo_threads(Vec::new(), true, None);
+        assert!(!demo.is_empty());
+    }
+
+    #[test]
+    fn demo_threads_are_tagged_and_cannot_be_resumed() {
+        let demo = with_demo_threads(Vec::new(), true, None);
+
+        for thread in &demo {
+            assert!(thread.tags.iter().any(|tag| tag == DEMO_THREAD_TAG));
+            assert!(is_demo_thread_id(&thread.id));
+            assert!(ensure_not_demo_thread(&thread.id, true).is_err());
+            assert!(ensure_not_demo_thread(&thread.id, false).is_ok());
+        }
+        assert!(ensure_not_demo_thread("session-1", true).is_ok());
+    }
+
+    #[test]
+    fn demo_threads_respect_project_filter() {
+        let demo = with_demo_threads(Vec::new(), true, Some("/demo/todo-api"));
+
+        assert_eq!(demo.len(), 1);
+        assert_eq!(demo[0].provider_id, "opencode");
+
+        assert!(with_demo_threads(Vec::new(), true, Some("/demo/todo")).is_empty());
+    }
+}
@@ -1,19 +1,23 @@
+mod app_events;
+mod app_storage;
 mod ccswitch;
 mod command_utils;
 mod commands;
+mod demo_threads;
 mod mcp;
 mod open_targets;
 mod path_env;
 mod payloads;
+mod process_probe;
 mod provider_health;
 mod provider_id;
+mod provider_registry;
+mod remote_devices;
+mod schema;
 mod skills;
 mod terminal;
 mod threads;
 
-use std::fs;
-use tauri::Manager;
-
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     path_env::hydrate_path_from_login_shell();
@@ -23,11 +27,35 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .invoke_handler(tauri::generate_handler![
             commands::list_threads,
+            commands::list_threads_with_status,
+            commands::stream_threads,
+            commands::count_threads,
+            commands::list_recent_projects,
+            commands::get_default_project,
+            commands::set_default_project,
+            commands::list_pinned_threads,
+            commands::pin_thread,
+            commands::unpin_thread,
+            commands::register_remote_device,
+            commands::list_remote_devices,
+            commands::create_remote_session,
+            commands::list_remote_sessions,
             commands::list_provider_install_statuses,
+            commands::get_provider_auth_mode,
+            commands::get_provider_config_summary,
+            commands::get_path_hydration_report,
+            commands::get_storage_status,
+            commands::get_payload_schema,
             commands::import_ccswitch_suppliers,
             commands::get_claude_thread_runtime_state,
             commands::get_codex_thread_runtime_state,
+            commands::get_codex_thread_usage,
             commands::get_opencode_thread_runtime_state,
+            commands::get_latest_message_preview,
+            commands::get_final_assistant_message,
+            commands::thread_exists,
+            commands::compare_threads,
+            commands::get_thread_event_log,
             commands::open_thread_in_terminal,
             commands::open_thread_in_happy,
             commands::is_happy_installed,
@@ -37,9 +65,17 @@ pub fn run() {
             commands::open_new_thread_in_terminal,
             commands::start_embedded_terminal,
             commands::start_new_embedded_terminal,
+            commands::list_terminal_profiles,
+            commands::save_terminal_profile,
+            commands::delete_terminal_profile,
+            commands::get_launch_templates,
+            commands::save_launch_templates,
+            commands::reset_launch_templates,
             commands::write_embedded_terminal_input,
+            commands::interrupt_thread_terminal,
             commands::resize_embedded_terminal,
             commands::close_embedded_terminal,
+            commands::restart_embedded_terminal,
             commands::list_skills,
             commands::install_skill_from_path,
             commands::install_skill_from_git,
@@ -62,10 +98,7 @@ pub fn run() {
             commands::sync_mcp_configs
         ])
         .setup(|app| {
-            let app_data_dir = app.path().app_data_dir()?;
-            fs::create_dir_all(&app_data_dir)?;
-            let db_path = app_data_dir.join("agentdock.db");
-            agentdock_core::db::init_db(&db_path)?;
+            app_storage::init_app_storage(app.handle());
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -9,11 +9,12 @@ use agentdock_core::mcp::{
     list_mcp_servers, update_mcp_server_enabled, update_mcp_server_test_result, upsert_mcp_server,
     McpServer,
 };
+use provider_contract::{local_app_data_dir, roaming_app_data_dir};
 use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Map, Value};
-use tauri::Manager;
 
+use crate::app_storage;
 use crate::payloads::{
     DeleteMcpServerRequest, McpConnectionTestResultPayload, McpFieldErrorPayload,
     McpOperationLogPayload, McpServerPayload, SaveMcpServerRequest, SaveMcpServerResponsePayload,
@@ -47,11 +48,7 @@ pub struct McpContext {
 
 impl McpContext {
     pub fn from_app_handle(app: &tauri::AppHandle) -> Result<Self, String> {
-        let app_data_dir = app
-            .path()
-            .app_data_dir()
-            .map_err(|error| format!("Failed to get app data directory: {error}"))?;
-        let db_path = app_data_dir.join("agentdock.db");
+        let db_path = app_storage::db_path(app)?;
         let home_dir =
             dirs::home_dir().ok_or_else(|| "Failed to resolve home directory".to_string())?;
 
@@ -431,21 +428,11 @@ fn provider_discovery_paths(home_dir: &Path, provider_id: &str) -> Vec<PathBuf>
 }
 
 fn opencode_config_paths(home_dir: &Path) -> Vec<PathBuf> {
-    let mut roots = Vec::new();
-
-    if let Ok(local_app_data) = std::env::var("LOCALAPPDATA") {
-        let trimmed = local_app_data.trim();
-        if !trimmed.is_empty() {
-            roots.push(PathBuf::from(trimmed).join("opencode"));
-        }
-    }
-    if let Ok(app_data) = std::env::var("APPDATA") {
-        let trimmed = app_data.trim();
-        if !trimmed.is_empty() {
-            roots.push(PathBuf::from(trimmed).join("opencode"));
-        }
-    }
-
+    let mut roots: Vec<PathBuf> = [local_app_data_dir(), roaming_app_data_dir()]
+        .into_iter()
+        .flatten()
+        .map(|dir| dir.join("opencode"))
+        .collect();
     roots.push(home_dir.join(".config").join("opencode"));
 
     let mut paths = Vec::new();
@@ -1,13 +1,53 @@
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
 use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::payloads::PathHydrationReportPayload;
 
 const SHELL_PATH_SENTINEL_START: &str = "__AGENTDOCK_PATH_START__";
 const SHELL_PATH_SENTINEL_END: &str = "__AGENTDOCK_PATH_END__";
 const SHELL_PATH_PROBE_COMMAND: &str =
     "printf '__AGENTDOCK_PATH_START__%s__AGENTDOCK_PATH_END__' \"$PATH\"";
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+const SHELL_PATH_PROBE_ENV: &str = "AGENTDOCK_PATH_PROBE";
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+const SHELL_PATH_PROBE_FLAGS: [&str; 2] = ["-ilc", "-lc"];
+
+static PATH_HYDRATION_REPORT: OnceLock<PathHydrationReportPayload> = OnceLock::new();
 
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
 pub fn hydrate_path_from_login_shell() {
+    // Login shells that launch AgentDock from their rc files would otherwise probe forever.
+    if std::env::var_os(SHELL_PATH_PROBE_ENV).is_some() {
+        return;
+    }
+
+    let report = probe_login_shell_path(&login_shell_candidates(), run_login_shell_probe);
+    if let (true, Some(path)) = (report.hydrated, report.resolved_path.as_deref()) {
+        std::env::set_var("PATH", path);
+    }
+    let _ = PATH_HYDRATION_REPORT.set(report);
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+pub fn hydrate_path_from_login_shell() {}
+
+pub fn path_hydration_report() -> PathHydrationReportPayload {
+    PATH_HYDRATION_REPORT
+        .get()
+        .cloned()
+        .unwrap_or_else(|| PathHydrationReportPayload {
+            hydrated: false,
+            shell: None,
+            resolved_path: std::env::var("PATH").ok(),
+            failure_reason: Some(
+                "PATH hydration from login shell is not used on this platform".to_string(),
+            ),
+        })
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn login_shell_candidates() -> Vec<String> {
     let mut shells = Vec::new();
 
     if let Ok(shell) = std::env::var("SHELL") {
@@ -17,33 +57,71 @@ pub fn hydrate_path_from_login_shell() {
         }
     }
 
-    shells.push("/bin/zsh".to_string());
-    shells.push("/bin/bash".to_string());
+    let fallbacks: &[&str] = if cfg!(target_os = "macos") {
+        &["/bin/zsh", "/bin/bash"]
+    } else {
+        &["/bin/bash"]
+    };
+    for fallback in fallbacks {
+        if !shells.iter().any(|shell| shell == fallback) {
+            shells.push(fallback.to_string());
+        }
+    }
+    shells
+}
 
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn probe_login_shell_path<F>(shells: &[String], mut run_probe: F) -> PathHydrationReportPayload
+where
+    F: FnMut(&str, &str) -> Result<Vec<u8>, String>,
+{
+    let mut failures = Vec::new();
     for shell in shells {
-        if let Some(path) = read_login_shell_path(&shell) {
-            std::env::set_var("PATH", path);
-            break;
+        for flag in SHELL_PATH_PROBE_FLAGS {
+            let result = run_probe(shell, flag).and_then(|stdout| {
+                extract_path_from_shell_output(&stdout)
+                    .ok_or_else(|| "PATH markers missing or empty in shell output".to_string())
+            });
+            match result {
+                Ok(path) => {
+                    return PathHydrationReportPayload {
+                        hydrated: true,
+                        shell: Some(shell.clone()),
+                        resolved_path: Some(path),
+                        failure_reason: None,
+                    };
+                }
+                Err(reason) => failures.push(format!("{shell} {flag}: {reason}")),
+            }
         }
     }
-}
 
-#[cfg(not(target_os = "macos"))]
-pub fn hydrate_path_from_login_shell() {}
+    PathHydrationReportPayload {
+        hydrated: false,
+        shell: None,
+        resolved_path: std::env::var("PATH").ok(),
+        failure_reason: Some(if failures.is_empty() {
+            "No login shell available to probe".to_string()
+        } else {
+            failures.join("; ")
+        }),
+    }
+}
 
-#[cfg(target_os = "macos")]
-fn read_login_shell_path(shell: &str) -> Option<String> {
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn run_login_shell_probe(shell: &str, flag: &str) -> Result<Vec<u8>, String> {
     let output = Command::new(shell)
-        .arg("-ilc")
+        .arg(flag)
         .arg(SHELL_PATH_PROBE_COMMAND)
+        .env(SHELL_PATH_PROBE_ENV, "1")
         .output()
-        .ok()?;
+        .map_err(|error| format!("failed to launch shell ({error})"))?;
 
     if !output.status.success() {
-        return None;
+        return Err(format!("shell exited with {}", output.status));
     }
 
-    extract_path_from_shell_output(&output.stdout)
+    Ok(output.stdout)
 }
 
 pub fn extract_path_from_shell_output(stdout: &[u8]) -> Option<String> {
@@ -60,7 +138,7 @@ pub fn extract_path_from_shell_output(stdout: &[u8]) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::extract_path_from_shell_output;
+    use super::*;
 
     #[test]
     fn extract_path_from_shell_output_reads_marker_payload() {
@@ -75,4 +153,66 @@ mod tests {
         let output = b"/usr/bin:/bin\n";
         assert!(extract_path_from_shell_output(output).is_none());
     }
+
+    #[test]
+    fn extract_path_from_shell_output_returns_none_for_empty_payload() {
+        let output = b"__AGENTDOCK_PATH_START____AGENTDOCK_PATH_END__";
+        assert!(extract_path_from_shell_output(output).is_none());
+    }
+
+    #[test]
+    fn extract_path_from_shell_output_returns_none_for_whitespace_payload() {
+        let output = b"motd\n__AGENTDOCK_PATH_START__  \n\t __AGENTDOCK_PATH_END__\n";
+        assert!(extract_path_from_shell_output(output).is_none());
+    }
+
+    #[test]
+    fn extract_path_from_shell_output_requires_end_marker() {
+        let output = b"__AGENTDOCK_PATH_START__/usr/bin:/bin\n";
+        assert!(extract_path_from_shell_output(output).is_none());
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    #[test]
+    fn probe_login_shell_path_falls_back_to_plain_login_flag() {
+        let shells = vec!["/usr/bin/fish".to_string(), "/bin/bash".to_string()];
+        let mut calls = Vec::new();
+        let report = probe_login_shell_path(&shells, |shell, flag| {
+            calls.push(format!("{shell} {flag}"));
+            match (shell, flag) {
+                ("/usr/bin/fish", "-ilc") => Err("unsupported option".to_string()),
+                ("/usr/bin/fish", "-lc") => Ok(
+                    b"welcome\n__AGENTDOCK_PATH_START__/home/me/.local/bin:/usr/bin__AGENTDOCK_PATH_END__"
+                        .to_vec(),
+                ),
+                _ => panic!("unexpected probe: {shell} {flag}"),
+            }
+        });
+
+        assert!(report.hydrated);
+        assert_eq!(report.shell.as_deref(), Some("/usr/bin/fish"));
+        assert_eq!(
+            report.resolved_path.as_deref(),
+            Some("/home/me/.local/bin:/usr/bin")
+        );
+        assert_eq!(calls, vec!["/usr/bin/fish -ilc", "/usr/bin/fish -lc"]);
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    #[test]
+    fn probe_login_shell_path_reports_every_failed_probe() {
+        let shells = vec!["/bin/bash".to_string()];
+        let report = probe_login_shell_path(&shells, |_, _| Ok(b"no markers".to_vec()));
+
+        assert!(!report.hydrated);
+        let reason = report.failure_reason.expect("failure reason should be set");
+        assert!(reason.contains("/bin/bash -ilc"));
+        assert!(reason.contains("/bin/bash -lc"));
+    }
+
+    #[test]
+    fn path_hydration_report_always_describes_current_state() {
+        let report = path_hydration_report();
+        assert!(report.hydrated || report.failure_reason.is_some());
+    }
 }
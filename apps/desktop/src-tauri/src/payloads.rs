@@ -1,20 +1,89 @@
 use std::collections::HashMap;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ThreadSummaryPayload {
     pub id: String,
     pub provider_id: String,
     pub project_path: String,
+    #[serde(default)]
+    pub original_cwd: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub provider_backend: Option<String>,
     pub title: String,
     pub tags: Vec<String>,
-    pub last_active_at: String,
+    pub last_active_at: i64,
     pub last_message_preview: Option<String>,
+    #[serde(default)]
+    pub is_pinned: bool,
+    /// Runtime-state fields, only filled when the list was requested with
+    /// `includeRuntimeState`.
+    #[serde(default)]
+    pub agent_answering: Option<bool>,
+    #[serde(default)]
+    pub last_event_kind: Option<String>,
+    /// Only filled when the list was requested with `includeMessageCount`.
+    #[serde(default)]
+    pub message_count: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadListResultPayload {
+    pub threads: Vec<ThreadSummaryPayload>,
+    pub providers_scanned: Vec<String>,
+    pub providers_missing: Vec<String>,
+    /// Session files left unread because they exceed the size cap.
+    #[serde(default)]
+    pub skipped_files: Vec<String>,
+    /// Providers whose scan failed; `threads` still holds the other providers' threads.
+    #[serde(default)]
+    pub provider_errors: Vec<ProviderListErrorPayload>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderListErrorPayload {
+    pub provider_id: String,
+    pub message: String,
+}
+
+/// One provider's threads from `stream_threads`, sorted like `list_threads`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadDiscoveredPayload {
+    pub threads: Vec<ThreadSummaryPayload>,
+}
+
+/// Last event of a `stream_threads` scan.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadScanCompletePayload {
+    pub thread_count: usize,
+    pub provider_errors: Vec<ProviderListErrorPayload>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadCountPayload {
+    pub total: usize,
+    pub per_provider: HashMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentProjectPayload {
+    pub project_path: String,
+    pub last_active_at: i64,
+    pub thread_count: usize,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProviderInstallStatusPayload {
     pub provider_id: String,
@@ -23,7 +92,63 @@ pub struct ProviderInstallStatusPayload {
     pub message: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetProviderAuthModeRequest {
+    pub provider_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderAuthModePayload {
+    pub provider_id: String,
+    pub auth_mode: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetProviderConfigSummaryRequest {
+    pub provider_id: String,
+}
+
+/// Resolved provider locations for diagnostics. `auth_source` names the env var or file
+/// holding credentials, never the secret itself.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderConfigSummaryPayload {
+    pub provider_id: String,
+    pub config_dir: String,
+    pub config_dir_exists: bool,
+    pub sessions_dir: String,
+    pub sessions_dir_exists: bool,
+    pub cli_binary: String,
+    pub cli_binary_path: Option<String>,
+    pub cli_binary_exists: bool,
+    pub auth_present: bool,
+    pub auth_source: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PathHydrationReportPayload {
+    pub hydrated: bool,
+    pub shell: Option<String>,
+    pub resolved_path: Option<String>,
+    pub failure_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageStatusPayload {
+    /// `appData`, `tempDir` or `inMemory`.
+    pub mode: String,
+    pub degraded: bool,
+    pub data_dir: String,
+    pub db_path: String,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CcSwitchImportedSupplierPayload {
     pub provider_id: String,
@@ -37,56 +162,227 @@ pub struct CcSwitchImportedSupplierPayload {
     pub is_current: bool,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CcSwitchImportPayload {
     pub db_path: String,
     pub suppliers: Vec<CcSwitchImportedSupplierPayload>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetClaudeThreadRuntimeStateRequest {
     pub thread_id: String,
+    #[serde(default)]
+    pub check_process: bool,
+    #[serde(default)]
+    pub project_path: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ClaudeThreadRuntimeStatePayload {
     pub agent_answering: bool,
     pub last_event_kind: Option<String>,
     pub last_event_at_ms: Option<i64>,
+    /// Only filled when the state was requested with `checkProcess`.
+    #[serde(default)]
+    pub process_running: Option<bool>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetCodexThreadRuntimeStateRequest {
     pub thread_id: String,
+    #[serde(default)]
+    pub check_process: bool,
+    #[serde(default)]
+    pub project_path: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CodexThreadRuntimeStatePayload {
     pub agent_answering: bool,
     pub last_event_kind: Option<String>,
     pub last_event_at_ms: Option<i64>,
+    /// Only filled when the state was requested with `checkProcess`.
+    #[serde(default)]
+    pub process_running: Option<bool>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCodexThreadUsageRequest {
+    pub thread_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexThreadUsagePayload {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub total_tokens: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLatestMessagePreviewRequest {
+    pub provider_id: String,
+    pub thread_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFinalAssistantMessageRequest {
+    pub provider_id: String,
+    pub thread_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadRefPayload {
+    pub provider_id: String,
+    pub thread_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareThreadsRequest {
+    pub left: ThreadRefPayload,
+    pub right: ThreadRefPayload,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadComparisonSidePayload {
+    pub provider_id: String,
+    pub thread_id: String,
+    pub final_assistant_message: Option<String>,
+    pub touched_files: Vec<String>,
+    pub message_count: usize,
+    pub duration_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadComparisonPayload {
+    pub left: ThreadComparisonSidePayload,
+    pub right: ThreadComparisonSidePayload,
+    /// Files touched by both threads, in the left thread's order.
+    pub common_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadPinRequest {
+    pub provider_id: String,
+    pub thread_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedThreadPayload {
+    pub provider_id: String,
+    pub thread_id: String,
+    pub pinned_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterRemoteDeviceRequest {
+    pub name: String,
+    pub platform: String,
+    pub public_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteDevicePayload {
+    pub id: String,
+    pub name: String,
+    pub platform: String,
+    pub public_key: String,
+    pub paired_at: String,
+    #[serde(default)]
+    pub last_seen_at: Option<String>,
+    #[serde(default)]
+    pub revoked_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRemoteSessionRequest {
+    pub device_id: String,
+    pub provider_id: String,
+    pub thread_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRemoteSessionsRequest {
+    #[serde(default)]
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteSessionPayload {
+    pub id: String,
+    pub device_id: String,
+    pub provider_id: String,
+    pub thread_id: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadExistsRequest {
+    pub provider_id: String,
+    pub thread_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetThreadEventLogRequest {
+    pub provider_id: String,
+    pub thread_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadEventPayload {
+    pub kind: String,
+    pub at_ms: Option<i64>,
+    /// Timestamp exactly as the provider recorded it, for display without conversion.
+    #[serde(default)]
+    pub timestamp_raw: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetOpenCodeThreadRuntimeStateRequest {
     pub thread_id: String,
+    #[serde(default)]
+    pub check_process: bool,
+    #[serde(default)]
+    pub project_path: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenCodeThreadRuntimeStatePayload {
     pub agent_answering: bool,
     pub last_event_kind: Option<String>,
     pub last_event_at_ms: Option<i64>,
+    /// Only filled when the state was requested with `checkProcess`.
+    #[serde(default)]
+    pub process_running: Option<bool>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenThreadInTerminalRequest {
     pub thread_id: String,
@@ -96,7 +392,7 @@ pub struct OpenThreadInTerminalRequest {
     pub project_path: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenThreadInHappyRequest {
     pub provider_id: String,
@@ -104,7 +400,7 @@ pub struct OpenThreadInHappyRequest {
     pub project_path: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenTargetStatusPayload {
     pub id: String,
@@ -115,14 +411,14 @@ pub struct OpenTargetStatusPayload {
     pub kind: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenProjectWithTargetRequest {
     pub project_path: String,
     pub target_id: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenProjectWithTargetResponse {
     pub launched: bool,
@@ -130,13 +426,13 @@ pub struct OpenProjectWithTargetResponse {
     pub command: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetProjectGitBranchRequest {
     pub project_path: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectGitBranchPayload {
     pub status: String,
@@ -144,7 +440,7 @@ pub struct ProjectGitBranchPayload {
     pub message: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenNewThreadInTerminalRequest {
     pub provider_id: String,
@@ -153,7 +449,7 @@ pub struct OpenNewThreadInTerminalRequest {
     pub project_path: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenThreadInTerminalResponse {
     pub launched: bool,
@@ -161,46 +457,96 @@ pub struct OpenThreadInTerminalResponse {
     pub terminal_app: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbeddedTerminalOutputEncoding {
+    /// Lossy UTF-8 text; invalid sequences become U+FFFD.
+    #[default]
+    Utf8,
+    /// Base64 of the raw PTY bytes.
+    Base64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct StartEmbeddedTerminalRequest {
     pub thread_id: String,
     pub provider_id: String,
     pub profile_name: Option<String>,
+    #[serde(default)]
+    pub terminal_profile: Option<String>,
     pub env: Option<HashMap<String, String>>,
     pub project_path: Option<String>,
     pub terminal_theme: Option<String>,
     pub cols: Option<u16>,
     pub rows: Option<u16>,
+    #[serde(default)]
+    pub output_encoding: Option<EmbeddedTerminalOutputEncoding>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct StartNewEmbeddedTerminalRequest {
     pub provider_id: String,
     pub profile_name: Option<String>,
+    #[serde(default)]
+    pub terminal_profile: Option<String>,
     pub env: Option<HashMap<String, String>>,
     pub project_path: Option<String>,
     pub terminal_theme: Option<String>,
     pub cols: Option<u16>,
     pub rows: Option<u16>,
+    #[serde(default)]
+    pub output_encoding: Option<EmbeddedTerminalOutputEncoding>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalProfilePayload {
+    pub name: String,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    pub working_directory: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTerminalProfileRequest {
+    pub name: String,
+}
+
+/// CLI arguments after the provider binary; `{thread_id}` and `{project_path}` are
+/// substituted with shell-quoted values.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchTemplatesPayload {
+    pub provider_id: String,
+    pub resume: String,
+    #[serde(default)]
+    pub new_thread: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchTemplatesRequest {
+    pub provider_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct StartEmbeddedTerminalResponse {
     pub session_id: String,
     pub command: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct WriteEmbeddedTerminalInputRequest {
     pub session_id: String,
     pub data: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ResizeEmbeddedTerminalRequest {
     pub session_id: String,
@@ -208,27 +554,55 @@ pub struct ResizeEmbeddedTerminalRequest {
     pub rows: u16,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CloseEmbeddedTerminalRequest {
     pub session_id: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RestartEmbeddedTerminalRequest {
+    pub session_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InterruptThreadTerminalRequest {
+    pub session_id: String,
+    #[serde(default)]
+    pub double: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct EmbeddedTerminalOutputPayload {
     pub session_id: String,
     pub data: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct EmbeddedTerminalExitPayload {
     pub session_id: String,
     pub status_code: Option<i32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddedTerminalRestartedPayload {
+    pub session_id: String,
+    pub command: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AppErrorPayload {
+    pub source: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SkillPayload {
     pub id: String,
@@ -264,7 +638,7 @@ impl From<agentdock_core::skills::Skill> for SkillPayload {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SkillRepoPayload {
     pub id: String,
@@ -288,26 +662,26 @@ impl From<agentdock_core::skills::SkillRepo> for SkillRepoPayload {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InstallSkillFromPathRequest {
     pub path: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InstallSkillFromGitRequest {
     pub url: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ToggleSkillEnabledRequest {
     pub id: String,
     pub enabled: bool,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ToggleSkillEnabledForProviderRequest {
     pub id: String,
@@ -315,13 +689,13 @@ pub struct ToggleSkillEnabledForProviderRequest {
     pub enabled: bool,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UninstallSkillRequest {
     pub id: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AddSkillRepoRequest {
     pub owner: String,
@@ -329,19 +703,19 @@ pub struct AddSkillRepoRequest {
     pub branch: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RemoveSkillRepoRequest {
     pub id: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InstallDiscoveredSkillRequest {
     pub skill: DiscoverableSkillPayload,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DiscoverableSkillPayload {
     pub key: String,
@@ -354,7 +728,7 @@ pub struct DiscoverableSkillPayload {
     pub repo_branch: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DiscoverSkillInstallProgressPayload {
     pub key: String,
@@ -362,7 +736,7 @@ pub struct DiscoverSkillInstallProgressPayload {
     pub message: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProviderSkillPayload {
     pub key: String,
@@ -373,7 +747,7 @@ pub struct ProviderSkillPayload {
     pub path: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ImportProviderSkillsRequest {
     pub skill_keys: Vec<String>,
@@ -392,7 +766,7 @@ impl From<crate::skills::ProviderSkill> for ProviderSkillPayload {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct McpServerPayload {
     pub id: String,
@@ -415,14 +789,14 @@ pub struct McpServerPayload {
     pub last_test_duration_ms: Option<i64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct McpFieldErrorPayload {
     pub field: String,
     pub message: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SaveMcpServerRequest {
     pub id: Option<String>,
@@ -440,7 +814,7 @@ pub struct SaveMcpServerRequest {
     pub clear_secret: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SaveMcpServerResponsePayload {
     pub server: Option<McpServerPayload>,
@@ -448,20 +822,20 @@ pub struct SaveMcpServerResponsePayload {
     pub message: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteMcpServerRequest {
     pub id: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ToggleMcpServerEnabledRequest {
     pub id: String,
     pub enabled: bool,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TestMcpConnectionRequest {
     pub id: Option<String>,
@@ -474,7 +848,7 @@ pub struct TestMcpConnectionRequest {
     pub secret_token: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct McpConnectionTestResultPayload {
     pub success: bool,
@@ -484,14 +858,14 @@ pub struct McpConnectionTestResultPayload {
     pub field_errors: Vec<McpFieldErrorPayload>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SyncMcpConfigsRequest {
     pub provider_ids: Option<Vec<String>>,
     pub simulate_failure_provider_id: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SyncMcpProviderResultPayload {
     pub provider_id: String,
@@ -501,7 +875,7 @@ pub struct SyncMcpProviderResultPayload {
     pub server_count: usize,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SyncMcpConfigsResponsePayload {
     pub success: bool,
@@ -510,7 +884,7 @@ pub struct SyncMcpConfigsResponsePayload {
     pub results: Vec<SyncMcpProviderResultPayload>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct McpOperationLogPayload {
     pub id: i64,
@@ -0,0 +1,170 @@
+use std::path::Path;
+
+use provider_contract::ProviderId;
+use sysinfo::System;
+
+#[derive(Debug, Clone, Default)]
+pub struct ProcessSnapshot {
+    pub name: String,
+    pub args: Vec<String>,
+    pub cwd: Option<String>,
+}
+
+/// Scans the process table for a provider CLI working on the thread. This refreshes every
+/// process on the machine, so callers only run it when explicitly asked to.
+pub fn is_thread_process_running(
+    provider_id: ProviderId,
+    thread_id: &str,
+    project_path: Option<&str>,
+) -> bool {
+    list_process_snapshots()
+        .iter()
+        .any(|process| process_matches_thread(process, provider_id, thread_id, project_path))
+}
+
+fn list_process_snapshots() -> Vec<ProcessSnapshot> {
+    let mut system = System::new();
+    system.refresh_processes();
+    system
+        .processes()
+        .values()
+        .map(|process| ProcessSnapshot {
+            name: process.name().to_string(),
+            args: process.cmd().to_vec(),
+            cwd: process.cwd().map(|cwd| cwd.to_string_lossy().to_string()),
+        })
+        .collect()
+}
+
+fn provider_binary_name(provider_id: ProviderId) -> &'static str {
+    match provider_id {
+        ProviderId::ClaudeCode => "claude",
+        ProviderId::Codex => "codex",
+        ProviderId::OpenCode => "opencode",
+    }
+}
+
+pub fn process_matches_thread(
+    process: &ProcessSnapshot,
+    provider_id: ProviderId,
+    thread_id: &str,
+    project_path: Option<&str>,
+) -> bool {
+    let thread_id = thread_id.trim();
+    if thread_id.is_empty() || !is_provider_process(process, provider_id) {
+        return false;
+    }
+
+    if process.args.iter().any(|arg| arg.contains(thread_id)) {
+        return true;
+    }
+
+    let Some(project_path) = project_path
+        .map(|path| path.trim().trim_end_matches(['/', '\\']))
+        .filter(|path| !path.is_empty() && *path != ".")
+    else {
+        return false;
+    };
+    process
+        .cwd
+        .as_deref()
+        .map(|cwd| cwd.trim_end_matches(['/', '\\']) == project_path)
+        .unwrap_or(false)
+}
+
+// CLIs installed through npm run as `node /path/to/claude ...`, so check the first couple of
+// argv entries as well as the process name.
+fn is_provider_process(process: &ProcessSnapshot, provider_id: ProviderId) -> bool {
+    let binary = provider_binary_name(provider_id);
+    std::iter::once(process.name.as_str())
+        .chain(process.args.iter().take(2).map(String::as_str))
+        .any(|candidate| executable_stem(candidate) == binary)
+}
+
+fn executable_stem(raw: &str) -> &str {
+    Path::new(raw.trim())
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(name: &str, args: &[&str], cwd: Option<&str>) -> ProcessSnapshot {
+        ProcessSnapshot {
+            name: name.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            cwd: cwd.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn process_matches_thread_by_thread_id_argument() {
+        let claude = process(
+            "node",
+            &["node", "/usr/local/bin/claude", "--resume", "session-1"],
+            None,
+        );
+
+        assert!(process_matches_thread(
+            &claude,
+            ProviderId::ClaudeCode,
+            "session-1",
+            None
+        ));
+        assert!(!process_matches_thread(
+            &claude,
+            ProviderId::ClaudeCode,
+            "session-2",
+            None
+        ));
+        assert!(!process_matches_thread(
+            &claude,
+            ProviderId::Codex,
+            "session-1",
+            None
+        ));
+    }
+
+    #[test]
+    fn process_matches_thread_by_working_directory() {
+        let codex = process("codex", &["codex"], Some("/workspace/app"));
+
+        assert!(process_matches_thread(
+            &codex,
+            ProviderId::Codex,
+            "thread-1",
+            Some("/workspace/app/")
+        ));
+        assert!(!process_matches_thread(
+            &codex,
+            ProviderId::Codex,
+            "thread-1",
+            Some(".")
+        ));
+        assert!(!process_matches_thread(
+            &codex,
+            ProviderId::Codex,
+            "thread-1",
+            None
+        ));
+    }
+
+    #[test]
+    fn process_matches_thread_ignores_unrelated_processes() {
+        let editor = process(
+            "vim",
+            &["vim", "/workspace/app/session-1.md"],
+            Some("/workspace/app"),
+        );
+
+        assert!(!process_matches_thread(
+            &editor,
+            ProviderId::OpenCode,
+            "session-1",
+            Some("/workspace/app")
+        ));
+    }
+}
@@ -1,57 +1,146 @@
-use provider_claude::ClaudeAdapter;
-use provider_codex::CodexAdapter;
 use provider_contract::{
-    ProviderAdapter, ProviderHealthCheckRequest, ProviderHealthCheckResult, ProviderHealthStatus,
+    ProviderConfigSummary, ProviderHealthCheckRequest, ProviderHealthCheckResult,
+    ProviderHealthStatus, ProviderId, ProviderResult,
 };
-use provider_opencode::OpenCodeAdapter;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
-use crate::payloads::ProviderInstallStatusPayload;
+use crate::payloads::{
+    ProviderAuthModePayload, ProviderConfigSummaryPayload, ProviderInstallStatusPayload,
+};
+use crate::provider_registry::ProviderRegistry;
+
+// The UI polls install statuses; each check spawns a `--version` subprocess.
+const HEALTH_CHECK_CACHE_TTL: Duration = Duration::from_secs(10);
+
+static HEALTH_CHECK_CACHE: OnceLock<HealthCheckCache> = OnceLock::new();
+
+type HealthCheckKey = (ProviderId, Option<String>);
+
+/// Health results keyed by provider and project path, reused while younger than the TTL.
+struct HealthCheckCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<HealthCheckKey, (Instant, ProviderHealthCheckResult)>>,
+}
+
+impl HealthCheckCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get_or_check<F>(
+        &self,
+        provider_id: ProviderId,
+        project_path: Option<&str>,
+        force: bool,
+        check: F,
+    ) -> ProviderResult<ProviderHealthCheckResult>
+    where
+        F: FnOnce() -> ProviderResult<ProviderHealthCheckResult>,
+    {
+        let key = (provider_id, project_path.map(ToString::to_string));
+        if !force {
+            if let Ok(entries) = self.entries.lock() {
+                if let Some((checked_at, result)) = entries.get(&key) {
+                    if checked_at.elapsed() < self.ttl {
+                        return Ok(result.clone());
+                    }
+                }
+            }
+        }
+
+        let result = check()?;
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(key, (Instant::now(), result.clone()));
+        }
+        Ok(result)
+    }
+}
 
+fn health_check_cache() -> &'static HealthCheckCache {
+    HEALTH_CHECK_CACHE.get_or_init(|| HealthCheckCache::new(HEALTH_CHECK_CACHE_TTL))
+}
+
+/// Pass `force` to bypass results cached within the last few seconds.
 pub fn list_provider_install_statuses(
     project_path: Option<&str>,
+    force: bool,
 ) -> Result<Vec<ProviderInstallStatusPayload>, String> {
-    let profile_name = "default".to_string();
-    let project_path_owned = project_path.map(ToString::to_string);
-
-    let codex = CodexAdapter::new()
-        .health_check(ProviderHealthCheckRequest {
-            profile_name: profile_name.clone(),
-            project_path: project_path_owned.clone(),
-        })
-        .map_err(|error| {
-            format!(
-                "Failed to check Codex health ({:?}): {}",
-                error.code, error.message
-            )
-        })?;
-    let claude = ClaudeAdapter::new()
-        .health_check(ProviderHealthCheckRequest {
-            profile_name: profile_name.clone(),
-            project_path: project_path_owned.clone(),
-        })
-        .map_err(|error| {
-            format!(
-                "Failed to check Claude Code health ({:?}): {}",
-                error.code, error.message
-            )
-        })?;
-    let opencode = OpenCodeAdapter::new()
-        .health_check(ProviderHealthCheckRequest {
-            profile_name,
-            project_path: project_path_owned,
-        })
-        .map_err(|error| {
-            format!(
-                "Failed to check OpenCode health ({:?}): {}",
-                error.code, error.message
-            )
-        })?;
-
-    Ok(vec![
-        map_provider_install_status(codex),
-        map_provider_install_status(claude),
-        map_provider_install_status(opencode),
-    ])
+    let registry = ProviderRegistry::new();
+    let mut statuses = Vec::with_capacity(registry.providers().len());
+    for provider in registry.providers() {
+        let result = health_check_cache()
+            .get_or_check(provider.provider_id(), project_path, force, || {
+                provider.adapter().health_check(ProviderHealthCheckRequest {
+                    profile_name: "default".to_string(),
+                    project_path: project_path.map(ToString::to_string),
+                })
+            })
+            .map_err(|error| {
+                format!(
+                    "Failed to check {} health ({:?}): {}",
+                    provider.label(),
+                    error.code,
+                    error.message
+                )
+            })?;
+        statuses.push(map_provider_install_status(result));
+    }
+
+    Ok(statuses)
+}
+
+pub fn get_provider_auth_mode(provider_id: ProviderId) -> Result<ProviderAuthModePayload, String> {
+    let registry = ProviderRegistry::new();
+    let provider = registry.require(provider_id)?;
+    let auth_mode = provider.auth_mode().map_err(|error| {
+        format!(
+            "Failed to detect {} auth mode ({:?}): {}",
+            provider.label(),
+            error.code,
+            error.message
+        )
+    })?;
+
+    Ok(ProviderAuthModePayload {
+        provider_id: provider_id.as_str().to_string(),
+        auth_mode: auth_mode.map(ToString::to_string),
+    })
+}
+
+pub fn get_provider_config_summary(
+    provider_id: ProviderId,
+) -> Result<ProviderConfigSummaryPayload, String> {
+    let registry = ProviderRegistry::new();
+    let provider = registry.require(provider_id)?;
+    Ok(map_provider_config_summary(
+        provider_id,
+        provider.config_summary(),
+    ))
+}
+
+fn map_provider_config_summary(
+    provider_id: ProviderId,
+    summary: ProviderConfigSummary,
+) -> ProviderConfigSummaryPayload {
+    ProviderConfigSummaryPayload {
+        provider_id: provider_id.as_str().to_string(),
+        config_dir_exists: summary.config_dir_exists(),
+        sessions_dir_exists: summary.sessions_dir_exists(),
+        config_dir: summary.config_dir.display().to_string(),
+        sessions_dir: summary.sessions_dir.display().to_string(),
+        cli_binary: summary.cli_binary,
+        cli_binary_exists: summary.cli_binary_path.is_some(),
+        cli_binary_path: summary
+            .cli_binary_path
+            .map(|path| path.display().to_string()),
+        auth_present: summary.auth_source.is_some(),
+        auth_source: summary.auth_source,
+    }
 }
 
 fn map_provider_install_status(result: ProviderHealthCheckResult) -> ProviderInstallStatusPayload {
@@ -84,16 +173,103 @@ fn health_status_as_str(status: ProviderHealthStatus) -> &'static str {
 
 #[cfg(test)]
 mod tests {
-    use provider_contract::{ProviderHealthCheckResult, ProviderHealthStatus, ProviderId};
+    use provider_contract::{
+        ProviderConfigSummary, ProviderHealthCheckResult, ProviderHealthStatus, ProviderId,
+        Timestamp,
+    };
+
+    use std::cell::Cell;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::{
+        health_status_as_str, is_cli_missing, map_provider_config_summary, HealthCheckCache,
+    };
+
+    fn healthy_result(provider_id: ProviderId) -> ProviderHealthCheckResult {
+        ProviderHealthCheckResult {
+            provider_id,
+            status: ProviderHealthStatus::Healthy,
+            checked_at: Timestamp::default(),
+            message: None,
+        }
+    }
+
+    #[test]
+    fn health_check_cache_reuses_fresh_results() {
+        let cache = HealthCheckCache::new(Duration::from_secs(60));
+        let runs = Cell::new(0);
+        let check = || {
+            runs.set(runs.get() + 1);
+            Ok(healthy_result(ProviderId::Codex))
+        };
+
+        cache
+            .get_or_check(ProviderId::Codex, Some("/workspace/a"), false, check)
+            .expect("first check should run");
+        cache
+            .get_or_check(ProviderId::Codex, Some("/workspace/a"), false, check)
+            .expect("second check should hit the cache");
+        assert_eq!(runs.get(), 1);
+
+        cache
+            .get_or_check(ProviderId::Codex, Some("/workspace/b"), false, check)
+            .expect("different project path should run");
+        cache
+            .get_or_check(ProviderId::Codex, Some("/workspace/a"), true, check)
+            .expect("forced check should run");
+        assert_eq!(runs.get(), 3);
+    }
 
-    use super::{health_status_as_str, is_cli_missing};
+    #[test]
+    fn health_check_cache_expires_after_ttl() {
+        let cache = HealthCheckCache::new(Duration::ZERO);
+        let runs = Cell::new(0);
+        let check = || {
+            runs.set(runs.get() + 1);
+            Ok(healthy_result(ProviderId::ClaudeCode))
+        };
+
+        for _ in 0..2 {
+            cache
+                .get_or_check(ProviderId::ClaudeCode, None, false, check)
+                .expect("check should run");
+        }
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn maps_config_summary_to_existence_flags() {
+        let config_dir = std::env::temp_dir();
+        let payload = map_provider_config_summary(
+            ProviderId::Codex,
+            ProviderConfigSummary {
+                config_dir: config_dir.clone(),
+                sessions_dir: config_dir.join("agentdock-missing-sessions-dir"),
+                cli_binary: "codex".to_string(),
+                cli_binary_path: Some(PathBuf::from("/usr/local/bin/codex")),
+                auth_source: Some("auth.json".to_string()),
+            },
+        );
+
+        assert_eq!(payload.provider_id, "codex");
+        assert!(payload.config_dir_exists);
+        assert!(!payload.sessions_dir_exists);
+        assert!(payload.cli_binary_exists);
+        assert_eq!(
+            payload.cli_binary_path.as_deref(),
+            Some("/usr/local/bin/codex")
+        );
+        assert!(payload.auth_present);
+        assert_eq!(payload.auth_source.as_deref(), Some("auth.json"));
+    }
 
     #[test]
     fn marks_cli_missing_when_offline_not_found_message_present() {
         let result = ProviderHealthCheckResult {
             provider_id: ProviderId::Codex,
             status: ProviderHealthStatus::Offline,
-            checked_at: "0".to_string(),
+            checked_at: Timestamp::default(),
             message: Some("Codex CLI not found in PATH: codex".to_string()),
         };
 
@@ -105,7 +281,7 @@ mod tests {
         let result = ProviderHealthCheckResult {
             provider_id: ProviderId::ClaudeCode,
             status: ProviderHealthStatus::Degraded,
-            checked_at: "0".to_string(),
+            checked_at: Timestamp::default(),
             message: Some("settings missing".to_string()),
         };
 
@@ -0,0 +1,265 @@
+use provider_claude::ClaudeAdapter;
+use provider_codex::CodexAdapter;
+use provider_contract::{
+    OversizedFile, ProviderAdapter, ProviderConfigSummary, ProviderId, ProviderResult,
+    ThreadActivity, ThreadEvent, ThreadOverviewOptions,
+};
+use provider_opencode::OpenCodeAdapter;
+
+use crate::demo_threads::demo_mode_from_env;
+use crate::payloads::ThreadSummaryPayload;
+
+/// A provider adapter plus the extension methods that sit outside `ProviderAdapter`.
+pub enum RegisteredProvider {
+    Codex(CodexAdapter),
+    Claude(ClaudeAdapter),
+    OpenCode(OpenCodeAdapter),
+}
+
+impl RegisteredProvider {
+    pub fn provider_id(&self) -> ProviderId {
+        self.adapter().provider_id()
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RegisteredProvider::Codex(_) => "Codex",
+            RegisteredProvider::Claude(_) => "Claude",
+            RegisteredProvider::OpenCode(_) => "OpenCode",
+        }
+    }
+
+    pub fn adapter(&self) -> &dyn ProviderAdapter {
+        match self {
+            RegisteredProvider::Codex(adapter) => adapter,
+            RegisteredProvider::Claude(adapter) => adapter,
+            RegisteredProvider::OpenCode(adapter) => adapter,
+        }
+    }
+
+    pub fn list_thread_overviews(
+        &self,
+        project_path: Option<&str>,
+        options: ThreadOverviewOptions,
+    ) -> ProviderResult<Vec<ThreadSummaryPayload>> {
+        macro_rules! map_overviews {
+            ($overviews:expr) => {
+                $overviews
+                    .into_iter()
+                    .map(|overview| ThreadSummaryPayload {
+                        id: overview.summary.id,
+                        provider_id: overview.summary.provider_id.as_str().to_string(),
+                        project_path: overview.summary.project_path,
+                        original_cwd: overview.summary.original_cwd,
+                        model: overview.summary.model,
+                        provider_backend: overview.summary.provider_backend,
+                        title: overview.summary.title,
+                        tags: overview.summary.tags,
+                        last_active_at: overview.summary.last_active_at.as_millis(),
+                        last_message_preview: overview.last_message_preview,
+                        is_pinned: false,
+                        agent_answering: overview
+                            .runtime_state
+                            .as_ref()
+                            .map(|state| state.agent_answering),
+                        last_event_kind: overview
+                            .runtime_state
+                            .and_then(|state| state.last_event_kind),
+                        message_count: overview.message_count,
+                    })
+                    .collect()
+            };
+        }
+
+        Ok(match self {
+            RegisteredProvider::Codex(adapter) => {
+                map_overviews!(adapter.list_thread_overviews_with_options(project_path, options)?)
+            }
+            RegisteredProvider::Claude(adapter) => {
+                map_overviews!(adapter.list_thread_overviews_with_options(project_path, options)?)
+            }
+            RegisteredProvider::OpenCode(adapter) => {
+                map_overviews!(adapter.list_thread_overviews_with_options(project_path, options)?)
+            }
+        })
+    }
+
+    /// `None` for providers without auth detection yet.
+    pub fn auth_mode(&self) -> ProviderResult<Option<&'static str>> {
+        match self {
+            RegisteredProvider::Claude(adapter) => Ok(Some(adapter.get_auth_mode()?.as_str())),
+            RegisteredProvider::Codex(_) | RegisteredProvider::OpenCode(_) => Ok(None),
+        }
+    }
+
+    pub fn config_summary(&self) -> ProviderConfigSummary {
+        match self {
+            RegisteredProvider::Codex(adapter) => adapter.config_summary(),
+            RegisteredProvider::Claude(adapter) => adapter.config_summary(),
+            RegisteredProvider::OpenCode(adapter) => adapter.config_summary(),
+        }
+    }
+
+    pub fn get_latest_message_preview(&self, thread_id: &str) -> ProviderResult<Option<String>> {
+        match self {
+            RegisteredProvider::Codex(adapter) => adapter.get_latest_message_preview(thread_id),
+            RegisteredProvider::Claude(adapter) => adapter.get_latest_message_preview(thread_id),
+            RegisteredProvider::OpenCode(adapter) => adapter.get_latest_message_preview(thread_id),
+        }
+    }
+
+    pub fn get_final_assistant_message(&self, thread_id: &str) -> ProviderResult<Option<String>> {
+        match self {
+            RegisteredProvider::Codex(adapter) => adapter.get_final_assistant_message(thread_id),
+            RegisteredProvider::Claude(adapter) => adapter.get_final_assistant_message(thread_id),
+            RegisteredProvider::OpenCode(adapter) => adapter.get_final_assistant_message(thread_id),
+        }
+    }
+
+    pub fn get_thread_activity(&self, thread_id: &str) -> ProviderResult<ThreadActivity> {
+        match self {
+            RegisteredProvider::Codex(adapter) => adapter.get_thread_activity(thread_id),
+            RegisteredProvider::Claude(adapter) => adapter.get_thread_activity(thread_id),
+            RegisteredProvider::OpenCode(adapter) => adapter.get_thread_activity(thread_id),
+        }
+    }
+
+    pub fn thread_exists(&self, thread_id: &str) -> bool {
+        match self {
+            RegisteredProvider::Codex(adapter) => adapter.thread_exists(thread_id),
+            RegisteredProvider::Claude(adapter) => adapter.thread_exists(thread_id),
+            RegisteredProvider::OpenCode(adapter) => adapter.thread_exists(thread_id),
+        }
+    }
+
+    pub fn oversized_session_files(&self) -> Vec<OversizedFile> {
+        match self {
+            RegisteredProvider::Codex(adapter) => adapter.oversized_session_files(),
+            RegisteredProvider::Claude(adapter) => adapter.oversized_session_files(),
+            RegisteredProvider::OpenCode(adapter) => adapter.oversized_session_files(),
+        }
+    }
+
+    pub fn get_thread_event_log(
+        &self,
+        thread_id: &str,
+        limit: usize,
+    ) -> ProviderResult<Vec<ThreadEvent>> {
+        match self {
+            RegisteredProvider::Codex(adapter) => adapter.get_thread_event_log(thread_id, limit),
+            RegisteredProvider::Claude(adapter) => adapter.get_thread_event_log(thread_id, limit),
+            RegisteredProvider::OpenCode(adapter) => adapter.get_thread_event_log(thread_id, limit),
+        }
+    }
+}
+
+pub struct ProviderRegistry {
+    providers: Vec<RegisteredProvider>,
+    demo_mode: bool,
+}
+
+impl ProviderRegistry {
+    pub fn new() -> Self {
+        Self::from_providers(vec![
+            RegisteredProvider::Codex(CodexAdapter::new()),
+            RegisteredProvider::Claude(ClaudeAdapter::new()),
+            RegisteredProvider::OpenCode(OpenCodeAdapter::new()),
+        ])
+        .with_demo_mode(demo_mode_from_env())
+    }
+
+    pub fn from_providers(providers: Vec<RegisteredProvider>) -> Self {
+        Self {
+            providers,
+            demo_mode: false,
+        }
+    }
+
+    /// Serve the bundled demo threads when no provider has any sessions.
+    pub fn with_demo_mode(mut self, enabled: bool) -> Self {
+        self.demo_mode = enabled;
+        self
+    }
+
+    pub fn demo_mode(&self) -> bool {
+        self.demo_mode
+    }
+
+    pub fn providers(&self) -> &[RegisteredProvider] {
+        &self.providers
+    }
+
+    pub fn provider_ids(&self) -> Vec<ProviderId> {
+        self.providers
+            .iter()
+            .map(RegisteredProvider::provider_id)
+            .collect()
+    }
+
+    pub fn get(&self, provider_id: ProviderId) -> Option<&RegisteredProvider> {
+        self.providers
+            .iter()
+            .find(|provider| provider.provider_id() == provider_id)
+    }
+
+    pub fn require(&self, provider_id: ProviderId) -> Result<&RegisteredProvider, String> {
+        self.get(provider_id)
+            .ok_or_else(|| format!("Provider is not registered: {}", provider_id.as_str()))
+    }
+}
+
+impl Default for ProviderRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_registry_lists_every_registered_provider() {
+        let registry = ProviderRegistry::new();
+
+        assert_eq!(
+            registry.provider_ids(),
+            vec![
+                ProviderId::Codex,
+                ProviderId::ClaudeCode,
+                ProviderId::OpenCode
+            ]
+        );
+        for provider in registry.providers() {
+            assert_eq!(provider.adapter().provider_id(), provider.provider_id());
+        }
+    }
+
+    #[test]
+    fn provider_registry_dispatches_by_provider_id() {
+        let registry = ProviderRegistry::new();
+
+        let claude = registry
+            .get(ProviderId::ClaudeCode)
+            .expect("claude should be registered");
+        assert!(matches!(claude, RegisteredProvider::Claude(_)));
+        assert_eq!(claude.label(), "Claude");
+        assert!(matches!(
+            registry.get(ProviderId::OpenCode),
+            Some(RegisteredProvider::OpenCode(_))
+        ));
+    }
+
+    #[test]
+    fn provider_registry_rejects_unregistered_provider() {
+        let registry =
+            ProviderRegistry::from_providers(vec![RegisteredProvider::Codex(CodexAdapter::new())]);
+
+        assert!(registry.get(ProviderId::ClaudeCode).is_none());
+        let error = registry
+            .require(ProviderId::ClaudeCode)
+            .err()
+            .expect("unregistered provider should fail");
+        assert_eq!(error, "Provider is not registered: claude_code");
+    }
+}
@@ -0,0 +1,68 @@
+use agentdock_core::db::{self, RemoteDevice, RemoteSession};
+use provider_contract::ProviderId;
+
+use crate::app_storage;
+use crate::payloads::{RemoteDevicePayload, RemoteSessionPayload};
+
+pub fn register_remote_device(
+    app: &tauri::AppHandle,
+    name: &str,
+    platform: &str,
+    public_key: &str,
+) -> Result<RemoteDevicePayload, String> {
+    let connection = app_storage::open_db_connection(app)?;
+    db::register_remote_device(&connection, name, platform, public_key)
+        .map(map_remote_device)
+        .map_err(|error| format!("Failed to register remote device: {error}"))
+}
+
+pub fn list_remote_devices(app: &tauri::AppHandle) -> Result<Vec<RemoteDevicePayload>, String> {
+    let connection = app_storage::open_db_connection(app)?;
+    let devices = db::list_remote_devices(&connection)
+        .map_err(|error| format!("Failed to load remote devices: {error}"))?;
+    Ok(devices.into_iter().map(map_remote_device).collect())
+}
+
+pub fn create_remote_session(
+    app: &tauri::AppHandle,
+    device_id: &str,
+    provider_id: ProviderId,
+    thread_id: &str,
+) -> Result<RemoteSessionPayload, String> {
+    let connection = app_storage::open_db_connection(app)?;
+    db::create_remote_session(&connection, device_id, provider_id, thread_id)
+        .map(map_remote_session)
+        .map_err(|error| format!("Failed to create remote session: {error}"))
+}
+
+pub fn list_remote_sessions(
+    app: &tauri::AppHandle,
+    device_id: Option<&str>,
+) -> Result<Vec<RemoteSessionPayload>, String> {
+    let connection = app_storage::open_db_connection(app)?;
+    let sessions = db::list_remote_sessions(&connection, device_id)
+        .map_err(|error| format!("Failed to load remote sessions: {error}"))?;
+    Ok(sessions.into_iter().map(map_remote_session).collect())
+}
+
+fn map_remote_device(device: RemoteDevice) -> RemoteDevicePayload {
+    RemoteDevicePayload {
+        id: device.id,
+        name: device.name,
+        platform: device.platform,
+        public_key: device.public_key,
+        paired_at: device.paired_at,
+        last_seen_at: device.last_seen_at,
+        revoked_at: device.revoked_at,
+    }
+}
+
+fn map_remote_session(session: RemoteSession) -> RemoteSessionPayload {
+    RemoteSessionPayload {
+        id: session.id,
+        device_id: session.device_id,
+        provider_id: session.provider_id,
+        thread_id: session.thread_id,
+        created_at: session.created_at,
+    }
+}
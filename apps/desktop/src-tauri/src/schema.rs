@@ -0,0 +1,328 @@
+use schemars::schema_for;
+use serde_json::{json, Map, Value};
+
+use crate::payloads;
+
+// Every payload crossing the Tauri command boundary. Add new payloads here so their
+// schema is exported and covered by the round-trip tests below.
+macro_rules! for_each_payload {
+    ($callback:ident) => {
+        $callback!(
+            ThreadSummaryPayload,
+            ThreadListResultPayload,
+            ProviderListErrorPayload,
+            ThreadDiscoveredPayload,
+            ThreadScanCompletePayload,
+            ThreadCountPayload,
+            RecentProjectPayload,
+            ProviderInstallStatusPayload,
+            GetProviderAuthModeRequest,
+            ProviderAuthModePayload,
+            GetProviderConfigSummaryRequest,
+            ProviderConfigSummaryPayload,
+            PathHydrationReportPayload,
+            StorageStatusPayload,
+            CcSwitchImportedSupplierPayload,
+            CcSwitchImportPayload,
+            GetClaudeThreadRuntimeStateRequest,
+            ClaudeThreadRuntimeStatePayload,
+            GetCodexThreadRuntimeStateRequest,
+            CodexThreadRuntimeStatePayload,
+            GetCodexThreadUsageRequest,
+            CodexThreadUsagePayload,
+            GetLatestMessagePreviewRequest,
+            GetFinalAssistantMessageRequest,
+            ThreadPinRequest,
+            ThreadRefPayload,
+            CompareThreadsRequest,
+            ThreadComparisonSidePayload,
+            ThreadComparisonPayload,
+            PinnedThreadPayload,
+            RegisterRemoteDeviceRequest,
+            RemoteDevicePayload,
+            CreateRemoteSessionRequest,
+            ListRemoteSessionsRequest,
+            RemoteSessionPayload,
+            ThreadExistsRequest,
+            GetThreadEventLogRequest,
+            ThreadEventPayload,
+            GetOpenCodeThreadRuntimeStateRequest,
+            OpenCodeThreadRuntimeStatePayload,
+            OpenThreadInTerminalRequest,
+            OpenThreadInHappyRequest,
+            OpenTargetStatusPayload,
+            OpenProjectWithTargetRequest,
+            OpenProjectWithTargetResponse,
+            GetProjectGitBranchRequest,
+            ProjectGitBranchPayload,
+            OpenNewThreadInTerminalRequest,
+            OpenThreadInTerminalResponse,
+            StartEmbeddedTerminalRequest,
+            StartNewEmbeddedTerminalRequest,
+            TerminalProfilePayload,
+            DeleteTerminalProfileRequest,
+            LaunchTemplatesPayload,
+            LaunchTemplatesRequest,
+            StartEmbeddedTerminalResponse,
+            WriteEmbeddedTerminalInputRequest,
+            ResizeEmbeddedTerminalRequest,
+            CloseEmbeddedTerminalRequest,
+            RestartEmbeddedTerminalRequest,
+            InterruptThreadTerminalRequest,
+            EmbeddedTerminalOutputPayload,
+            EmbeddedTerminalExitPayload,
+            EmbeddedTerminalRestartedPayload,
+            AppErrorPayload,
+            SkillPayload,
+            SkillRepoPayload,
+            InstallSkillFromPathRequest,
+            InstallSkillFromGitRequest,
+            ToggleSkillEnabledRequest,
+            ToggleSkillEnabledForProviderRequest,
+            UninstallSkillRequest,
+            AddSkillRepoRequest,
+            RemoveSkillRepoRequest,
+            InstallDiscoveredSkillRequest,
+            DiscoverableSkillPayload,
+            DiscoverSkillInstallProgressPayload,
+            ProviderSkillPayload,
+            ImportProviderSkillsRequest,
+            McpServerPayload,
+            McpFieldErrorPayload,
+            SaveMcpServerRequest,
+            SaveMcpServerResponsePayload,
+            DeleteMcpServerRequest,
+            ToggleMcpServerEnabledRequest,
+            TestMcpConnectionRequest,
+            McpConnectionTestResultPayload,
+            SyncMcpConfigsRequest,
+            SyncMcpProviderResultPayload,
+            SyncMcpConfigsResponsePayload,
+            McpOperationLogPayload,
+        )
+    };
+}
+
+/// JSON Schema (draft-07) for every command payload, keyed by Rust type name.
+/// Suitable as input for `json-schema-to-typescript`.
+pub fn payload_schema_document() -> Value {
+    let mut definitions = Map::new();
+
+    macro_rules! insert_schemas {
+        ($($name:ident),* $(,)?) => {
+            $(
+                definitions.insert(
+                    stringify!($name).to_string(),
+                    serde_json::to_value(schema_for!(payloads::$name)).unwrap_or(Value::Null),
+                );
+            )*
+        };
+    }
+    for_each_payload!(insert_schemas);
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "AgentDockCommandPayloads",
+        "definitions": definitions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use schemars::JsonSchema;
+    use serde::de::DeserializeOwned;
+    use serde::Serialize;
+
+    fn resolve<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+        match schema.get("$ref").and_then(Value::as_str) {
+            Some(reference) => {
+                let name = reference.trim_start_matches("#/definitions/");
+                &root["definitions"][name]
+            }
+            None => schema,
+        }
+    }
+
+    fn schema_types(schema: &Value) -> Vec<&str> {
+        match schema.get("type") {
+            Some(Value::String(kind)) => vec![kind.as_str()],
+            Some(Value::Array(kinds)) => kinds.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn sample_value(schema: &Value, root: &Value) -> Value {
+        let schema = resolve(schema, root);
+        if let Some(first) = schema
+            .get("enum")
+            .and_then(Value::as_array)
+            .and_then(|values| values.first())
+        {
+            return first.clone();
+        }
+        for key in ["anyOf", "allOf", "oneOf"] {
+            if let Some(Value::Array(variants)) = schema.get(key) {
+                let variant = variants
+                    .iter()
+                    .find(|variant| schema_types(variant) != vec!["null"])
+                    .expect("schema variant should be non-null");
+                return sample_value(variant, root);
+            }
+        }
+
+        let kind = schema_types(schema)
+            .into_iter()
+            .find(|kind| *kind != "null")
+            .unwrap_or("object");
+        match kind {
+            "string" => json!("sample"),
+            "boolean" => json!(true),
+            "integer" | "number" => json!(1),
+            "array" => json!([sample_value(&schema["items"], root)]),
+            _ => {
+                let mut object = Map::new();
+                if let Some(Value::Object(properties)) = schema.get("properties") {
+                    for (name, property) in properties {
+                        object.insert(name.clone(), sample_value(property, root));
+                    }
+                }
+                if let Some(additional) = schema
+                    .get("additionalProperties")
+                    .filter(|value| value.is_object())
+                {
+                    object.insert("KEY".to_string(), sample_value(additional, root));
+                }
+                Value::Object(object)
+            }
+        }
+    }
+
+    fn validate(value: &Value, schema: &Value, root: &Value, path: &str) -> Result<(), String> {
+        let schema = resolve(schema, root);
+        if let Some(Value::Array(allowed)) = schema.get("enum") {
+            if !allowed.contains(value) {
+                return Err(format!("{path}: {value} is not one of {allowed:?}"));
+            }
+        }
+        if let Some(Value::Array(variants)) = schema.get("anyOf").or_else(|| schema.get("oneOf")) {
+            return if variants
+                .iter()
+                .any(|variant| validate(value, variant, root, path).is_ok())
+            {
+                Ok(())
+            } else {
+                Err(format!("{path}: no schema variant matched"))
+            };
+        }
+        if let Some(Value::Array(variants)) = schema.get("allOf") {
+            for variant in variants {
+                validate(value, variant, root, path)?;
+            }
+            return Ok(());
+        }
+
+        let kinds = schema_types(schema);
+        let matches_kind = kinds.is_empty()
+            || kinds.iter().any(|kind| match *kind {
+                "null" => value.is_null(),
+                "string" => value.is_string(),
+                "boolean" => value.is_boolean(),
+                "integer" => value.is_i64() || value.is_u64(),
+                "number" => value.is_number(),
+                "array" => value.is_array(),
+                "object" => value.is_object(),
+                _ => false,
+            });
+        if !matches_kind {
+            return Err(format!("{path}: expected {kinds:?}, got {value}"));
+        }
+
+        match value {
+            Value::Array(items) => {
+                if let Some(item_schema) = schema.get("items") {
+                    for (index, item) in items.iter().enumerate() {
+                        validate(item, item_schema, root, &format!("{path}[{index}]"))?;
+                    }
+                }
+            }
+            Value::Object(object) => {
+                let properties = schema.get("properties").and_then(Value::as_object);
+                if let Some(Value::Array(required)) = schema.get("required") {
+                    for name in required.iter().filter_map(Value::as_str) {
+                        if !object.contains_key(name) {
+                            return Err(format!("{path}: missing required field `{name}`"));
+                        }
+                    }
+                }
+                for (name, field) in object {
+                    let field_path = format!("{path}.{name}");
+                    match properties.and_then(|properties| properties.get(name)) {
+                        Some(property) => validate(field, property, root, &field_path)?,
+                        None => match schema.get("additionalProperties") {
+                            Some(additional) if additional.is_object() => {
+                                validate(field, additional, root, &field_path)?
+                            }
+                            _ if properties.is_none() => {}
+                            _ => return Err(format!("{field_path}: unknown field")),
+                        },
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn assert_payload_round_trip<T>(name: &str)
+    where
+        T: Serialize + DeserializeOwned + JsonSchema,
+    {
+        let root = serde_json::to_value(schema_for!(T)).expect("schema should serialize");
+        let sample = sample_value(&root, &root);
+
+        let typed: T = serde_json::from_value(sample.clone()).unwrap_or_else(|error| {
+            panic!("{name} should deserialize from schema sample: {error}")
+        });
+        let serialized = serde_json::to_value(&typed).expect("payload should serialize");
+
+        assert_eq!(serialized, sample, "{name} should round-trip unchanged");
+        if let Err(error) = validate(&serialized, &root, &root, name) {
+            panic!("{name} does not match its schema: {error}");
+        }
+    }
+
+    #[test]
+    fn every_payload_round_trips_and_matches_schema() {
+        macro_rules! check_round_trips {
+            ($($name:ident),* $(,)?) => {
+                $( assert_payload_round_trip::<payloads::$name>(stringify!($name)); )*
+            };
+        }
+        for_each_payload!(check_round_trips);
+    }
+
+    #[test]
+    fn validate_rejects_renamed_fields() {
+        let root = serde_json::to_value(schema_for!(payloads::ThreadSummaryPayload))
+            .expect("schema should serialize");
+        let mut sample = sample_value(&root, &root);
+        let object = sample.as_object_mut().expect("sample should be an object");
+        let value = object.remove("projectPath").expect("field should exist");
+        object.insert("project_path".to_string(), value);
+
+        let error = validate(&sample, &root, &root, "ThreadSummaryPayload")
+            .expect_err("renamed field should fail validation");
+        assert!(error.contains("projectPath"));
+    }
+
+    #[test]
+    fn payload_schema_document_lists_camel_case_fields() {
+        let document = payload_schema_document();
+        let thread_schema = &document["definitions"]["ThreadSummaryPayload"];
+
+        assert!(thread_schema["properties"]["lastActiveAt"].is_object());
+        assert!(document["definitions"]["McpOperationLogPayload"].is_object());
+    }
+}
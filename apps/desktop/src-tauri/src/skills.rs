@@ -4,7 +4,6 @@ use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 use serde::{Deserialize, Serialize};
-use tauri::Manager;
 
 use agentdock_core::skills::{
     create_skill_from_git_metadata, create_skill_from_metadata, delete_skill, delete_skill_repo,
@@ -13,6 +12,8 @@ use agentdock_core::skills::{
     Skill, SkillRepo,
 };
 
+use crate::app_storage;
+
 /// Discoverable skill from a repository
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -51,12 +52,8 @@ static CODEX_SKILLS_MIGRATION_ONCE: OnceLock<()> = OnceLock::new();
 
 impl SkillsContext {
     pub fn from_app_handle(app: &tauri::AppHandle) -> Result<Self, String> {
-        let app_data_dir = app
-            .path()
-            .app_data_dir()
-            .map_err(|e| format!("Failed to get app data directory: {e}"))?;
-
-        let db_path = app_data_dir.join("agentdock.db");
+        let app_data_dir = app_storage::data_dir(app)?;
+        let db_path = app_storage::db_path(app)?;
         let skills_dir = app_data_dir.join("skills");
 
         fs::create_dir_all(&skills_dir)
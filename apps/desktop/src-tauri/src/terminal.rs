@@ -1,43 +1,128 @@
+use agentdock_core::config::{
+    self, ConfigError, LaunchTemplates, TerminalProfile, PROJECT_PATH_PLACEHOLDER,
+    THREAD_ID_PLACEHOLDER,
+};
+use base64::prelude::{Engine as _, BASE64_STANDARD};
 use portable_pty::{native_pty_system, CommandBuilder, MasterPty, PtySize};
-use provider_contract::ProviderId;
+use provider_contract::{ProviderConfigSummary, ProviderId};
 use std::collections::HashMap;
-use std::io::{Read, Write};
+use std::fmt;
+use std::io::{self, Read, Write};
 use std::process::Command;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
 use std::time::Duration;
 use tauri::Emitter;
 
+use crate::app_events::{app_error_payload, emit_app_error};
+use crate::app_storage;
 use crate::command_utils::command_available;
+use crate::demo_threads::ensure_not_demo_thread;
 use crate::payloads::{
-    EmbeddedTerminalExitPayload, EmbeddedTerminalOutputPayload, OpenThreadInTerminalResponse,
-    StartEmbeddedTerminalResponse,
+    AppErrorPayload, EmbeddedTerminalExitPayload, EmbeddedTerminalOutputEncoding,
+    EmbeddedTerminalOutputPayload, EmbeddedTerminalRestartedPayload, LaunchTemplatesPayload,
+    OpenThreadInTerminalResponse, StartEmbeddedTerminalResponse, TerminalProfilePayload,
 };
+use crate::provider_registry::ProviderRegistry;
+
+const TERMINAL_OUTPUT_ERROR_SOURCE: &str = "terminal-output";
+const TERMINAL_EXIT_ERROR_SOURCE: &str = "terminal-exit";
+const MAX_EMBEDDED_TERMINAL_SESSIONS_ENV: &str = "AGENTDOCK_MAX_EMBEDDED_TERMINALS";
+const DEFAULT_MAX_EMBEDDED_TERMINAL_SESSIONS: usize = 32;
+/// Set to `1`/`true` to launch provider CLIs by absolute path instead of relying on the
+/// terminal shell's PATH.
+const RESOLVE_CLI_PATHS_ENV: &str = "AGENTDOCK_RESOLVE_CLI_PATHS";
+// Ctrl-C as the PTY line discipline sees it.
+const TERMINAL_INTERRUPT_SEQUENCE: &[u8] = b"\x03";
+const DOUBLE_INTERRUPT_DELAY: Duration = Duration::from_millis(500);
+// Linux reports EIO on the PTY master once the child side has closed.
+const PTY_CLOSED_OS_ERROR: i32 = 5;
+
+/// Rejected before a project path is embedded in a shell command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnsafeProjectPath {
+    ControlCharacter(char),
+}
+
+impl fmt::Display for UnsafeProjectPath {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnsafeProjectPath::ControlCharacter(character) => write!(
+                formatter,
+                "Project path contains a control character (U+{:04X})",
+                u32::from(*character)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TooManyEmbeddedTerminals {
+    limit: usize,
+}
+
+impl fmt::Display for TooManyEmbeddedTerminals {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "Too many embedded terminal sessions (limit {}); close one before starting another",
+            self.limit
+        )
+    }
+}
+
+/// What an embedded session was spawned with, kept so it can be restarted.
+#[derive(Clone)]
+struct EmbeddedTerminalLaunch {
+    command: String,
+    terminal_profile: Option<TerminalProfile>,
+    terminal_theme: Option<String>,
+    output_encoding: EmbeddedTerminalOutputEncoding,
+}
 
 struct EmbeddedTerminalSession {
     child: Mutex<Box<dyn portable_pty::Child + Send>>,
     stdin: Mutex<Box<dyn Write + Send>>,
     master: Mutex<Box<dyn MasterPty + Send>>,
+    launch: EmbeddedTerminalLaunch,
+    /// Set when a restart replaced this session, so its exit is not reported.
+    superseded: AtomicBool,
 }
 
-static EMBEDDED_TERMINAL_SESSIONS: OnceLock<Mutex<HashMap<String, Arc<EmbeddedTerminalSession>>>> =
-    OnceLock::new();
+#[derive(Default)]
+struct EmbeddedTerminalSessions {
+    active: HashMap<String, Arc<EmbeddedTerminalSession>>,
+    /// Slots held by starts whose PTY is still being spawned.
+    reserved: usize,
+}
+
+/// A slot counted against the session limit until the spawned session is inserted.
+/// Dropping it without `fill` gives the slot back.
+struct EmbeddedSessionSlot;
+
+static EMBEDDED_TERMINAL_SESSIONS: OnceLock<Mutex<EmbeddedTerminalSessions>> = OnceLock::new();
 static EMBEDDED_TERMINAL_COUNTER: AtomicU64 = AtomicU64::new(1);
 
 pub fn open_thread_in_terminal(
+    app: &tauri::AppHandle,
     provider_id: ProviderId,
     thread_id: &str,
     profile_name: Option<&str>,
     env: Option<HashMap<String, String>>,
     project_path: Option<&str>,
 ) -> Result<OpenThreadInTerminalResponse, String> {
+    ensure_not_demo_thread(thread_id, ProviderRegistry::new().demo_mode())?;
+    validate_project_path(project_path).map_err(|error| error.to_string())?;
+    let templates = load_launch_templates(app, provider_id)?;
     let command = build_resume_command_from_parts(
         provider_id,
         thread_id,
         profile_name,
         env.as_ref(),
         project_path,
+        provider_cli_binary(provider_id).as_deref(),
+        &templates,
     );
     launch_in_terminal(&command)?;
     Ok(OpenThreadInTerminalResponse {
@@ -48,13 +133,22 @@ pub fn open_thread_in_terminal(
 }
 
 pub fn open_new_thread_in_terminal(
+    app: &tauri::AppHandle,
     provider_id: ProviderId,
     profile_name: Option<&str>,
     env: Option<HashMap<String, String>>,
     project_path: Option<&str>,
 ) -> Result<OpenThreadInTerminalResponse, String> {
-    let command =
-        build_new_thread_command_from_parts(provider_id, profile_name, env.as_ref(), project_path);
+    validate_project_path(project_path).map_err(|error| error.to_string())?;
+    let templates = load_launch_templates(app, provider_id)?;
+    let command = build_new_thread_command_from_parts(
+        provider_id,
+        profile_name,
+        env.as_ref(),
+        project_path,
+        provider_cli_binary(provider_id).as_deref(),
+        &templates,
+    );
     launch_in_terminal(&command)?;
     Ok(OpenThreadInTerminalResponse {
         launched: true,
@@ -68,7 +162,11 @@ pub fn open_thread_in_happy(
     thread_id: Option<&str>,
     project_path: Option<&str>,
 ) -> Result<OpenThreadInTerminalResponse, String> {
+    if let Some(thread_id) = thread_id {
+        ensure_not_demo_thread(thread_id, ProviderRegistry::new().demo_mode())?;
+    }
     ensure_command_available("happy", "Happy CLI")?;
+    validate_project_path(project_path).map_err(|error| error.to_string())?;
     let command = build_happy_command_from_parts(provider_id, thread_id, project_path)?;
     launch_in_terminal(&command)?;
     Ok(OpenThreadInTerminalResponse {
@@ -87,29 +185,50 @@ pub fn start_embedded_terminal(
     provider_id: ProviderId,
     thread_id: &str,
     profile_name: Option<&str>,
+    terminal_profile: Option<&str>,
     env: Option<HashMap<String, String>>,
     project_path: Option<&str>,
     terminal_theme: Option<&str>,
     cols: Option<u16>,
     rows: Option<u16>,
+    output_encoding: Option<EmbeddedTerminalOutputEncoding>,
 ) -> Result<StartEmbeddedTerminalResponse, String> {
+    ensure_not_demo_thread(thread_id, ProviderRegistry::new().demo_mode())?;
+    let slot = EmbeddedSessionSlot::reserve()?;
     let cols = clamp_terminal_cols(cols);
     let rows = clamp_terminal_rows(rows);
+    let terminal_profile = resolve_terminal_profile(&app, terminal_profile)?;
+    let project_path = profile_project_path(terminal_profile.as_ref(), project_path);
+    validate_project_path(project_path).map_err(|error| error.to_string())?;
+    let templates = load_launch_templates(&app, provider_id)?;
     let command = build_resume_command_from_parts(
         provider_id,
         thread_id,
         profile_name,
         env.as_ref(),
         project_path,
+        provider_cli_binary(provider_id).as_deref(),
+        &templates,
     );
     let session_id = next_embedded_terminal_session_id();
-    let (reader, session) = create_embedded_session(&command, terminal_theme, cols, rows)?;
-    terminal_sessions()
-        .lock()
-        .map_err(|_| "Embedded terminal sessions lock poisoned".to_string())?
-        .insert(session_id.clone(), Arc::clone(&session));
-
-    spawn_terminal_output_reader(app.clone(), session_id.clone(), reader);
+    let (reader, session) = create_embedded_session(
+        EmbeddedTerminalLaunch {
+            command: command.clone(),
+            terminal_profile,
+            terminal_theme: terminal_theme.map(ToString::to_string),
+            output_encoding: output_encoding.unwrap_or_default(),
+        },
+        cols,
+        rows,
+    )?;
+    slot.fill(session_id.clone(), Arc::clone(&session))?;
+
+    spawn_terminal_output_reader(
+        app.clone(),
+        session_id.clone(),
+        reader,
+        session.launch.output_encoding,
+    );
     spawn_terminal_exit_watcher(app, session_id.clone(), session);
 
     Ok(StartEmbeddedTerminalResponse {
@@ -122,24 +241,48 @@ pub fn start_new_embedded_terminal(
     app: tauri::AppHandle,
     provider_id: ProviderId,
     profile_name: Option<&str>,
+    terminal_profile: Option<&str>,
     env: Option<HashMap<String, String>>,
     project_path: Option<&str>,
     terminal_theme: Option<&str>,
     cols: Option<u16>,
     rows: Option<u16>,
+    output_encoding: Option<EmbeddedTerminalOutputEncoding>,
 ) -> Result<StartEmbeddedTerminalResponse, String> {
+    let slot = EmbeddedSessionSlot::reserve()?;
     let cols = clamp_terminal_cols(cols);
     let rows = clamp_terminal_rows(rows);
-    let command =
-        build_new_thread_command_from_parts(provider_id, profile_name, env.as_ref(), project_path);
+    let terminal_profile = resolve_terminal_profile(&app, terminal_profile)?;
+    let project_path = profile_project_path(terminal_profile.as_ref(), project_path);
+    validate_project_path(project_path).map_err(|error| error.to_string())?;
+    let templates = load_launch_templates(&app, provider_id)?;
+    let command = build_new_thread_command_from_parts(
+        provider_id,
+        profile_name,
+        env.as_ref(),
+        project_path,
+        provider_cli_binary(provider_id).as_deref(),
+        &templates,
+    );
     let session_id = next_embedded_terminal_session_id();
-    let (reader, session) = create_embedded_session(&command, terminal_theme, cols, rows)?;
-    terminal_sessions()
-        .lock()
-        .map_err(|_| "Embedded terminal sessions lock poisoned".to_string())?
-        .insert(session_id.clone(), Arc::clone(&session));
-
-    spawn_terminal_output_reader(app.clone(), session_id.clone(), reader);
+    let (reader, session) = create_embedded_session(
+        EmbeddedTerminalLaunch {
+            command: command.clone(),
+            terminal_profile,
+            terminal_theme: terminal_theme.map(ToString::to_string),
+            output_encoding: output_encoding.unwrap_or_default(),
+        },
+        cols,
+        rows,
+    )?;
+    slot.fill(session_id.clone(), Arc::clone(&session))?;
+
+    spawn_terminal_output_reader(
+        app.clone(),
+        session_id.clone(),
+        reader,
+        session.launch.output_encoding,
+    );
     spawn_terminal_exit_watcher(app, session_id.clone(), session);
 
     Ok(StartEmbeddedTerminalResponse {
@@ -148,40 +291,97 @@ pub fn start_new_embedded_terminal(
     })
 }
 
-pub fn write_embedded_terminal_input(session_id: &str, data: &str) -> Result<(), String> {
-    let session = {
-        let sessions = terminal_sessions()
-            .lock()
-            .map_err(|_| "Embedded terminal sessions lock poisoned".to_string())?;
-        sessions
-            .get(session_id)
-            .cloned()
-            .ok_or_else(|| format!("Embedded terminal session not found: {session_id}"))?
+pub fn list_terminal_profiles(
+    app: &tauri::AppHandle,
+) -> Result<Vec<TerminalProfilePayload>, String> {
+    let connection = open_config_connection(app)?;
+    let profiles = config::list_terminal_profiles(&connection)
+        .map_err(|error| format!("Failed to list terminal profiles: {error}"))?;
+    Ok(profiles.into_iter().map(map_terminal_profile).collect())
+}
+
+pub fn save_terminal_profile(
+    app: &tauri::AppHandle,
+    payload: TerminalProfilePayload,
+) -> Result<TerminalProfilePayload, String> {
+    let connection = open_config_connection(app)?;
+    let profile = TerminalProfile {
+        name: payload.name,
+        env: payload.env.into_iter().collect(),
+        working_directory: payload.working_directory,
     };
+    config::save_terminal_profile(&connection, &profile)
+        .map_err(|error| format!("Failed to save terminal profile: {error}"))?;
+    load_terminal_profile(&connection, Some(&profile.name))?
+        .map(map_terminal_profile)
+        .ok_or_else(|| "Terminal profile name is required".to_string())
+}
+
+pub fn delete_terminal_profile(app: &tauri::AppHandle, name: &str) -> Result<(), String> {
+    let connection = open_config_connection(app)?;
+    config::delete_terminal_profile(&connection, name)
+        .map_err(|error| format!("Failed to delete terminal profile: {error}"))
+}
+
+pub fn get_launch_templates(
+    app: &tauri::AppHandle,
+    provider_id: ProviderId,
+) -> Result<LaunchTemplatesPayload, String> {
+    let templates = load_launch_templates(app, provider_id)?;
+    Ok(map_launch_templates(provider_id, templates))
+}
+
+pub fn save_launch_templates(
+    app: &tauri::AppHandle,
+    provider_id: ProviderId,
+    payload: LaunchTemplatesPayload,
+) -> Result<LaunchTemplatesPayload, String> {
+    let connection = open_config_connection(app)?;
+    let templates = LaunchTemplates {
+        resume: payload.resume,
+        new_thread: payload.new_thread,
+    };
+    config::save_launch_templates(&connection, provider_id, &templates)
+        .map_err(|error| format!("Failed to save launch templates: {error}"))?;
+    get_launch_templates(app, provider_id)
+}
+
+pub fn reset_launch_templates(
+    app: &tauri::AppHandle,
+    provider_id: ProviderId,
+) -> Result<LaunchTemplatesPayload, String> {
+    let connection = open_config_connection(app)?;
+    config::reset_launch_templates(&connection, provider_id)
+        .map_err(|error| format!("Failed to reset launch templates: {error}"))?;
+    Ok(map_launch_templates(
+        provider_id,
+        LaunchTemplates::default_for(provider_id),
+    ))
+}
 
+pub fn write_embedded_terminal_input(session_id: &str, data: &str) -> Result<(), String> {
+    let session = find_embedded_terminal_session(session_id)?;
     let mut stdin = session
         .stdin
         .lock()
         .map_err(|_| "Embedded terminal stdin lock poisoned".to_string())?;
-    stdin
-        .write_all(data.as_bytes())
-        .map_err(|error| format!("Failed to write terminal input: {error}"))?;
-    stdin
-        .flush()
-        .map_err(|error| format!("Failed to flush terminal input: {error}"))?;
+    write_terminal_input(&mut **stdin, data.as_bytes())
+}
+
+/// Sends Ctrl-C to the running agent. With `double`, sends a second one after a short
+/// delay for CLIs that only confirm the first interrupt.
+pub fn interrupt_thread_terminal(session_id: &str, double: bool) -> Result<(), String> {
+    let session = find_embedded_terminal_session(session_id)?;
+    send_terminal_interrupt(&session)?;
+    if double {
+        thread::sleep(DOUBLE_INTERRUPT_DELAY);
+        send_terminal_interrupt(&session)?;
+    }
     Ok(())
 }
 
 pub fn resize_embedded_terminal(session_id: &str, cols: u16, rows: u16) -> Result<(), String> {
-    let session = {
-        let sessions = terminal_sessions()
-            .lock()
-            .map_err(|_| "Embedded terminal sessions lock poisoned".to_string())?;
-        sessions
-            .get(session_id)
-            .cloned()
-            .ok_or_else(|| format!("Embedded terminal session not found: {session_id}"))?
-    };
+    let session = find_embedded_terminal_session(session_id)?;
 
     let cols = clamp_terminal_cols(Some(cols));
     let rows = clamp_terminal_rows(Some(rows));
@@ -213,6 +413,41 @@ pub fn close_embedded_terminal(session_id: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Kills the session's child and re-runs its command in a new PTY of the current size.
+/// The session id is kept, so output and exit listeners stay subscribed.
+pub fn restart_embedded_terminal(
+    app: tauri::AppHandle,
+    session_id: &str,
+) -> Result<StartEmbeddedTerminalResponse, String> {
+    let (reader, session) = replace_embedded_terminal_session(session_id)?;
+    spawn_terminal_output_reader(
+        app.clone(),
+        session_id.to_string(),
+        reader,
+        session.launch.output_encoding,
+    );
+    spawn_terminal_exit_watcher(app.clone(), session_id.to_string(), Arc::clone(&session));
+
+    let payload = EmbeddedTerminalRestartedPayload {
+        session_id: session_id.to_string(),
+        command: session.launch.command.clone(),
+    };
+    if let Err(error) = app.emit("embedded-terminal-restarted", payload) {
+        emit_app_error(
+            &app,
+            app_error_payload(
+                TERMINAL_EXIT_ERROR_SOURCE,
+                format!("Failed to deliver embedded terminal {session_id} restart: {error}"),
+            ),
+        );
+    }
+
+    Ok(StartEmbeddedTerminalResponse {
+        session_id: session_id.to_string(),
+        command: session.launch.command.clone(),
+    })
+}
+
 pub fn clamp_terminal_cols(value: Option<u16>) -> u16 {
     match value.unwrap_or(120) {
         0..=39 => 120,
@@ -227,8 +462,119 @@ pub fn clamp_terminal_rows(value: Option<u16>) -> u16 {
     }
 }
 
-fn terminal_sessions() -> &'static Mutex<HashMap<String, Arc<EmbeddedTerminalSession>>> {
-    EMBEDDED_TERMINAL_SESSIONS.get_or_init(|| Mutex::new(HashMap::new()))
+fn terminal_sessions() -> &'static Mutex<EmbeddedTerminalSessions> {
+    EMBEDDED_TERMINAL_SESSIONS.get_or_init(|| Mutex::new(EmbeddedTerminalSessions::default()))
+}
+
+fn find_embedded_terminal_session(
+    session_id: &str,
+) -> Result<Arc<EmbeddedTerminalSession>, String> {
+    let sessions = terminal_sessions()
+        .lock()
+        .map_err(|_| "Embedded terminal sessions lock poisoned".to_string())?;
+    sessions
+        .active
+        .get(session_id)
+        .cloned()
+        .ok_or_else(|| format!("Embedded terminal session not found: {session_id}"))
+}
+
+fn send_terminal_interrupt(session: &EmbeddedTerminalSession) -> Result<(), String> {
+    let mut stdin = session
+        .stdin
+        .lock()
+        .map_err(|_| "Embedded terminal stdin lock poisoned".to_string())?;
+    write_terminal_input(&mut **stdin, TERMINAL_INTERRUPT_SEQUENCE)
+}
+
+fn write_terminal_input(stdin: &mut dyn Write, data: &[u8]) -> Result<(), String> {
+    stdin
+        .write_all(data)
+        .map_err(|error| format!("Failed to write terminal input: {error}"))?;
+    stdin
+        .flush()
+        .map_err(|error| format!("Failed to flush terminal input: {error}"))
+}
+
+fn max_embedded_terminal_sessions() -> usize {
+    std::env::var(MAX_EMBEDDED_TERMINAL_SESSIONS_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|limit| *limit > 0)
+        .unwrap_or(DEFAULT_MAX_EMBEDDED_TERMINAL_SESSIONS)
+}
+
+impl EmbeddedSessionSlot {
+    /// Checks the limit and takes a slot under one lock, so concurrent starts cannot
+    /// both pass the check before either session is inserted.
+    fn reserve() -> Result<Self, String> {
+        let mut sessions = terminal_sessions()
+            .lock()
+            .map_err(|_| "Embedded terminal sessions lock poisoned".to_string())?;
+        check_session_capacity(
+            sessions.active.len() + sessions.reserved,
+            max_embedded_terminal_sessions(),
+        )
+        .map_err(|error| error.to_string())?;
+        sessions.reserved += 1;
+        Ok(Self)
+    }
+
+    fn fill(self, session_id: String, session: Arc<EmbeddedTerminalSession>) -> Result<(), String> {
+        let mut sessions = terminal_sessions()
+            .lock()
+            .map_err(|_| "Embedded terminal sessions lock poisoned".to_string())?;
+        sessions.reserved = sessions.reserved.saturating_sub(1);
+        sessions.active.insert(session_id, session);
+        drop(sessions);
+        std::mem::forget(self);
+        Ok(())
+    }
+}
+
+impl Drop for EmbeddedSessionSlot {
+    fn drop(&mut self) {
+        if let Ok(mut sessions) = terminal_sessions().lock() {
+            sessions.reserved = sessions.reserved.saturating_sub(1);
+        }
+    }
+}
+
+fn check_session_capacity(
+    open_sessions: usize,
+    limit: usize,
+) -> Result<(), TooManyEmbeddedTerminals> {
+    if open_sessions >= limit {
+        return Err(TooManyEmbeddedTerminals { limit });
+    }
+    Ok(())
+}
+
+fn replace_embedded_terminal_session(
+    session_id: &str,
+) -> Result<(Box<dyn Read + Send>, Arc<EmbeddedTerminalSession>), String> {
+    let previous = find_embedded_terminal_session(session_id)?;
+    let size = previous
+        .master
+        .lock()
+        .map_err(|_| "Embedded terminal master lock poisoned".to_string())?
+        .get_size()
+        .map_err(|error| format!("Failed to read embedded terminal size: {error}"))?;
+
+    // Spawn first: if that fails the old process keeps running and its exit watcher still
+    // reports the exit, so the frontend is never left waiting on a removed session.
+    let (reader, session) = create_embedded_session(previous.launch.clone(), size.cols, size.rows)?;
+    terminal_sessions()
+        .lock()
+        .map_err(|_| "Embedded terminal sessions lock poisoned".to_string())?
+        .active
+        .insert(session_id.to_string(), Arc::clone(&session));
+
+    previous.superseded.store(true, Ordering::SeqCst);
+    if let Ok(mut child) = previous.child.lock() {
+        let _ = child.kill();
+    }
+    Ok((reader, session))
 }
 
 fn next_embedded_terminal_session_id() -> String {
@@ -236,9 +582,94 @@ fn next_embedded_terminal_session_id() -> String {
     format!("embedded-terminal-{value}")
 }
 
+pub(crate) fn open_config_connection(
+    app: &tauri::AppHandle,
+) -> Result<rusqlite::Connection, String> {
+    app_storage::open_db_connection(app)
+}
+
+fn resolve_terminal_profile(
+    app: &tauri::AppHandle,
+    name: Option<&str>,
+) -> Result<Option<TerminalProfile>, String> {
+    if name
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .is_none()
+    {
+        return Ok(None);
+    }
+    let connection = open_config_connection(app)?;
+    load_terminal_profile(&connection, name)
+}
+
+fn load_terminal_profile(
+    connection: &rusqlite::Connection,
+    name: Option<&str>,
+) -> Result<Option<TerminalProfile>, String> {
+    let Some(name) = name.map(str::trim).filter(|name| !name.is_empty()) else {
+        return Ok(None);
+    };
+
+    match config::get_terminal_profile(connection, name) {
+        Ok(profile) => Ok(Some(profile)),
+        Err(ConfigError::NotFound(_)) => Err(format!("Unknown terminal profile: {name}")),
+        Err(error) => Err(format!("Failed to load terminal profile `{name}`: {error}")),
+    }
+}
+
+fn map_terminal_profile(profile: TerminalProfile) -> TerminalProfilePayload {
+    TerminalProfilePayload {
+        name: profile.name,
+        env: profile.env.into_iter().collect(),
+        working_directory: profile.working_directory,
+    }
+}
+
+fn load_launch_templates(
+    app: &tauri::AppHandle,
+    provider_id: ProviderId,
+) -> Result<LaunchTemplates, String> {
+    let connection = open_config_connection(app)?;
+    config::get_launch_templates(&connection, provider_id)
+        .map_err(|error| format!("Failed to load launch templates: {error}"))
+}
+
+fn map_launch_templates(
+    provider_id: ProviderId,
+    templates: LaunchTemplates,
+) -> LaunchTemplatesPayload {
+    LaunchTemplatesPayload {
+        provider_id: provider_id.as_str().to_string(),
+        resume: templates.resume,
+        new_thread: templates.new_thread,
+    }
+}
+
+/// A profile working directory overrides the project path the session would `cd` into.
+fn profile_project_path<'a>(
+    profile: Option<&'a TerminalProfile>,
+    project_path: Option<&'a str>,
+) -> Option<&'a str> {
+    profile
+        .and_then(|profile| profile.working_directory.as_deref())
+        .or(project_path)
+}
+
+fn apply_terminal_profile(cmd: &mut CommandBuilder, profile: Option<&TerminalProfile>) {
+    let Some(profile) = profile else {
+        return;
+    };
+    for (key, value) in &profile.env {
+        cmd.env(key, value);
+    }
+    if let Some(working_directory) = profile.working_directory.as_deref() {
+        cmd.cwd(working_directory);
+    }
+}
+
 fn create_embedded_session(
-    command: &str,
-    terminal_theme: Option<&str>,
+    launch: EmbeddedTerminalLaunch,
     cols: u16,
     rows: u16,
 ) -> Result<(Box<dyn Read + Send>, Arc<EmbeddedTerminalSession>), String> {
@@ -252,12 +683,16 @@ fn create_embedded_session(
         })
         .map_err(|error| format!("Failed to allocate PTY: {error}"))?;
 
-    let mut cmd = build_embedded_shell_command(command);
+    let mut cmd = build_embedded_shell_command(&launch.command);
     cmd.env("TERM", "xterm-256color");
     cmd.env("TERM_PROGRAM", embedded_term_program());
-    cmd.env("COLORFGBG", colorfgbg_for_theme(terminal_theme));
+    cmd.env(
+        "COLORFGBG",
+        colorfgbg_for_theme(launch.terminal_theme.as_deref()),
+    );
     cmd.env("COLUMNS", cols.to_string());
     cmd.env("LINES", rows.to_string());
+    apply_terminal_profile(&mut cmd, launch.terminal_profile.as_ref());
 
     let child = pair
         .slave
@@ -276,6 +711,8 @@ fn create_embedded_session(
         child: Mutex::new(child),
         stdin: Mutex::new(writer),
         master: Mutex::new(pair.master),
+        launch,
+        superseded: AtomicBool::new(false),
     });
     Ok((reader, session))
 }
@@ -322,24 +759,32 @@ fn spawn_terminal_output_reader<R: Read + Send + 'static>(
     app: tauri::AppHandle,
     session_id: String,
     mut stream: R,
+    output_encoding: EmbeddedTerminalOutputEncoding,
 ) {
     thread::spawn(move || {
+        let mut output = TerminalOutputEmitter::new(&app, &session_id);
         let mut buffer = [0_u8; 8192];
         let mut pending = Vec::new();
         loop {
             let read = match stream.read(&mut buffer) {
                 Ok(size) => size,
-                Err(_) => break,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    if let Some(payload) = classify_terminal_read_error(&session_id, &error) {
+                        emit_app_error(&app, payload);
+                    }
+                    0
+                }
             };
+            if read > 0 && output_encoding == EmbeddedTerminalOutputEncoding::Base64 {
+                output.emit(encode_raw_terminal_output(&buffer[..read]));
+                continue;
+            }
             if read == 0 {
                 if !pending.is_empty() {
                     let data = String::from_utf8_lossy(&pending).to_string();
                     if !data.is_empty() {
-                        let payload = EmbeddedTerminalOutputPayload {
-                            session_id: session_id.clone(),
-                            data,
-                        };
-                        let _ = app.emit("embedded-terminal-output", payload);
+                        output.emit(data);
                     }
                     pending.clear();
                 }
@@ -352,11 +797,7 @@ fn spawn_terminal_output_reader<R: Read + Send + 'static>(
                 match std::str::from_utf8(&pending) {
                     Ok(text) => {
                         if !text.is_empty() {
-                            let payload = EmbeddedTerminalOutputPayload {
-                                session_id: session_id.clone(),
-                                data: text.to_string(),
-                            };
-                            let _ = app.emit("embedded-terminal-output", payload);
+                            output.emit(text.to_string());
                         }
                         pending.clear();
                         break;
@@ -365,11 +806,7 @@ fn spawn_terminal_output_reader<R: Read + Send + 'static>(
                         let valid_up_to = error.valid_up_to();
                         if valid_up_to > 0 {
                             let valid = &pending[..valid_up_to];
-                            let payload = EmbeddedTerminalOutputPayload {
-                                session_id: session_id.clone(),
-                                data: String::from_utf8_lossy(valid).to_string(),
-                            };
-                            let _ = app.emit("embedded-terminal-output", payload);
+                            output.emit(String::from_utf8_lossy(valid).to_string());
                         }
 
                         match error.error_len() {
@@ -377,11 +814,7 @@ fn spawn_terminal_output_reader<R: Read + Send + 'static>(
                                 // True invalid bytes: skip the offending sequence and continue.
                                 let drain_to = valid_up_to + error_len;
                                 pending.drain(..drain_to);
-                                let payload = EmbeddedTerminalOutputPayload {
-                                    session_id: session_id.clone(),
-                                    data: "\u{FFFD}".to_string(),
-                                };
-                                let _ = app.emit("embedded-terminal-output", payload);
+                                output.emit("\u{FFFD}".to_string());
                                 if pending.is_empty() {
                                     break;
                                 }
@@ -399,6 +832,66 @@ fn spawn_terminal_output_reader<R: Read + Send + 'static>(
     });
 }
 
+struct TerminalOutputEmitter<'a> {
+    app: &'a tauri::AppHandle,
+    session_id: &'a str,
+    reported_failure: bool,
+}
+
+impl<'a> TerminalOutputEmitter<'a> {
+    fn new(app: &'a tauri::AppHandle, session_id: &'a str) -> Self {
+        Self {
+            app,
+            session_id,
+            reported_failure: false,
+        }
+    }
+
+    fn emit(&mut self, data: String) {
+        let payload = EmbeddedTerminalOutputPayload {
+            session_id: self.session_id.to_string(),
+            data,
+        };
+        if let Err(error) = self.app.emit("embedded-terminal-output", payload) {
+            // Keep draining the PTY so the child never blocks, but only report once.
+            if !self.reported_failure {
+                self.reported_failure = true;
+                emit_app_error(
+                    self.app,
+                    app_error_payload(
+                        TERMINAL_OUTPUT_ERROR_SOURCE,
+                        format!(
+                            "Failed to deliver embedded terminal {} output: {error}",
+                            self.session_id
+                        ),
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Raw mode leaves decoding to the frontend terminal emulator, so bytes that are not
+/// valid UTF-8 survive untouched.
+fn encode_raw_terminal_output(bytes: &[u8]) -> String {
+    BASE64_STANDARD.encode(bytes)
+}
+
+/// Returns `None` for read errors that just mean the terminal closed normally.
+fn classify_terminal_read_error(session_id: &str, error: &io::Error) -> Option<AppErrorPayload> {
+    if cfg!(unix) && error.raw_os_error() == Some(PTY_CLOSED_OS_ERROR) {
+        return None;
+    }
+    if error.kind() == io::ErrorKind::UnexpectedEof {
+        return None;
+    }
+
+    Some(app_error_payload(
+        TERMINAL_OUTPUT_ERROR_SOURCE,
+        format!("Failed to read embedded terminal {session_id} output: {error}"),
+    ))
+}
+
 fn spawn_terminal_exit_watcher(
     app: tauri::AppHandle,
     session_id: String,
@@ -408,36 +901,50 @@ fn spawn_terminal_exit_watcher(
         enum PollStatus {
             Running,
             Exited(Option<i32>),
-            Failed,
+            Failed(String),
         }
 
         let status_code = loop {
-            let poll = {
-                let mut child = match session.child.lock() {
-                    Ok(child) => child,
-                    Err(_) => break None,
-                };
-
-                match child.try_wait() {
+            let poll = match session.child.lock() {
+                Ok(mut child) => match child.try_wait() {
                     Ok(Some(status)) => PollStatus::Exited(Some(status.exit_code() as i32)),
                     Ok(None) => PollStatus::Running,
-                    Err(_) => PollStatus::Failed,
-                }
+                    Err(error) => PollStatus::Failed(format!(
+                        "Failed to poll embedded terminal {session_id} process: {error}"
+                    )),
+                },
+                Err(_) => PollStatus::Failed(format!(
+                    "Embedded terminal {session_id} process lock is poisoned"
+                )),
             };
 
             match poll {
                 PollStatus::Exited(code) => break code,
-                PollStatus::Failed => break None,
+                PollStatus::Failed(message) => {
+                    emit_app_error(&app, app_error_payload(TERMINAL_EXIT_ERROR_SOURCE, message));
+                    break None;
+                }
                 PollStatus::Running => thread::sleep(Duration::from_millis(80)),
             }
         };
 
+        if session.superseded.load(Ordering::SeqCst) {
+            return;
+        }
         remove_embedded_terminal_session(&session_id);
         let payload = EmbeddedTerminalExitPayload {
-            session_id,
+            session_id: session_id.clone(),
             status_code,
         };
-        let _ = app.emit("embedded-terminal-exit", payload);
+        if let Err(error) = app.emit("embedded-terminal-exit", payload) {
+            emit_app_error(
+                &app,
+                app_error_payload(
+                    TERMINAL_EXIT_ERROR_SOURCE,
+                    format!("Failed to deliver embedded terminal {session_id} exit: {error}"),
+                ),
+            );
+        }
     });
 }
 
@@ -445,21 +952,30 @@ fn remove_embedded_terminal_session(session_id: &str) -> Option<Arc<EmbeddedTerm
     terminal_sessions()
         .lock()
         .ok()
-        .and_then(|mut sessions| sessions.remove(session_id))
+        .and_then(|mut sessions| sessions.active.remove(session_id))
+}
+
+fn validate_project_path(project_path: Option<&str>) -> Result<(), UnsafeProjectPath> {
+    match project_path.and_then(|path| path.chars().find(|character| character.is_control())) {
+        Some(character) => Err(UnsafeProjectPath::ControlCharacter(character)),
+        None => Ok(()),
+    }
 }
 
+/// `cli_binary` replaces the bare provider CLI name, e.g. with a resolved absolute path.
 fn build_resume_command_from_parts(
     provider_id: ProviderId,
     thread_id: &str,
     profile_name: Option<&str>,
     env: Option<&HashMap<String, String>>,
     project_path: Option<&str>,
+    cli_binary: Option<&str>,
+    templates: &LaunchTemplates,
 ) -> String {
-    let resume_base = match provider_id {
-        ProviderId::ClaudeCode => format!("claude --resume {}", shell_quote(thread_id)),
-        ProviderId::Codex => format!("codex resume {}", shell_quote(thread_id)),
-        ProviderId::OpenCode => format!("opencode --session {}", shell_quote(thread_id)),
-    };
+    let resume_base = join_command_args(
+        command_binary(provider_id, cli_binary),
+        &render_launch_template(&templates.resume, Some(thread_id), project_path),
+    );
     apply_env_and_profile_to_command(resume_base, env, profile_name, project_path)
 }
 
@@ -468,15 +984,95 @@ fn build_new_thread_command_from_parts(
     profile_name: Option<&str>,
     env: Option<&HashMap<String, String>>,
     project_path: Option<&str>,
+    cli_binary: Option<&str>,
+    templates: &LaunchTemplates,
 ) -> String {
-    let start_base = match provider_id {
-        ProviderId::ClaudeCode => "claude".to_string(),
-        ProviderId::Codex => "codex".to_string(),
-        ProviderId::OpenCode => "opencode".to_string(),
-    };
+    let start_base = join_command_args(
+        command_binary(provider_id, cli_binary),
+        &render_launch_template(&templates.new_thread, None, project_path),
+    );
     apply_env_and_profile_to_command(start_base, env, profile_name, project_path)
 }
 
+/// Substitutes `{thread_id}` and `{project_path}` with shell-quoted values in one pass,
+/// so a value that itself contains a placeholder is never expanded again. Without a
+/// project path `{project_path}` becomes `.`, the directory the command runs in.
+fn render_launch_template(
+    template: &str,
+    thread_id: Option<&str>,
+    project_path: Option<&str>,
+) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if let Some(after) = tail.strip_prefix(THREAD_ID_PLACEHOLDER) {
+            rendered.push_str(&shell_quote(thread_id.unwrap_or_default()));
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix(PROJECT_PATH_PLACEHOLDER) {
+            rendered.push_str(&shell_quote(project_path.unwrap_or(".")));
+            rest = after;
+        } else {
+            rendered.push('{');
+            rest = &tail[1..];
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+fn join_command_args(binary: String, args: &str) -> String {
+    let args = args.trim();
+    if args.is_empty() {
+        binary
+    } else {
+        format!("{binary} {args}")
+    }
+}
+
+fn provider_cli_name(provider_id: ProviderId) -> &'static str {
+    match provider_id {
+        ProviderId::ClaudeCode => "claude",
+        ProviderId::Codex => "codex",
+        ProviderId::OpenCode => "opencode",
+    }
+}
+
+fn command_binary(provider_id: ProviderId, cli_binary: Option<&str>) -> String {
+    match cli_binary {
+        Some(binary) => shell_quote(binary),
+        None => provider_cli_name(provider_id).to_string(),
+    }
+}
+
+fn resolve_cli_paths_enabled() -> bool {
+    std::env::var(RESOLVE_CLI_PATHS_ENV)
+        .map(|value| matches!(value.trim(), "1" | "true" | "TRUE" | "yes"))
+        .unwrap_or(false)
+}
+
+/// CLI the adapter is configured to spawn, so `AGENTDOCK_*_BIN` overrides also apply to
+/// terminal commands. `None` keeps the bare name for PATH lookup.
+fn provider_cli_binary(provider_id: ProviderId) -> Option<String> {
+    let summary = ProviderRegistry::new().get(provider_id)?.config_summary();
+    resolve_provider_cli_path(provider_id, &summary, resolve_cli_paths_enabled())
+}
+
+/// With `absolute` (`AGENTDOCK_RESOLVE_CLI_PATHS`), prefers the file the binary resolves to.
+fn resolve_provider_cli_path(
+    provider_id: ProviderId,
+    summary: &ProviderConfigSummary,
+    absolute: bool,
+) -> Option<String> {
+    if absolute {
+        if let Some(path) = &summary.cli_binary_path {
+            return Some(path.to_string_lossy().to_string());
+        }
+    }
+    (summary.cli_binary != provider_cli_name(provider_id)).then(|| summary.cli_binary.clone())
+}
+
 fn apply_env_and_profile_to_command(
     command: String,
     env: Option<&HashMap<String, String>>,
@@ -664,12 +1260,24 @@ fn escape_cmd_fragment(value: &str) -> String {
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
 
     use provider_contract::ProviderId;
 
     use super::{
-        build_happy_command_from_parts, build_new_thread_command_from_parts,
-        build_resume_command_from_parts, clamp_terminal_cols, clamp_terminal_rows, shell_quote,
+        apply_terminal_profile, build_happy_command_from_parts,
+        build_new_thread_command_from_parts, build_resume_command_from_parts,
+        check_session_capacity, clamp_terminal_cols, clamp_terminal_rows,
+        classify_terminal_read_error, close_embedded_terminal, create_embedded_session,
+        encode_raw_terminal_output, find_embedded_terminal_session, io, load_terminal_profile,
+        next_embedded_terminal_session_id, profile_project_path, replace_embedded_terminal_session,
+        resolve_provider_cli_path, shell_quote, terminal_sessions, validate_project_path,
+        write_terminal_input, CommandBuilder, EmbeddedSessionSlot, EmbeddedTerminalLaunch,
+        EmbeddedTerminalOutputEncoding, LaunchTemplates, ProviderConfigSummary, TerminalProfile,
+        TooManyEmbeddedTerminals, UnsafeProjectPath, PTY_CLOSED_OS_ERROR,
+        TERMINAL_INTERRUPT_SEQUENCE, TERMINAL_OUTPUT_ERROR_SOURCE,
     };
 
     #[test]
@@ -680,6 +1288,8 @@ mod tests {
             None,
             None,
             Some("/tmp/my project"),
+            None,
+            &LaunchTemplates::default_for(ProviderId::ClaudeCode),
         );
         if cfg!(target_os = "windows") {
             assert_eq!(
@@ -696,7 +1306,14 @@ mod tests {
 
     #[test]
     fn build_new_thread_command_supports_provider_without_project_path() {
-        let command = build_new_thread_command_from_parts(ProviderId::OpenCode, None, None, None);
+        let command = build_new_thread_command_from_parts(
+            ProviderId::OpenCode,
+            None,
+            None,
+            None,
+            None,
+            &LaunchTemplates::default_for(ProviderId::OpenCode),
+        );
         assert_eq!(command, "opencode");
     }
 
@@ -708,6 +1325,8 @@ mod tests {
             Some("work"),
             None,
             None,
+            None,
+            &LaunchTemplates::default_for(ProviderId::Codex),
         );
         if cfg!(target_os = "windows") {
             assert_eq!(
@@ -724,8 +1343,14 @@ mod tests {
 
     #[test]
     fn build_new_thread_command_includes_profile_env_when_provided() {
-        let command =
-            build_new_thread_command_from_parts(ProviderId::ClaudeCode, Some("demo"), None, None);
+        let command = build_new_thread_command_from_parts(
+            ProviderId::ClaudeCode,
+            Some("demo"),
+            None,
+            None,
+            None,
+            &LaunchTemplates::default_for(ProviderId::ClaudeCode),
+        );
         if cfg!(target_os = "windows") {
             assert_eq!(command, "set \"AGENTDOCK_ACTIVE_PROFILE=demo\" && claude");
         } else {
@@ -746,6 +1371,8 @@ mod tests {
             Some("team-profile"),
             Some(&env),
             None,
+            None,
+            &LaunchTemplates::default_for(ProviderId::Codex),
         );
         if cfg!(target_os = "windows") {
             assert_eq!(
@@ -817,4 +1444,310 @@ mod tests {
         assert_eq!(clamp_terminal_rows(Some(5)), 36);
         assert_eq!(clamp_terminal_rows(Some(200)), 120);
     }
+
+    #[test]
+    fn classify_terminal_read_error_reports_unexpected_failures() {
+        let error = io::Error::new(io::ErrorKind::PermissionDenied, "denied");
+        let payload =
+            classify_terminal_read_error("embedded-1", &error).expect("error should be reported");
+
+        assert_eq!(payload.source, TERMINAL_OUTPUT_ERROR_SOURCE);
+        assert!(payload.message.contains("embedded-1"));
+        assert!(payload.message.contains("denied"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn classify_terminal_read_error_treats_closed_pty_as_normal_exit() {
+        let error = io::Error::from_raw_os_error(PTY_CLOSED_OS_ERROR);
+        assert!(classify_terminal_read_error("embedded-1", &error).is_none());
+    }
+
+    fn sample_terminal_profile() -> TerminalProfile {
+        TerminalProfile {
+            name: "direnv".to_string(),
+            env: [("DIRENV_DIR".to_string(), "-/workspace/app".to_string())]
+                .into_iter()
+                .collect(),
+            working_directory: Some("/workspace/app".to_string()),
+        }
+    }
+
+    #[test]
+    fn apply_terminal_profile_sets_env_and_working_directory() {
+        let profile = sample_terminal_profile();
+        let mut cmd = CommandBuilder::new("/bin/sh");
+        apply_terminal_profile(&mut cmd, Some(&profile));
+
+        assert_eq!(
+            cmd.get_env("DIRENV_DIR").and_then(|value| value.to_str()),
+            Some("-/workspace/app")
+        );
+        assert_eq!(
+            cmd.get_cwd().and_then(|value| value.to_str()),
+            Some("/workspace/app")
+        );
+        assert_eq!(
+            profile_project_path(Some(&profile), Some("/workspace/other")),
+            Some("/workspace/app")
+        );
+        assert_eq!(
+            profile_project_path(None, Some("/workspace/other")),
+            Some("/workspace/other")
+        );
+    }
+
+    #[test]
+    fn load_terminal_profile_rejects_unknown_profile() {
+        let mut connection =
+            rusqlite::Connection::open_in_memory().expect("in-memory sqlite should open");
+        agentdock_core::db::run_migrations(&mut connection).expect("migrations should run");
+        agentdock_core::config::save_terminal_profile(&connection, &sample_terminal_profile())
+            .expect("profile should save");
+
+        let loaded = load_terminal_profile(&connection, Some("direnv"))
+            .expect("known profile should load")
+            .expect("profile should be present");
+        assert_eq!(loaded.working_directory.as_deref(), Some("/workspace/app"));
+        assert!(load_terminal_profile(&connection, None)
+            .expect("missing name is not an error")
+            .is_none());
+
+        let error = load_terminal_profile(&connection, Some("missing"))
+            .expect_err("unknown profile should fail");
+        assert_eq!(error, "Unknown terminal profile: missing");
+    }
+
+    #[test]
+    fn encode_raw_terminal_output_preserves_exact_bytes() {
+        use base64::prelude::{Engine as _, BASE64_STANDARD};
+
+        let bytes = [b'o', b'k', 0x1b, b'[', b'0', b'm', 0xff, 0xfe, 0xe2, 0x94];
+
+        let encoded = encode_raw_terminal_output(&bytes);
+
+        assert_eq!(encoded, "b2sbWzBt//7ilA==");
+        let decoded = BASE64_STANDARD
+            .decode(&encoded)
+            .expect("output should be valid base64");
+        assert_eq!(decoded, bytes);
+    }
+
+    #[test]
+    fn validate_project_path_rejects_newlines_and_nul() {
+        assert_eq!(
+            validate_project_path(Some("/workspace/app\n; rm -rf ~")),
+            Err(UnsafeProjectPath::ControlCharacter('\n'))
+        );
+        assert_eq!(
+            validate_project_path(Some("/workspace/app\0")),
+            Err(UnsafeProjectPath::ControlCharacter('\0'))
+        );
+        assert_eq!(
+            UnsafeProjectPath::ControlCharacter('\n').to_string(),
+            "Project path contains a control character (U+000A)"
+        );
+    }
+
+    #[test]
+    fn validate_project_path_accepts_regular_paths() {
+        assert_eq!(validate_project_path(Some("/workspace/it's mine")), Ok(()));
+        assert_eq!(validate_project_path(None), Ok(()));
+    }
+
+    #[test]
+    fn check_session_capacity_rejects_sessions_beyond_limit() {
+        for open_sessions in 0..3 {
+            assert_eq!(check_session_capacity(open_sessions, 3), Ok(()));
+        }
+
+        let error = check_session_capacity(3, 3).expect_err("fourth session should fail");
+        assert_eq!(error, TooManyEmbeddedTerminals { limit: 3 });
+        assert!(error.to_string().contains("limit 3"));
+    }
+
+    #[test]
+    fn embedded_session_slot_is_released_when_dropped_unfilled() {
+        let reserved = || {
+            terminal_sessions()
+                .lock()
+                .expect("sessions lock should be available")
+                .reserved
+        };
+        let before = reserved();
+
+        let slot = EmbeddedSessionSlot::reserve().expect("slot should be reserved");
+        assert_eq!(reserved(), before + 1);
+        drop(slot);
+
+        assert_eq!(reserved(), before);
+    }
+
+    #[test]
+    fn terminal_interrupt_writes_ctrl_c_byte() {
+        let mut written = Vec::new();
+
+        write_terminal_input(&mut written, TERMINAL_INTERRUPT_SEQUENCE)
+            .expect("interrupt should be written");
+
+        assert_eq!(written, vec![0x03]);
+    }
+
+    #[test]
+    fn build_commands_substitute_resolved_cli_binary() {
+        let command = build_resume_command_from_parts(
+            ProviderId::ClaudeCode,
+            "thread-id",
+            None,
+            None,
+            None,
+            Some("/opt/tools/bin/claude"),
+            &LaunchTemplates::default_for(ProviderId::ClaudeCode),
+        );
+        let new_command = build_new_thread_command_from_parts(
+            ProviderId::Codex,
+            None,
+            None,
+            None,
+            Some("/opt/tools/bin/codex"),
+            &LaunchTemplates::default_for(ProviderId::Codex),
+        );
+        if cfg!(target_os = "windows") {
+            assert_eq!(command, "\"/opt/tools/bin/claude\" --resume \"thread-id\"");
+            assert_eq!(new_command, "\"/opt/tools/bin/codex\"");
+        } else {
+            assert_eq!(command, "'/opt/tools/bin/claude' --resume 'thread-id'");
+            assert_eq!(new_command, "'/opt/tools/bin/codex'");
+        }
+    }
+
+    fn cli_config_summary(
+        cli_binary: &str,
+        cli_binary_path: Option<&str>,
+    ) -> ProviderConfigSummary {
+        ProviderConfigSummary {
+            config_dir: PathBuf::from("/home/me/.config/opencode"),
+            sessions_dir: PathBuf::from("/home/me/.local/share/opencode/storage"),
+            cli_binary: cli_binary.to_string(),
+            cli_binary_path: cli_binary_path.map(PathBuf::from),
+            auth_source: None,
+        }
+    }
+
+    #[test]
+    fn resolve_provider_cli_path_returns_absolute_binary_path() {
+        let summary = cli_config_summary("opencode", Some("/opt/homebrew/bin/opencode"));
+
+        assert_eq!(
+            resolve_provider_cli_path(ProviderId::OpenCode, &summary, true).as_deref(),
+            Some("/opt/homebrew/bin/opencode")
+        );
+        assert_eq!(
+            resolve_provider_cli_path(ProviderId::OpenCode, &summary, false),
+            None
+        );
+    }
+
+    #[test]
+    fn resolve_provider_cli_path_keeps_configured_binary_override() {
+        let summary = cli_config_summary("/opt/tools/opencode-wrapper", None);
+
+        for absolute in [false, true] {
+            assert_eq!(
+                resolve_provider_cli_path(ProviderId::OpenCode, &summary, absolute).as_deref(),
+                Some("/opt/tools/opencode-wrapper")
+            );
+        }
+    }
+
+    #[test]
+    fn build_resume_command_renders_custom_template_with_quoting() {
+        let templates = LaunchTemplates {
+            resume: "resume --cd {project_path} {thread_id} --note {unknown}".to_string(),
+            new_thread: "--cd {project_path}".to_string(),
+        };
+
+        let command = build_resume_command_from_parts(
+            ProviderId::Codex,
+            "it's {project_path}",
+            None,
+            None,
+            Some("/tmp/my project"),
+            None,
+            &templates,
+        );
+        let new_command = build_new_thread_command_from_parts(
+            ProviderId::Codex,
+            None,
+            None,
+            None,
+            None,
+            &templates,
+        );
+
+        if cfg!(target_os = "windows") {
+            assert_eq!(
+                command,
+                "cd /d \"/tmp/my project\" && codex resume --cd \"/tmp/my project\" \"it's {project_path}\" --note {unknown}"
+            );
+            assert_eq!(new_command, "codex --cd \".\"");
+        } else {
+            assert_eq!(
+                command,
+                "cd '/tmp/my project' && codex resume --cd '/tmp/my project' 'it'\"'\"'s {project_path}' --note {unknown}"
+            );
+            assert_eq!(new_command, "codex --cd '.'");
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn replace_embedded_terminal_session_keeps_id_and_spawns_new_child() {
+        let session_id = next_embedded_terminal_session_id();
+        let launch = EmbeddedTerminalLaunch {
+            command: "sleep 30".to_string(),
+            terminal_profile: None,
+            terminal_theme: None,
+            output_encoding: EmbeddedTerminalOutputEncoding::Utf8,
+        };
+        let (_reader, previous) =
+            create_embedded_session(launch, 100, 30).expect("session should spawn");
+        terminal_sessions()
+            .lock()
+            .expect("sessions lock should be available")
+            .active
+            .insert(session_id.clone(), Arc::clone(&previous));
+        let previous_pid = previous
+            .child
+            .lock()
+            .expect("child lock should be available")
+            .process_id();
+
+        let (_reader, restarted) =
+            replace_embedded_terminal_session(&session_id).expect("session should restart");
+
+        let current =
+            find_embedded_terminal_session(&session_id).expect("session id should be preserved");
+        assert!(Arc::ptr_eq(&current, &restarted));
+        assert!(!Arc::ptr_eq(&current, &previous));
+        assert!(previous.superseded.load(Ordering::SeqCst));
+        assert_ne!(
+            restarted
+                .child
+                .lock()
+                .expect("child lock should be available")
+                .process_id(),
+            previous_pid
+        );
+        assert_eq!(restarted.launch.command, "sleep 30");
+        let size = restarted
+            .master
+            .lock()
+            .expect("master lock should be available")
+            .get_size()
+            .expect("size should be readable");
+        assert_eq!((size.cols, size.rows), (100, 30));
+
+        close_embedded_terminal(&session_id).expect("session should close");
+    }
 }
@@ -1,52 +1,274 @@
-use provider_claude::{ClaudeAdapter, ClaudeThreadOverview, ClaudeThreadRuntimeState};
-use provider_codex::{CodexAdapter, CodexThreadOverview, CodexThreadRuntimeState};
-use provider_opencode::{OpenCodeAdapter, OpenCodeThreadOverview, OpenCodeThreadRuntimeState};
-use std::collections::HashMap;
+use agentdock_core::config;
+use agentdock_core::threads::{self as core_threads, PinnedThread};
+use provider_claude::{ClaudeAdapter, ClaudeThreadRuntimeState};
+use provider_codex::{CodexAdapter, CodexThreadRuntimeState, CodexThreadUsage};
+use provider_contract::{
+    same_git_worktree, ProviderId, ProviderResult, ThreadOverviewOptions, ThreadSummary,
+};
+use provider_opencode::{OpenCodeAdapter, OpenCodeThreadRuntimeState};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
+use crate::demo_threads::{is_demo_thread_id, with_demo_threads};
 use crate::payloads::{
-    ClaudeThreadRuntimeStatePayload, CodexThreadRuntimeStatePayload,
-    OpenCodeThreadRuntimeStatePayload, ThreadSummaryPayload,
+    ClaudeThreadRuntimeStatePayload, CodexThreadRuntimeStatePayload, CodexThreadUsagePayload,
+    OpenCodeThreadRuntimeStatePayload, PinnedThreadPayload, ProviderListErrorPayload,
+    RecentProjectPayload, ThreadComparisonPayload, ThreadComparisonSidePayload, ThreadCountPayload,
+    ThreadDiscoveredPayload, ThreadEventPayload, ThreadListResultPayload,
+    ThreadScanCompletePayload, ThreadSummaryPayload,
 };
+use crate::provider_registry::{ProviderRegistry, RegisteredProvider};
+use crate::terminal::open_config_connection;
 
-pub fn list_threads(project_path: Option<&str>) -> Result<Vec<ThreadSummaryPayload>, String> {
-    let claude_threads = ClaudeAdapter::new()
-        .list_thread_overviews(project_path)
-        .map_err(|error| {
-            format!(
-                "Failed to list Claude threads ({:?}): {}",
-                error.code, error.message
-            )
-        })?;
-    let codex_threads = CodexAdapter::new()
-        .list_thread_overviews(project_path)
-        .map_err(|error| {
-            format!(
-                "Failed to list Codex threads ({:?}): {}",
-                error.code, error.message
-            )
-        })?;
-    let opencode_threads = OpenCodeAdapter::new()
-        .list_thread_overviews(project_path)
-        .map_err(|error| {
-            format!(
-                "Failed to list OpenCode threads ({:?}): {}",
-                error.code, error.message
-            )
-        })?;
-
-    let mut threads =
-        Vec::with_capacity(claude_threads.len() + codex_threads.len() + opencode_threads.len());
-    threads.extend(claude_threads.into_iter().map(map_claude_thread_overview));
-    threads.extend(codex_threads.into_iter().map(map_codex_thread_overview));
-    threads.extend(
-        opencode_threads
+/// Threads across providers, with `pinned` threads flagged and listed first. A provider
+/// that fails to scan is logged and left out; the call only fails when every provider does.
+pub fn list_threads(
+    project_path: Option<&str>,
+    pinned: &[PinnedThread],
+    options: ThreadOverviewOptions,
+) -> Result<Vec<ThreadSummaryPayload>, String> {
+    let provider_count = ProviderRegistry::new().providers().len();
+    let (threads, provider_errors) = scan_threads(project_path, pinned, options);
+    if provider_count > 0 && provider_errors.len() == provider_count {
+        return Err(provider_errors
             .into_iter()
-            .map(map_opencode_thread_overview),
+            .map(|error| error.message)
+            .collect::<Vec<_>>()
+            .join("; "));
+    }
+    for error in &provider_errors {
+        eprintln!("[threads] {}", error.message);
+    }
+    Ok(threads)
+}
+
+fn scan_threads(
+    project_path: Option<&str>,
+    pinned: &[PinnedThread],
+    options: ThreadOverviewOptions,
+) -> (Vec<ThreadSummaryPayload>, Vec<ProviderListErrorPayload>) {
+    let registry = ProviderRegistry::new();
+    let (threads, provider_errors) = collect_provider_threads(registry.providers(), |provider| {
+        provider.list_thread_overviews(project_path, options)
+    });
+    let mut threads = dedupe_thread_summaries(threads);
+    mark_pinned_threads(&mut threads, pinned);
+    sort_thread_summaries(&mut threads);
+
+    (
+        with_demo_threads(threads, registry.demo_mode(), project_path),
+        provider_errors,
+    )
+}
+
+pub const THREAD_DISCOVERED_EVENT: &str = "thread-discovered";
+pub const THREAD_SCAN_COMPLETE_EVENT: &str = "thread-scan-complete";
+
+#[derive(Debug, Clone)]
+pub enum ThreadScanEvent {
+    Discovered(ThreadDiscoveredPayload),
+    Complete(ThreadScanCompletePayload),
+}
+
+/// Streaming `list_threads`: emits each provider's threads as soon as that provider is
+/// scanned, then one `Complete`. Failed providers are reported in `Complete` only.
+pub fn stream_threads<E>(
+    project_path: Option<&str>,
+    pinned: &[PinnedThread],
+    options: ThreadOverviewOptions,
+    worktree_path: Option<&str>,
+    mut emit: E,
+) where
+    E: FnMut(ThreadScanEvent),
+{
+    let registry = ProviderRegistry::new();
+    stream_provider_threads(
+        registry.providers(),
+        |provider| provider.list_thread_overviews(project_path, options),
+        |threads| {
+            mark_pinned_threads(threads, pinned);
+            retain_worktree_threads(threads, worktree_path);
+        },
+        || with_demo_threads(Vec::new(), registry.demo_mode(), project_path),
+        &mut emit,
     );
-    threads = dedupe_thread_summaries(threads);
+}
+
+// `prepare` runs on each provider batch; `fallback` supplies threads (the demo set) when
+// no provider found any.
+fn stream_provider_threads<F, P, D, E>(
+    providers: &[RegisteredProvider],
+    list: F,
+    prepare: P,
+    fallback: D,
+    emit: &mut E,
+) where
+    F: Fn(&RegisteredProvider) -> ProviderResult<Vec<ThreadSummaryPayload>>,
+    P: Fn(&mut Vec<ThreadSummaryPayload>),
+    D: FnOnce() -> Vec<ThreadSummaryPayload>,
+    E: FnMut(ThreadScanEvent),
+{
+    let mut thread_count = 0;
+    let mut provider_errors = Vec::new();
+    for provider in providers {
+        let (threads, errors) = collect_provider_threads(std::slice::from_ref(provider), &list);
+        provider_errors.extend(errors);
+        thread_count += emit_thread_batch(dedupe_thread_summaries(threads), &prepare, emit);
+    }
+    if thread_count == 0 {
+        thread_count = emit_thread_batch(fallback(), &prepare, emit);
+    }
+
+    emit(ThreadScanEvent::Complete(ThreadScanCompletePayload {
+        thread_count,
+        provider_errors,
+    }));
+}
+
+fn emit_thread_batch<P, E>(
+    mut threads: Vec<ThreadSummaryPayload>,
+    prepare: &P,
+    emit: &mut E,
+) -> usize
+where
+    P: Fn(&mut Vec<ThreadSummaryPayload>),
+    E: FnMut(ThreadScanEvent),
+{
+    prepare(&mut threads);
     sort_thread_summaries(&mut threads);
+    let count = threads.len();
+    if count > 0 {
+        emit(ThreadScanEvent::Discovered(ThreadDiscoveredPayload {
+            threads,
+        }));
+    }
+    count
+}
 
-    Ok(threads)
+/// Like `list_threads`, but also reports which providers were scanned and which have no
+/// CLI installed, so an empty list can be told apart from a missing install.
+pub fn list_threads_with_status(
+    project_path: Option<&str>,
+    pinned: &[PinnedThread],
+    options: ThreadOverviewOptions,
+) -> Result<ThreadListResultPayload, String> {
+    let (threads, provider_errors) = scan_threads(project_path, pinned, options);
+    let registry = ProviderRegistry::new();
+    let (providers_scanned, providers_missing) =
+        partition_providers_by_cli(registry.providers(), |provider| {
+            provider.config_summary().cli_binary_path.is_some()
+        });
+    let skipped_files = registry
+        .providers()
+        .iter()
+        .flat_map(|provider| provider.oversized_session_files())
+        .map(|file| file.to_string())
+        .collect();
+
+    Ok(ThreadListResultPayload {
+        threads,
+        providers_scanned,
+        providers_missing,
+        skipped_files,
+        provider_errors,
+    })
+}
+
+/// Counts threads from the plain summaries, skipping the per-thread preview reads
+/// that `list_threads` performs.
+pub fn count_threads(project_path: Option<&str>) -> Result<ThreadCountPayload, String> {
+    count_provider_threads(ProviderRegistry::new().providers(), project_path)
+}
+
+fn count_provider_threads(
+    providers: &[RegisteredProvider],
+    project_path: Option<&str>,
+) -> Result<ThreadCountPayload, String> {
+    let mut summaries = Vec::new();
+    for provider in providers {
+        let provider_threads = provider
+            .adapter()
+            .list_threads(project_path)
+            .map_err(|error| {
+                format!(
+                    "Failed to count {} threads ({:?}): {}",
+                    provider.label(),
+                    error.code,
+                    error.message
+                )
+            })?;
+        summaries.extend(provider_threads);
+    }
+
+    Ok(count_thread_summaries(&summaries))
+}
+
+pub fn get_default_project(app: &tauri::AppHandle) -> Result<Option<String>, String> {
+    let connection = open_config_connection(app)?;
+    config::get_default_project(&connection)
+        .map_err(|error| format!("Failed to load default project: {error}"))
+}
+
+pub fn set_default_project(
+    app: &tauri::AppHandle,
+    project_path: Option<&str>,
+) -> Result<Option<String>, String> {
+    let connection = open_config_connection(app)?;
+    config::set_default_project(&connection, project_path)
+        .map_err(|error| format!("Failed to save default project: {error}"))?;
+    get_default_project(app)
+}
+
+/// `project_path`, or the saved default project when the caller passed none.
+pub fn project_path_or_default(
+    app: &tauri::AppHandle,
+    project_path: Option<&str>,
+) -> Result<Option<String>, String> {
+    let connection = open_config_connection(app)?;
+    config::project_path_or_default(&connection, project_path)
+        .map_err(|error| format!("Failed to load default project: {error}"))
+}
+
+pub fn list_pinned(app: &tauri::AppHandle) -> Result<Vec<PinnedThread>, String> {
+    let connection = open_config_connection(app)?;
+    core_threads::list_pinned(&connection)
+        .map_err(|error| format!("Failed to load pinned threads: {error}"))
+}
+
+pub fn list_pinned_threads(app: &tauri::AppHandle) -> Result<Vec<PinnedThreadPayload>, String> {
+    Ok(list_pinned(app)?
+        .into_iter()
+        .map(|pin| PinnedThreadPayload {
+            provider_id: pin.provider_id,
+            thread_id: pin.thread_id,
+            pinned_at: pin.pinned_at,
+        })
+        .collect())
+}
+
+pub fn pin_thread(
+    app: &tauri::AppHandle,
+    provider_id: ProviderId,
+    thread_id: &str,
+) -> Result<(), String> {
+    let connection = open_config_connection(app)?;
+    core_threads::pin_thread(&connection, provider_id, thread_id)
+        .map_err(|error| format!("Failed to pin thread: {error}"))
+}
+
+pub fn unpin_thread(
+    app: &tauri::AppHandle,
+    provider_id: ProviderId,
+    thread_id: &str,
+) -> Result<bool, String> {
+    let connection = open_config_connection(app)?;
+    core_threads::unpin_thread(&connection, provider_id, thread_id)
+        .map_err(|error| format!("Failed to unpin thread: {error}"))
+}
+
+pub fn list_recent_projects(limit: usize) -> Result<Vec<RecentProjectPayload>, String> {
+    let threads = list_threads(None, &[], ThreadOverviewOptions::default())?;
+    Ok(collect_recent_projects(&threads, limit))
 }
 
 pub fn get_codex_thread_runtime_state(
@@ -63,6 +285,18 @@ pub fn get_codex_thread_runtime_state(
     Ok(map_codex_thread_runtime_state(state))
 }
 
+pub fn get_codex_thread_usage(thread_id: &str) -> Result<CodexThreadUsagePayload, String> {
+    let usage = CodexAdapter::new()
+        .get_thread_usage(thread_id)
+        .map_err(|error| {
+            format!(
+                "Failed to load Codex thread usage ({:?}): {}",
+                error.code, error.message
+            )
+        })?;
+    Ok(map_codex_thread_usage(usage))
+}
+
 pub fn get_claude_thread_runtime_state(
     thread_id: &str,
 ) -> Result<ClaudeThreadRuntimeStatePayload, String> {
@@ -91,42 +325,131 @@ pub fn get_opencode_thread_runtime_state(
     Ok(map_opencode_thread_runtime_state(state))
 }
 
-fn map_claude_thread_overview(overview: ClaudeThreadOverview) -> ThreadSummaryPayload {
-    ThreadSummaryPayload {
-        id: overview.summary.id,
-        provider_id: overview.summary.provider_id.as_str().to_string(),
-        project_path: overview.summary.project_path,
-        title: overview.summary.title,
-        tags: overview.summary.tags,
-        last_active_at: overview.summary.last_active_at,
-        last_message_preview: overview.last_message_preview,
-    }
+pub fn get_latest_message_preview(
+    provider_id: ProviderId,
+    thread_id: &str,
+) -> Result<Option<String>, String> {
+    ProviderRegistry::new()
+        .require(provider_id)?
+        .get_latest_message_preview(thread_id)
+        .map_err(|error| {
+            format!(
+                "Failed to load latest message preview ({:?}): {}",
+                error.code, error.message
+            )
+        })
+}
+
+/// Last assistant reply, for "finished responding" notifications once a thread goes idle.
+pub fn get_final_assistant_message(
+    provider_id: ProviderId,
+    thread_id: &str,
+) -> Result<Option<String>, String> {
+    ProviderRegistry::new()
+        .require(provider_id)?
+        .get_final_assistant_message(thread_id)
+        .map_err(|error| {
+            format!(
+                "Failed to load final assistant message ({:?}): {}",
+                error.code, error.message
+            )
+        })
 }
 
-fn map_codex_thread_overview(overview: CodexThreadOverview) -> ThreadSummaryPayload {
-    ThreadSummaryPayload {
-        id: overview.summary.id,
-        provider_id: overview.summary.provider_id.as_str().to_string(),
-        project_path: overview.summary.project_path,
-        title: overview.summary.title,
-        tags: overview.summary.tags,
-        last_active_at: overview.summary.last_active_at,
-        last_message_preview: overview.last_message_preview,
+/// Lets the UI drop cached rows whose session files the CLI has since deleted.
+pub fn thread_exists(provider_id: ProviderId, thread_id: &str) -> Result<bool, String> {
+    let registry = ProviderRegistry::new();
+    if registry.demo_mode() && is_demo_thread_id(thread_id) {
+        return Ok(true);
     }
+    Ok(registry.require(provider_id)?.thread_exists(thread_id))
 }
 
-fn map_opencode_thread_overview(overview: OpenCodeThreadOverview) -> ThreadSummaryPayload {
-    ThreadSummaryPayload {
-        id: overview.summary.id,
-        provider_id: overview.summary.provider_id.as_str().to_string(),
-        project_path: overview.summary.project_path,
-        title: overview.summary.title,
-        tags: overview.summary.tags,
-        last_active_at: overview.summary.last_active_at,
-        last_message_preview: overview.last_message_preview,
+/// Final assistant message, edited files and basic stats of two threads side by side.
+pub fn compare_threads(
+    left: (ProviderId, &str),
+    right: (ProviderId, &str),
+) -> Result<ThreadComparisonPayload, String> {
+    let registry = ProviderRegistry::new();
+    let left = load_thread_comparison_side(&registry, left.0, left.1)?;
+    let right = load_thread_comparison_side(&registry, right.0, right.1)?;
+    Ok(build_thread_comparison(left, right))
+}
+
+fn load_thread_comparison_side(
+    registry: &ProviderRegistry,
+    provider_id: ProviderId,
+    thread_id: &str,
+) -> Result<ThreadComparisonSidePayload, String> {
+    let provider = registry.require(provider_id)?;
+    let map_error = |error: provider_contract::ProviderError| {
+        format!(
+            "Failed to load {} thread {thread_id} ({:?}): {}",
+            provider.label(),
+            error.code,
+            error.message
+        )
+    };
+    let final_assistant_message = provider
+        .get_final_assistant_message(thread_id)
+        .map_err(map_error)?;
+    let activity = provider.get_thread_activity(thread_id).map_err(map_error)?;
+
+    Ok(ThreadComparisonSidePayload {
+        provider_id: provider_id.as_str().to_string(),
+        thread_id: thread_id.to_string(),
+        final_assistant_message,
+        message_count: activity.message_count,
+        duration_ms: activity.duration_ms(),
+        touched_files: activity.touched_files,
+    })
+}
+
+fn build_thread_comparison(
+    left: ThreadComparisonSidePayload,
+    right: ThreadComparisonSidePayload,
+) -> ThreadComparisonPayload {
+    let right_files = right.touched_files.iter().collect::<HashSet<_>>();
+    let common_files = left
+        .touched_files
+        .iter()
+        .filter(|path| right_files.contains(path))
+        .cloned()
+        .collect();
+    ThreadComparisonPayload {
+        left,
+        right,
+        common_files,
     }
 }
 
+/// Events returned when the caller does not pass a limit.
+pub const DEFAULT_THREAD_EVENT_LOG_LIMIT: usize = 50;
+
+pub fn get_thread_event_log(
+    provider_id: ProviderId,
+    thread_id: &str,
+    limit: Option<usize>,
+) -> Result<Vec<ThreadEventPayload>, String> {
+    let events = ProviderRegistry::new()
+        .require(provider_id)?
+        .get_thread_event_log(thread_id, limit.unwrap_or(DEFAULT_THREAD_EVENT_LOG_LIMIT))
+        .map_err(|error| {
+            format!(
+                "Failed to load thread event log ({:?}): {}",
+                error.code, error.message
+            )
+        })?;
+    Ok(events
+        .into_iter()
+        .map(|event| ThreadEventPayload {
+            kind: event.kind,
+            at_ms: event.at_ms,
+            timestamp_raw: event.timestamp_raw,
+        })
+        .collect())
+}
+
 fn map_codex_thread_runtime_state(
     state: CodexThreadRuntimeState,
 ) -> CodexThreadRuntimeStatePayload {
@@ -134,6 +457,15 @@ fn map_codex_thread_runtime_state(
         agent_answering: state.agent_answering,
         last_event_kind: state.last_event_kind,
         last_event_at_ms: state.last_event_at_ms,
+        process_running: None,
+    }
+}
+
+fn map_codex_thread_usage(usage: CodexThreadUsage) -> CodexThreadUsagePayload {
+    CodexThreadUsagePayload {
+        input_tokens: usage.input_tokens,
+        output_tokens: usage.output_tokens,
+        total_tokens: usage.total_tokens,
     }
 }
 
@@ -144,6 +476,7 @@ fn map_opencode_thread_runtime_state(
         agent_answering: state.agent_answering,
         last_event_kind: state.last_event_kind,
         last_event_at_ms: state.last_event_at_ms,
+        process_running: None,
     }
 }
 
@@ -154,15 +487,122 @@ fn map_claude_thread_runtime_state(
         agent_answering: state.agent_answering,
         last_event_kind: state.last_event_kind,
         last_event_at_ms: state.last_event_at_ms,
+        process_running: None,
+    }
+}
+
+/// Merges every provider's threads, turning a failing provider into an error entry
+/// instead of failing the whole listing.
+fn collect_provider_threads<F>(
+    providers: &[RegisteredProvider],
+    list: F,
+) -> (Vec<ThreadSummaryPayload>, Vec<ProviderListErrorPayload>)
+where
+    F: Fn(&RegisteredProvider) -> ProviderResult<Vec<ThreadSummaryPayload>>,
+{
+    let mut threads = Vec::new();
+    let mut errors = Vec::new();
+    for provider in providers {
+        match list(provider) {
+            Ok(provider_threads) => threads.extend(provider_threads),
+            Err(error) => errors.push(ProviderListErrorPayload {
+                provider_id: provider.provider_id().as_str().to_string(),
+                message: format!(
+                    "Failed to list {} threads ({:?}): {}",
+                    provider.label(),
+                    error.code,
+                    error.message
+                ),
+            }),
+        }
+    }
+    (threads, errors)
+}
+
+fn partition_providers_by_cli<F>(
+    providers: &[RegisteredProvider],
+    is_cli_available: F,
+) -> (Vec<String>, Vec<String>)
+where
+    F: Fn(&RegisteredProvider) -> bool,
+{
+    let scanned = providers
+        .iter()
+        .map(|provider| provider.provider_id().as_str().to_string())
+        .collect();
+    let missing = providers
+        .iter()
+        .filter(|provider| !is_cli_available(provider))
+        .map(|provider| provider.provider_id().as_str().to_string())
+        .collect();
+    (scanned, missing)
+}
+
+fn count_thread_summaries<'a>(
+    threads: impl IntoIterator<Item = &'a ThreadSummary>,
+) -> ThreadCountPayload {
+    let mut seen = HashSet::new();
+    let mut per_provider: HashMap<String, usize> = HashMap::new();
+
+    for thread in threads {
+        let provider_id = thread.provider_id.as_str();
+        if seen.insert((provider_id, thread.id.as_str())) {
+            *per_provider.entry(provider_id.to_string()).or_default() += 1;
+        }
+    }
+
+    ThreadCountPayload {
+        total: seen.len(),
+        per_provider,
+    }
+}
+
+fn collect_recent_projects(
+    threads: &[ThreadSummaryPayload],
+    limit: usize,
+) -> Vec<RecentProjectPayload> {
+    let mut projects: HashMap<String, RecentProjectPayload> = HashMap::new();
+
+    for thread in threads {
+        let project_path = normalize_recent_project_path(&thread.project_path);
+        if project_path == "." {
+            continue;
+        }
+
+        let last_active_at = thread.last_active_at;
+        let entry = projects
+            .entry(project_path.clone())
+            .or_insert_with(|| RecentProjectPayload {
+                project_path,
+                last_active_at,
+                thread_count: 0,
+            });
+        entry.thread_count += 1;
+        entry.last_active_at = entry.last_active_at.max(last_active_at);
     }
+
+    let mut projects = projects.into_values().collect::<Vec<_>>();
+    projects.sort_by(|left, right| {
+        right
+            .last_active_at
+            .cmp(&left.last_active_at)
+            .then_with(|| left.project_path.cmp(&right.project_path))
+    });
+    projects.truncate(limit);
+    projects
 }
 
-fn sortable_last_active_at(raw: &str) -> i64 {
-    let parsed = raw.parse::<i64>().unwrap_or(0);
-    if parsed.abs() < 1_000_000_000_000 {
-        parsed * 1000
+fn normalize_recent_project_path(raw: &str) -> String {
+    let trimmed = raw.trim();
+    let without_trailing = trimmed.trim_end_matches(['/', '\\']);
+    if without_trailing.is_empty() {
+        if trimmed.is_empty() {
+            ".".to_string()
+        } else {
+            trimmed[..1].to_string()
+        }
     } else {
-        parsed
+        without_trailing.to_string()
     }
 }
 
@@ -190,19 +630,93 @@ fn should_replace_thread_summary(
     existing: &ThreadSummaryPayload,
     candidate: &ThreadSummaryPayload,
 ) -> bool {
-    let existing_last_active = sortable_last_active_at(&existing.last_active_at);
-    let candidate_last_active = sortable_last_active_at(&candidate.last_active_at);
-    if candidate_last_active != existing_last_active {
-        return candidate_last_active > existing_last_active;
+    if candidate.last_active_at != existing.last_active_at {
+        return candidate.last_active_at > existing.last_active_at;
     }
 
     candidate.project_path < existing.project_path
 }
 
+/// "Threads for this checkout" filter: keeps threads started in the same git worktree as
+/// `worktree_path`, dropping sibling worktrees of the same repository. `None` keeps all.
+pub fn retain_worktree_threads(
+    threads: &mut Vec<ThreadSummaryPayload>,
+    worktree_path: Option<&str>,
+) {
+    let Some(worktree_path) = worktree_path.map(str::trim).filter(|path| !path.is_empty()) else {
+        return;
+    };
+    threads.retain(|thread| thread_matches_worktree(thread, Path::new(worktree_path)));
+}
+
+/// Compares the directory the thread ran in (its original cwd when the project path was
+/// grouped under a git root) against `worktree_path`.
+pub fn thread_matches_worktree(thread: &ThreadSummaryPayload, worktree_path: &Path) -> bool {
+    let cwd = thread
+        .original_cwd
+        .as_deref()
+        .unwrap_or(thread.project_path.as_str());
+    same_git_worktree(Path::new(cwd), worktree_path)
+}
+
+fn mark_pinned_threads(threads: &mut [ThreadSummaryPayload], pinned: &[PinnedThread]) {
+    let pinned_keys = pinned
+        .iter()
+        .map(|pin| (pin.provider_id.as_str(), pin.thread_id.as_str()))
+        .collect::<HashSet<_>>();
+    for thread in threads {
+        thread.is_pinned = pinned_keys.contains(&(thread.provider_id.as_str(), thread.id.as_str()));
+    }
+}
+
+/// Orderings for the merged thread list; pinned threads stay first under each of them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ThreadSortOrder {
+    #[default]
+    Recency,
+    Title,
+    Project,
+    Oldest,
+}
+
+pub fn parse_thread_sort_order(raw: &str) -> Result<ThreadSortOrder, String> {
+    match raw {
+        "recency" => Ok(ThreadSortOrder::Recency),
+        "title" => Ok(ThreadSortOrder::Title),
+        "project" => Ok(ThreadSortOrder::Project),
+        "oldest" => Ok(ThreadSortOrder::Oldest),
+        _ => Err(format!("Unsupported thread sort order: {raw}")),
+    }
+}
+
+/// Re-sorts a `list_threads` result. Each order is a stable sort over the recency order,
+/// so ties keep the most recently active thread first.
+pub fn sort_threads_by(threads: &mut [ThreadSummaryPayload], order: ThreadSortOrder) {
+    sort_thread_summaries(threads);
+    match order {
+        ThreadSortOrder::Recency => {}
+        ThreadSortOrder::Title => {
+            threads.sort_by_cached_key(|thread| (!thread.is_pinned, thread.title.to_lowercase()))
+        }
+        ThreadSortOrder::Project => threads.sort_by(|left, right| {
+            right
+                .is_pinned
+                .cmp(&left.is_pinned)
+                .then_with(|| left.project_path.cmp(&right.project_path))
+        }),
+        ThreadSortOrder::Oldest => {
+            threads.sort_by_key(|thread| (!thread.is_pinned, thread.last_active_at))
+        }
+    }
+}
+
+/// Pinned threads first, then most recently active.
 fn sort_thread_summaries(threads: &mut [ThreadSummaryPayload]) {
     threads.sort_by(|left, right| {
-        sortable_last_active_at(&right.last_active_at)
-            .cmp(&sortable_last_active_at(&left.last_active_at))
+        right
+            .is_pinned
+            .cmp(&left.is_pinned)
+            .then_with(|| right.last_active_at.cmp(&left.last_active_at))
             .then_with(|| left.provider_id.cmp(&right.provider_id))
             .then_with(|| left.id.cmp(&right.id))
             .then_with(|| left.project_path.cmp(&right.project_path))
@@ -212,30 +726,48 @@ fn sort_thread_summaries(threads: &mut [ThreadSummaryPayload]) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use provider_contract::Timestamp;
 
     fn build_thread(
         provider_id: &str,
         id: &str,
-        last_active_at: &str,
+        last_active_at: i64,
         project_path: &str,
     ) -> ThreadSummaryPayload {
         ThreadSummaryPayload {
             id: id.to_string(),
             provider_id: provider_id.to_string(),
             project_path: project_path.to_string(),
+            original_cwd: None,
+            model: None,
+            provider_backend: None,
             title: format!("{provider_id}-{id}"),
             tags: vec![provider_id.to_string()],
-            last_active_at: last_active_at.to_string(),
+            last_active_at,
             last_message_preview: None,
+            is_pinned: false,
+            agent_answering: None,
+            last_event_kind: None,
+            message_count: None,
         }
     }
 
     #[test]
     fn dedupe_thread_summaries_keeps_latest_record_for_same_provider_and_id() {
         let threads = vec![
-            build_thread("claude_code", "session-1", "1700000000000", "/workspace/old"),
-            build_thread("claude_code", "session-1", "1700000005000", "/workspace/new"),
-            build_thread("codex", "session-1", "1700000001000", "/workspace/codex"),
+            build_thread(
+                "claude_code",
+                "session-1",
+                1_700_000_000_000,
+                "/workspace/old",
+            ),
+            build_thread(
+                "claude_code",
+                "session-1",
+                1_700_000_005_000,
+                "/workspace/new",
+            ),
+            build_thread("codex", "session-1", 1_700_000_001_000, "/workspace/codex"),
         ];
 
         let mut deduped = dedupe_thread_summaries(threads);
@@ -248,4 +780,461 @@ mod tests {
         assert_eq!(deduped[1].provider_id, "codex");
         assert_eq!(deduped[1].id, "session-1");
     }
+
+    #[test]
+    fn dedupe_thread_summaries_keeps_same_id_from_different_providers() {
+        let threads = vec![
+            build_thread(
+                "claude_code",
+                "shared-id",
+                1_700_000_000_000,
+                "/workspace/a",
+            ),
+            build_thread("opencode", "shared-id", 1_700_000_002_000, "/workspace/b"),
+        ];
+
+        let deduped = dedupe_thread_summaries(threads);
+        let find = |provider_id: &str| {
+            deduped
+                .iter()
+                .filter(|thread| thread.provider_id == provider_id && thread.id == "shared-id")
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(deduped.len(), 2);
+        assert_eq!(find("claude_code").len(), 1);
+        assert_eq!(find("claude_code")[0].project_path, "/workspace/a");
+        assert_eq!(find("opencode").len(), 1);
+        assert_eq!(find("opencode")[0].project_path, "/workspace/b");
+    }
+
+    #[test]
+    fn sort_thread_summaries_orders_equal_timestamps_by_provider_and_id() {
+        let mut threads = vec![
+            build_thread("codex", "b", 1_700_000_000_000, "/workspace/b"),
+            build_thread("codex", "a", 1_700_000_000_000, "/workspace/a"),
+            build_thread("claude_code", "z", 1_700_000_000_000, "/workspace/z"),
+        ];
+
+        sort_thread_summaries(&mut threads);
+
+        let order = threads
+            .iter()
+            .map(|thread| format!("{}:{}", thread.provider_id, thread.id))
+            .collect::<Vec<_>>();
+        assert_eq!(order, vec!["claude_code:z", "codex:a", "codex:b"]);
+    }
+
+    #[test]
+    fn collect_recent_projects_dedupes_paths_across_providers() {
+        let threads = vec![
+            build_thread("claude_code", "a", 1_700_000_001_000, "/workspace/app"),
+            build_thread("codex", "b", 1_700_000_009_000, "/workspace/app/"),
+            build_thread("opencode", "c", 1_700_000_005_000, " /workspace/app "),
+            build_thread("codex", "d", 1_700_000_002_000, "."),
+            build_thread("opencode", "e", 1_700_000_003_000, ""),
+        ];
+
+        let projects = collect_recent_projects(&threads, 10);
+
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].project_path, "/workspace/app");
+        assert_eq!(projects[0].last_active_at, 1_700_000_009_000);
+        assert_eq!(projects[0].thread_count, 3);
+    }
+
+    #[test]
+    fn collect_recent_projects_orders_by_latest_activity_and_applies_limit() {
+        let threads = vec![
+            build_thread("claude_code", "a", 1_700_000_001_000, "/workspace/old"),
+            build_thread("codex", "b", 1_700_000_007_000, "/workspace/new"),
+            build_thread("opencode", "c", 1_700_000_004_000, "/workspace/mid"),
+            build_thread("claude_code", "d", 1_700_000_007_000, "/workspace/alpha"),
+        ];
+
+        let projects = collect_recent_projects(&threads, 3);
+        let paths = projects
+            .iter()
+            .map(|project| project.project_path.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            paths,
+            vec!["/workspace/alpha", "/workspace/new", "/workspace/mid"]
+        );
+    }
+
+    fn build_summary(provider_id: ProviderId, id: &str, last_active_at: i64) -> ThreadSummary {
+        ThreadSummary {
+            id: id.to_string(),
+            provider_id,
+            account_id: None,
+            project_path: "/workspace/app".to_string(),
+            original_cwd: None,
+            model: None,
+            provider_backend: None,
+            title: id.to_string(),
+            tags: Vec::new(),
+            last_active_at: Timestamp::from_epoch_millis(last_active_at),
+        }
+    }
+
+    #[test]
+    fn count_thread_summaries_matches_deduped_listing() {
+        let summaries = vec![
+            build_summary(ProviderId::ClaudeCode, "session-1", 1_700_000_000_000),
+            build_summary(ProviderId::ClaudeCode, "session-1", 1_700_000_005_000),
+            build_summary(ProviderId::ClaudeCode, "session-2", 1_700_000_001_000),
+            build_summary(ProviderId::Codex, "session-1", 1_700_000_002_000),
+            build_summary(ProviderId::OpenCode, "ses_1", 1_700_000_003_000),
+        ];
+        let listing = dedupe_thread_summaries(
+            summaries
+                .iter()
+                .map(|summary| {
+                    build_thread(
+                        summary.provider_id.as_str(),
+                        &summary.id,
+                        summary.last_active_at.as_millis(),
+                        &summary.project_path,
+                    )
+                })
+                .collect(),
+        );
+
+        let counts = count_thread_summaries(&summaries);
+
+        assert_eq!(counts.total, listing.len());
+        assert_eq!(counts.per_provider.get("claude_code"), Some(&2));
+        assert_eq!(counts.per_provider.get("codex"), Some(&1));
+        assert_eq!(counts.per_provider.get("opencode"), Some(&1));
+    }
+
+    #[test]
+    fn count_provider_threads_matches_listing_for_a_project() {
+        let root = tempfile::tempdir().expect("temp dir should be created");
+        let sessions_dir = root
+            .path()
+            .join("sessions")
+            .join("2026")
+            .join("02")
+            .join("12");
+        std::fs::create_dir_all(&sessions_dir).expect("sessions dir should be created");
+        for (id, cwd) in [
+            ("codex-app", "/workspace/app"),
+            ("codex-app-nested", "/workspace/app/api"),
+            ("codex-other", "/workspace/other"),
+        ] {
+            std::fs::write(
+                sessions_dir.join(format!("{id}.jsonl")),
+                format!(
+                    r#"{{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{{"id":"{id}","cwd":"{cwd}"}}}}"#
+                ),
+            )
+            .expect("session file should be written");
+        }
+        let registry = ProviderRegistry::from_providers(vec![RegisteredProvider::Codex(
+            CodexAdapter::new().with_home_dir(root.path()),
+        )]);
+
+        let (listed, errors) = collect_provider_threads(registry.providers(), |provider| {
+            provider.list_thread_overviews(Some("/workspace/app"), ThreadOverviewOptions::default())
+        });
+        let counts = count_provider_threads(registry.providers(), Some("/workspace/app"))
+            .expect("threads should be countable");
+
+        assert!(errors.is_empty());
+        assert_eq!(listed.len(), 2);
+        assert_eq!(counts.total, listed.len());
+        assert_eq!(counts.per_provider.get("codex"), Some(&2));
+    }
+
+    #[test]
+    fn partition_providers_by_cli_lists_providers_without_cli() {
+        let registry = ProviderRegistry::new();
+
+        let (scanned, missing) = partition_providers_by_cli(registry.providers(), |provider| {
+            provider.provider_id() == ProviderId::Codex
+        });
+
+        assert_eq!(scanned, vec!["codex", "claude_code", "opencode"]);
+        assert_eq!(missing, vec!["claude_code", "opencode"]);
+    }
+
+    #[test]
+    fn collect_provider_threads_keeps_other_providers_when_one_fails() {
+        let registry = ProviderRegistry::new();
+
+        let (threads, errors) = collect_provider_threads(registry.providers(), |provider| {
+            match provider.provider_id() {
+                ProviderId::ClaudeCode => Err(provider_contract::ProviderError {
+                    code: provider_contract::ProviderErrorCode::InvalidResponse,
+                    message: "projects dir unreadable".to_string(),
+                    retryable: false,
+                }),
+                provider_id => Ok(vec![build_thread(
+                    provider_id.as_str(),
+                    "session-1",
+                    1_700_000_000_000,
+                    "/workspace/a",
+                )]),
+            }
+        });
+
+        let providers = threads
+            .iter()
+            .map(|thread| thread.provider_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(providers, vec!["codex", "opencode"]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].provider_id, "claude_code");
+        assert!(errors[0].message.contains("projects dir unreadable"));
+    }
+
+    #[test]
+    fn stream_provider_threads_emits_each_provider_batch_then_completes() {
+        let registry = ProviderRegistry::new();
+        let pinned = [PinnedThread {
+            provider_id: "opencode".to_string(),
+            thread_id: "ses-old".to_string(),
+            pinned_at: 1_700_000_010_000_i64.to_string(),
+        }];
+        let mut events = Vec::new();
+
+        stream_provider_threads(
+            registry.providers(),
+            |provider| match provider.provider_id() {
+                ProviderId::Codex => Ok(vec![
+                    build_thread("codex", "codex-old", 1_700_000_000_000, "/workspace/a"),
+                    build_thread("codex", "codex-new", 1_700_000_005_000, "/workspace/a"),
+                ]),
+                ProviderId::ClaudeCode => Err(provider_contract::ProviderError {
+                    code: provider_contract::ProviderErrorCode::InvalidResponse,
+                    message: "projects dir unreadable".to_string(),
+                    retryable: false,
+                }),
+                ProviderId::OpenCode => Ok(vec![
+                    build_thread("opencode", "ses-new", 1_700_000_009_000, "/workspace/a"),
+                    build_thread("opencode", "ses-old", 1_700_000_001_000, "/workspace/a"),
+                ]),
+            },
+            |threads| mark_pinned_threads(threads, &pinned),
+            || panic!("fallback should only run when nothing was found"),
+            &mut |event| events.push(event),
+        );
+
+        let batches = events
+            .iter()
+            .map(|event| match event {
+                ThreadScanEvent::Discovered(payload) => payload
+                    .threads
+                    .iter()
+                    .map(|thread| thread.id.as_str())
+                    .collect::<Vec<_>>(),
+                ThreadScanEvent::Complete(_) => vec!["<complete>"],
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            batches,
+            vec![
+                vec!["codex-new", "codex-old"],
+                vec!["ses-old", "ses-new"],
+                vec!["<complete>"],
+            ]
+        );
+        let ThreadScanEvent::Complete(complete) = &events[2] else {
+            panic!("last event should complete the scan");
+        };
+        assert_eq!(complete.thread_count, 4);
+        assert_eq!(complete.provider_errors.len(), 1);
+        assert_eq!(complete.provider_errors[0].provider_id, "claude_code");
+    }
+
+    #[test]
+    fn stream_provider_threads_falls_back_when_no_provider_finds_threads() {
+        let registry = ProviderRegistry::new();
+        let mut events = Vec::new();
+
+        stream_provider_threads(
+            registry.providers(),
+            |_| Ok(Vec::new()),
+            |_| {},
+            || {
+                vec![build_thread(
+                    "codex",
+                    "demo-1",
+                    1_700_000_000_000,
+                    "/workspace/demo",
+                )]
+            },
+            &mut |event| events.push(event),
+        );
+
+        assert_eq!(events.len(), 2);
+        assert!(
+            matches!(&events[0], ThreadScanEvent::Discovered(payload) if payload.threads[0].id == "demo-1")
+        );
+        assert!(
+            matches!(&events[1], ThreadScanEvent::Complete(complete) if complete.thread_count == 1)
+        );
+    }
+
+    fn sorted_ids(order: ThreadSortOrder) -> Vec<String> {
+        let mut pinned = build_thread("codex", "pinned", 1_700_000_000_000, "/workspace/z");
+        pinned.is_pinned = true;
+        let mut threads = vec![
+            build_thread("codex", "b-old", 1_700_000_001_000, "/workspace/b"),
+            pinned,
+            build_thread("claude_code", "a-new", 1_700_000_009_000, "/workspace/b"),
+            build_thread("opencode", "c-mid", 1_700_000_005_000, "/workspace/a"),
+        ];
+        threads[0].title = "Aardvark".to_string();
+        threads[2].title = "Alpha".to_string();
+        threads[3].title = "alpha".to_string();
+
+        sort_threads_by(&mut threads, order);
+        threads.into_iter().map(|thread| thread.id).collect()
+    }
+
+    #[test]
+    fn sort_threads_by_orders_each_strategy_with_pinned_first() {
+        assert_eq!(
+            sorted_ids(ThreadSortOrder::Recency),
+            vec!["pinned", "a-new", "c-mid", "b-old"]
+        );
+        assert_eq!(
+            sorted_ids(ThreadSortOrder::Title),
+            vec!["pinned", "b-old", "a-new", "c-mid"]
+        );
+        assert_eq!(
+            sorted_ids(ThreadSortOrder::Project),
+            vec!["pinned", "c-mid", "a-new", "b-old"]
+        );
+        assert_eq!(
+            sorted_ids(ThreadSortOrder::Oldest),
+            vec!["pinned", "b-old", "c-mid", "a-new"]
+        );
+    }
+
+    #[test]
+    fn parse_thread_sort_order_rejects_unknown_orders() {
+        assert_eq!(
+            parse_thread_sort_order("oldest"),
+            Ok(ThreadSortOrder::Oldest)
+        );
+        assert_eq!(
+            parse_thread_sort_order("random"),
+            Err("Unsupported thread sort order: random".to_string())
+        );
+    }
+
+    #[test]
+    fn retain_worktree_threads_keeps_only_the_requested_checkout() {
+        let root = tempfile::tempdir().expect("temp dir should be created");
+        let repo = root.path().join("repo");
+        let linked = root.path().join("repo-feature");
+        std::fs::create_dir_all(repo.join(".git")).expect("git dir should be created");
+        std::fs::create_dir_all(linked.join("src")).expect("worktree should be created");
+        std::fs::write(
+            linked.join(".git"),
+            "gitdir: ../repo/.git/worktrees/feature\n",
+        )
+        .expect("gitdir file should be written");
+        let repo_path = repo.to_string_lossy().to_string();
+        let linked_path = linked.to_string_lossy().to_string();
+        let mut grouped =
+            build_thread("claude_code", "linked-src", 1_700_000_000_002, &linked_path);
+        grouped.original_cwd = Some(linked.join("src").to_string_lossy().to_string());
+        let threads = vec![
+            build_thread("codex", "main", 1_700_000_000_000, &repo_path),
+            build_thread("codex", "linked", 1_700_000_000_001, &linked_path),
+            grouped,
+        ];
+
+        let mut main_only = threads.clone();
+        retain_worktree_threads(&mut main_only, Some(&repo_path));
+        assert_eq!(main_only.len(), 1);
+        assert_eq!(main_only[0].id, "main");
+
+        let mut linked_only = threads.clone();
+        retain_worktree_threads(&mut linked_only, Some(&linked_path));
+        let ids = linked_only
+            .iter()
+            .map(|thread| thread.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["linked", "linked-src"]);
+
+        let mut unfiltered = threads;
+        retain_worktree_threads(&mut unfiltered, None);
+        assert_eq!(unfiltered.len(), 3);
+    }
+
+    #[test]
+    fn pinned_threads_are_flagged_and_sorted_before_recent_threads() {
+        let mut threads = vec![
+            build_thread("codex", "recent", 1_700_000_009_000, "/workspace/a"),
+            build_thread("claude_code", "old", 1_700_000_001_000, "/workspace/a"),
+            build_thread("codex", "middle", 1_700_000_005_000, "/workspace/a"),
+            build_thread("opencode", "old", 1_700_000_000_000, "/workspace/a"),
+        ];
+        let pinned = vec![
+            PinnedThread {
+                provider_id: "claude_code".to_string(),
+                thread_id: "old".to_string(),
+                pinned_at: "2025-01-01T00:00:00.000Z".to_string(),
+            },
+            PinnedThread {
+                provider_id: "codex".to_string(),
+                thread_id: "middle".to_string(),
+                pinned_at: "2025-01-02T00:00:00.000Z".to_string(),
+            },
+        ];
+
+        mark_pinned_threads(&mut threads, &pinned);
+        sort_thread_summaries(&mut threads);
+
+        let order = threads
+            .iter()
+            .map(|thread| format!("{}:{}:{}", thread.provider_id, thread.id, thread.is_pinned))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            order,
+            vec![
+                "codex:middle:true",
+                "claude_code:old:true",
+                "codex:recent:false",
+                "opencode:old:false",
+            ]
+        );
+        let payload = serde_json::to_value(&threads[0]).expect("payload should serialize");
+        assert_eq!(payload["isPinned"], true);
+    }
+
+    fn comparison_side(thread_id: &str, files: &[&str]) -> ThreadComparisonSidePayload {
+        ThreadComparisonSidePayload {
+            provider_id: "codex".to_string(),
+            thread_id: thread_id.to_string(),
+            final_assistant_message: Some(format!("done with {thread_id}")),
+            touched_files: files.iter().map(|file| file.to_string()).collect(),
+            message_count: 2,
+            duration_ms: Some(1_000),
+        }
+    }
+
+    #[test]
+    fn build_thread_comparison_lists_files_touched_by_both_threads() {
+        let comparison = build_thread_comparison(
+            comparison_side("left", &["src/lib.rs", "README.md", "src/main.rs"]),
+            comparison_side("right", &["src/main.rs", "Cargo.toml", "src/lib.rs"]),
+        );
+
+        assert_eq!(comparison.common_files, vec!["src/lib.rs", "src/main.rs"]);
+        assert_eq!(
+            comparison.left.final_assistant_message.as_deref(),
+            Some("done with left")
+        );
+        let payload = serde_json::to_value(&comparison).expect("payload should serialize");
+        assert_eq!(payload["right"]["touchedFiles"][1], "Cargo.toml");
+        assert_eq!(payload["left"]["durationMs"], 1_000);
+    }
 }
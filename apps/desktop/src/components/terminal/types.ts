@@ -40,6 +40,8 @@ export interface ThreadRuntimeState {
   agentAnswering: boolean;
   lastEventKind?: string | null;
   lastEventAtMs?: number | null;
+  /** Only set when the state was requested with `checkProcess`. */
+  processRunning?: boolean | null;
 }
 
 export interface TerminalSessionState {
@@ -29,7 +29,7 @@ interface ThreadFolderGroupProps<T extends ThreadListThreadItem> {
   onSelectThread: (threadKey: string) => void;
   onCreateThread: (projectPath: string, providerId: ThreadProviderId) => Promise<void>;
   isCreatingThread: boolean;
-  formatLastActive: (raw: string) => string;
+  formatLastActive: (raw: number) => string;
   getPreview: (thread: T) => string;
 }
 
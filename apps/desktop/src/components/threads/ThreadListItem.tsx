@@ -7,7 +7,7 @@ export interface ThreadListThreadItem {
   id: string;
   providerId: string;
   title: string;
-  lastActiveAt: string;
+  lastActiveAt: number;
   lastMessagePreview?: string | null;
 }
 
@@ -15,7 +15,7 @@ interface ThreadListItemProps<T extends ThreadListThreadItem> {
   thread: T;
   isActive: boolean;
   onSelectThread: (threadKey: string) => void;
-  formatLastActive: (raw: string) => string;
+  formatLastActive: (raw: number) => string;
   getPreview: (thread: T) => string;
 }
 
@@ -104,8 +104,8 @@ export function useThreads(): UseThreadsResult {
       })
       .filter((group) => group.key !== ".")
       .sort((a, b) => {
-        const aLatest = a.threads[0]?.lastActiveAt ?? "";
-        const bLatest = b.threads[0]?.lastActiveAt ?? "";
+        const aLatest = a.threads[0]?.lastActiveAt ?? 0;
+        const bLatest = b.threads[0]?.lastActiveAt ?? 0;
         return sortableTimestamp(bLatest) - sortableTimestamp(aLatest);
       });
   }, [threads]);
@@ -15,7 +15,7 @@ function buildThread(input: Partial<AgentThreadSummary> & Pick<AgentThreadSummar
     projectPath: input.projectPath ?? "/workspace/demo",
     title: input.title ?? "Demo",
     tags: input.tags ?? [],
-    lastActiveAt: input.lastActiveAt ?? "1700000000000",
+    lastActiveAt: input.lastActiveAt ?? 1_700_000_000_000,
     lastMessagePreview: input.lastMessagePreview ?? null,
   };
 }
@@ -58,13 +58,13 @@ describe("pickCreatedThread", () => {
         providerId: "claude_code",
         id: "same-id",
         projectPath: "/workspace/demo",
-        lastActiveAt: "1700000000000",
+        lastActiveAt: 1_700_000_000_000,
       }),
       buildThread({
         providerId: "codex",
         id: "same-id",
         projectPath: "/workspace/demo",
-        lastActiveAt: "1700000001000",
+        lastActiveAt: 1_700_000_001_000,
       }),
     ];
 
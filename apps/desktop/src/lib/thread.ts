@@ -1,9 +1,9 @@
 import type { AgentThreadSummary } from "@/types";
 
-export function formatLastActive(raw: string): string {
+export function formatLastActive(raw: string | number): string {
   const value = toTimestampMs(raw);
   if (!Number.isFinite(value)) {
-    return raw;
+    return String(raw);
   }
 
   return new Intl.DateTimeFormat("zh-CN", {
@@ -14,16 +14,16 @@ export function formatLastActive(raw: string): string {
   }).format(new Date(value));
 }
 
-export function toTimestampMs(raw: string): number {
+export function toTimestampMs(raw: string | number): number {
   const numeric = Number(raw);
 
   if (Number.isFinite(numeric)) {
     return numeric < 1_000_000_000_000 ? numeric * 1000 : numeric;
   }
-  return Date.parse(raw);
+  return Date.parse(String(raw));
 }
 
-export function sortableTimestamp(raw: string): number {
+export function sortableTimestamp(raw: string | number): number {
   const timestamp = toTimestampMs(raw);
   return Number.isFinite(timestamp) ? timestamp : 0;
 }
@@ -2,9 +2,12 @@ export interface AgentThreadSummary {
   id: string;
   providerId: "claude_code" | string;
   projectPath: string;
+  originalCwd?: string | null;
+  model?: string | null;
+  providerBackend?: string | null;
   title: string;
   tags: string[];
-  lastActiveAt: string;
+  lastActiveAt: number;
   lastMessagePreview?: string | null;
 }
 
@@ -0,0 +1,8 @@
+-- Threads pinned to the top of the thread list, keyed by provider and session id.
+
+CREATE TABLE IF NOT EXISTS pinned_threads (
+  provider_id TEXT NOT NULL,
+  thread_id TEXT NOT NULL,
+  pinned_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+  PRIMARY KEY(provider_id, thread_id)
+);
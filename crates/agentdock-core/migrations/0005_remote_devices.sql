@@ -0,0 +1,52 @@
+-- Rebuilds the remote tables from 0001 and copies their rows across. Devices are unique
+-- by public key, and sessions reference provider threads by `(provider_id, thread_id)`
+-- because those threads are not stored in `threads`.
+--
+-- Devices paired before public keys existed get the placeholder key `legacy:<id>` and
+-- platform `unknown`. Older sessions take their provider from `threads` and keep their
+-- `action` and `result`.
+
+ALTER TABLE remote_sessions RENAME TO remote_sessions_0001;
+ALTER TABLE remote_devices RENAME TO remote_devices_0001;
+
+CREATE TABLE remote_devices (
+  id TEXT PRIMARY KEY,
+  device_name TEXT NOT NULL,
+  platform TEXT NOT NULL,
+  public_key TEXT NOT NULL UNIQUE,
+  paired_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+  last_seen_at TEXT,
+  revoked_at TEXT
+);
+
+CREATE TABLE remote_sessions (
+  id TEXT PRIMARY KEY,
+  device_id TEXT NOT NULL,
+  provider_id TEXT NOT NULL,
+  thread_id TEXT NOT NULL,
+  action TEXT,
+  result TEXT,
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+  FOREIGN KEY(device_id) REFERENCES remote_devices(id) ON DELETE CASCADE
+);
+
+INSERT INTO remote_devices (id, device_name, platform, public_key, paired_at, last_seen_at, revoked_at)
+SELECT id, device_name, 'unknown', 'legacy:' || id, paired_at, last_seen_at, revoked_at
+FROM remote_devices_0001;
+
+INSERT INTO remote_sessions (id, device_id, provider_id, thread_id, action, result, created_at)
+SELECT
+  sessions.id,
+  sessions.device_id,
+  COALESCE(threads.provider_id, 'unknown'),
+  COALESCE(sessions.thread_id, ''),
+  sessions.action,
+  sessions.result,
+  sessions.created_at
+FROM remote_sessions_0001 AS sessions
+LEFT JOIN threads ON threads.id = sessions.thread_id;
+
+DROP TABLE remote_sessions_0001;
+DROP TABLE remote_devices_0001;
+
+CREATE INDEX IF NOT EXISTS idx_remote_sessions_device_id ON remote_sessions(device_id);
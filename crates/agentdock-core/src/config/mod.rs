@@ -0,0 +1,424 @@
+use std::collections::BTreeMap;
+
+use provider_contract::ProviderId;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub const TERMINAL_PROFILE_SCOPE: &str = "terminal_profile";
+pub const LAUNCH_TEMPLATES_SCOPE: &str = "launch_templates";
+pub const DEFAULT_PROJECT_SCOPE: &str = "default_project";
+pub const THREAD_ID_PLACEHOLDER: &str = "{thread_id}";
+pub const PROJECT_PATH_PLACEHOLDER: &str = "{project_path}";
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("sqlite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("config not found: {0}")]
+    NotFound(String),
+    #[error("validation error: {0}")]
+    Validation(String),
+}
+
+/// Named env-var set and working directory applied to embedded terminal sessions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalProfile {
+    pub name: String,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub working_directory: Option<String>,
+}
+
+/// CLI arguments placed after a provider binary to resume or start a session.
+/// `{thread_id}` and `{project_path}` are substituted with shell-quoted values.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchTemplates {
+    pub resume: String,
+    #[serde(default)]
+    pub new_thread: String,
+}
+
+impl LaunchTemplates {
+    pub fn default_for(provider_id: ProviderId) -> Self {
+        let resume = match provider_id {
+            ProviderId::ClaudeCode => "--resume {thread_id}",
+            ProviderId::Codex => "resume {thread_id}",
+            ProviderId::OpenCode => "--session {thread_id}",
+        };
+        Self {
+            resume: resume.to_string(),
+            new_thread: String::new(),
+        }
+    }
+}
+
+pub fn get_config<T: DeserializeOwned>(
+    connection: &Connection,
+    id: &str,
+) -> Result<Option<T>, ConfigError> {
+    let payload_json = connection
+        .query_row(
+            "SELECT payload_json FROM configs WHERE id = ?1",
+            params![id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?;
+
+    payload_json
+        .map(|payload_json| serde_json::from_str(&payload_json).map_err(ConfigError::from))
+        .transpose()
+}
+
+pub fn list_configs<T: DeserializeOwned>(
+    connection: &Connection,
+    scope: &str,
+) -> Result<Vec<T>, ConfigError> {
+    let mut stmt =
+        connection.prepare("SELECT payload_json FROM configs WHERE scope = ?1 ORDER BY id ASC")?;
+    let rows = stmt
+        .query_map(params![scope], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    rows.iter()
+        .map(|payload_json| serde_json::from_str(payload_json).map_err(ConfigError::from))
+        .collect()
+}
+
+pub fn put_config<T: Serialize>(
+    connection: &Connection,
+    id: &str,
+    scope: &str,
+    value: &T,
+) -> Result<(), ConfigError> {
+    let payload_json = serde_json::to_string(value)?;
+    connection.execute(
+        "INSERT INTO configs (id, scope, payload_json, updated_at)
+         VALUES (?1, ?2, ?3, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+         ON CONFLICT(id) DO UPDATE SET
+            scope = excluded.scope,
+            payload_json = excluded.payload_json,
+            updated_at = excluded.updated_at",
+        params![id, scope, payload_json],
+    )?;
+    Ok(())
+}
+
+pub fn delete_config(connection: &Connection, id: &str) -> Result<(), ConfigError> {
+    let rows_affected = connection.execute("DELETE FROM configs WHERE id = ?1", params![id])?;
+    if rows_affected == 0 {
+        return Err(ConfigError::NotFound(id.to_string()));
+    }
+    Ok(())
+}
+
+pub fn list_terminal_profiles(
+    connection: &Connection,
+) -> Result<Vec<TerminalProfile>, ConfigError> {
+    list_configs(connection, TERMINAL_PROFILE_SCOPE)
+}
+
+pub fn get_terminal_profile(
+    connection: &Connection,
+    name: &str,
+) -> Result<TerminalProfile, ConfigError> {
+    get_config(connection, &terminal_profile_config_id(name.trim()))?
+        .ok_or_else(|| ConfigError::NotFound(format!("terminal profile `{}`", name.trim())))
+}
+
+pub fn save_terminal_profile(
+    connection: &Connection,
+    profile: &TerminalProfile,
+) -> Result<(), ConfigError> {
+    let profile = normalize_terminal_profile(profile)?;
+    put_config(
+        connection,
+        &terminal_profile_config_id(&profile.name),
+        TERMINAL_PROFILE_SCOPE,
+        &profile,
+    )
+}
+
+pub fn delete_terminal_profile(connection: &Connection, name: &str) -> Result<(), ConfigError> {
+    let name = name.trim();
+    match delete_config(connection, &terminal_profile_config_id(name)) {
+        Err(ConfigError::NotFound(_)) => {
+            Err(ConfigError::NotFound(format!("terminal profile `{name}`")))
+        }
+        result => result,
+    }
+}
+
+/// Saved templates for the provider, or the built-in defaults.
+pub fn get_launch_templates(
+    connection: &Connection,
+    provider_id: ProviderId,
+) -> Result<LaunchTemplates, ConfigError> {
+    let saved = get_config(connection, &launch_templates_config_id(provider_id))?;
+    Ok(saved.unwrap_or_else(|| LaunchTemplates::default_for(provider_id)))
+}
+
+pub fn save_launch_templates(
+    connection: &Connection,
+    provider_id: ProviderId,
+    templates: &LaunchTemplates,
+) -> Result<(), ConfigError> {
+    let templates = normalize_launch_templates(provider_id, templates)?;
+    put_config(
+        connection,
+        &launch_templates_config_id(provider_id),
+        LAUNCH_TEMPLATES_SCOPE,
+        &templates,
+    )
+}
+
+/// Drops any saved templates so the defaults apply again.
+pub fn reset_launch_templates(
+    connection: &Connection,
+    provider_id: ProviderId,
+) -> Result<(), ConfigError> {
+    match delete_config(connection, &launch_templates_config_id(provider_id)) {
+        Err(ConfigError::NotFound(_)) => Ok(()),
+        result => result,
+    }
+}
+
+pub fn get_default_project(connection: &Connection) -> Result<Option<String>, ConfigError> {
+    get_config(connection, DEFAULT_PROJECT_SCOPE)
+}
+
+/// Saves the project applied when commands are called without `project_path`. A
+/// missing or blank path clears it.
+pub fn set_default_project(
+    connection: &Connection,
+    project_path: Option<&str>,
+) -> Result<(), ConfigError> {
+    match project_path.map(str::trim).filter(|path| !path.is_empty()) {
+        Some(path) => put_config(
+            connection,
+            DEFAULT_PROJECT_SCOPE,
+            DEFAULT_PROJECT_SCOPE,
+            &path,
+        ),
+        None => match delete_config(connection, DEFAULT_PROJECT_SCOPE) {
+            Err(ConfigError::NotFound(_)) => Ok(()),
+            result => result,
+        },
+    }
+}
+
+/// `project_path` when given, otherwise the saved default project.
+pub fn project_path_or_default(
+    connection: &Connection,
+    project_path: Option<&str>,
+) -> Result<Option<String>, ConfigError> {
+    match project_path {
+        Some(path) => Ok(Some(path.to_string())),
+        None => get_default_project(connection),
+    }
+}
+
+fn launch_templates_config_id(provider_id: ProviderId) -> String {
+    format!("{LAUNCH_TEMPLATES_SCOPE}:{}", provider_id.as_str())
+}
+
+fn normalize_launch_templates(
+    provider_id: ProviderId,
+    templates: &LaunchTemplates,
+) -> Result<LaunchTemplates, ConfigError> {
+    let resume = templates.resume.trim();
+    if !resume.contains(THREAD_ID_PLACEHOLDER) {
+        return Err(ConfigError::Validation(format!(
+            "resume template for `{}` must contain {THREAD_ID_PLACEHOLDER}",
+            provider_id.as_str()
+        )));
+    }
+    let new_thread = templates.new_thread.trim();
+    if new_thread.contains(THREAD_ID_PLACEHOLDER) {
+        return Err(ConfigError::Validation(format!(
+            "new thread template for `{}` cannot use {THREAD_ID_PLACEHOLDER}",
+            provider_id.as_str()
+        )));
+    }
+
+    Ok(LaunchTemplates {
+        resume: resume.to_string(),
+        new_thread: new_thread.to_string(),
+    })
+}
+
+fn terminal_profile_config_id(name: &str) -> String {
+    format!("{TERMINAL_PROFILE_SCOPE}:{name}")
+}
+
+fn normalize_terminal_profile(profile: &TerminalProfile) -> Result<TerminalProfile, ConfigError> {
+    let name = profile.name.trim();
+    if name.is_empty() {
+        return Err(ConfigError::Validation(
+            "terminal profile name is required".to_string(),
+        ));
+    }
+
+    let mut env = BTreeMap::new();
+    for (key, value) in &profile.env {
+        let key = key.trim();
+        if key.is_empty() || key.contains('=') {
+            return Err(ConfigError::Validation(format!(
+                "invalid environment variable name `{key}` in terminal profile `{name}`"
+            )));
+        }
+        env.insert(key.to_string(), value.clone());
+    }
+
+    let working_directory = profile
+        .working_directory
+        .as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(str::to_string);
+
+    Ok(TerminalProfile {
+        name: name.to_string(),
+        env,
+        working_directory,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_test_db() -> Connection {
+        let mut conn = Connection::open_in_memory().expect("in-memory sqlite should open");
+        crate::db::run_migrations(&mut conn).expect("migrations should run");
+        conn
+    }
+
+    fn sample_profile(name: &str) -> TerminalProfile {
+        TerminalProfile {
+            name: name.to_string(),
+            env: BTreeMap::from([("DIRENV_DIR".to_string(), "/workspace/app".to_string())]),
+            working_directory: Some("/workspace/app".to_string()),
+        }
+    }
+
+    #[test]
+    fn terminal_profile_crud_works() {
+        let conn = setup_test_db();
+        save_terminal_profile(&conn, &sample_profile("direnv")).expect("save should succeed");
+        save_terminal_profile(&conn, &sample_profile("alpha")).expect("save should succeed");
+
+        let loaded = get_terminal_profile(&conn, " direnv ").expect("profile should exist");
+        assert_eq!(loaded, sample_profile("direnv"));
+
+        let names = list_terminal_profiles(&conn)
+            .expect("list should succeed")
+            .into_iter()
+            .map(|profile| profile.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["alpha", "direnv"]);
+
+        delete_terminal_profile(&conn, "direnv").expect("delete should succeed");
+        assert!(matches!(
+            get_terminal_profile(&conn, "direnv"),
+            Err(ConfigError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn save_terminal_profile_rejects_invalid_env_names() {
+        let conn = setup_test_db();
+        let mut profile = sample_profile("broken");
+        profile.env.insert("A=B".to_string(), "value".to_string());
+
+        assert!(matches!(
+            save_terminal_profile(&conn, &profile),
+            Err(ConfigError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn launch_templates_default_until_saved_and_after_reset() {
+        let conn = setup_test_db();
+        assert_eq!(
+            get_launch_templates(&conn, ProviderId::Codex).expect("load should succeed"),
+            LaunchTemplates::default_for(ProviderId::Codex)
+        );
+
+        let custom = LaunchTemplates {
+            resume: "  resume --cd {project_path} {thread_id} ".to_string(),
+            new_thread: "--cd {project_path}".to_string(),
+        };
+        save_launch_templates(&conn, ProviderId::Codex, &custom).expect("save should succeed");
+        let loaded = get_launch_templates(&conn, ProviderId::Codex).expect("load should succeed");
+        assert_eq!(loaded.resume, "resume --cd {project_path} {thread_id}");
+        assert_eq!(loaded.new_thread, "--cd {project_path}");
+        assert_eq!(
+            get_launch_templates(&conn, ProviderId::ClaudeCode).expect("load should succeed"),
+            LaunchTemplates::default_for(ProviderId::ClaudeCode)
+        );
+
+        reset_launch_templates(&conn, ProviderId::Codex).expect("reset should succeed");
+        reset_launch_templates(&conn, ProviderId::Codex).expect("reset should be idempotent");
+        assert_eq!(
+            get_launch_templates(&conn, ProviderId::Codex).expect("load should succeed"),
+            LaunchTemplates::default_for(ProviderId::Codex)
+        );
+    }
+
+    #[test]
+    fn save_launch_templates_requires_thread_id_in_resume() {
+        let conn = setup_test_db();
+        let templates = LaunchTemplates {
+            resume: "--continue".to_string(),
+            new_thread: String::new(),
+        };
+
+        assert!(matches!(
+            save_launch_templates(&conn, ProviderId::ClaudeCode, &templates),
+            Err(ConfigError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn default_project_applies_only_when_project_path_is_omitted() {
+        let conn = setup_test_db();
+        assert_eq!(
+            project_path_or_default(&conn, None).expect("resolve should succeed"),
+            None
+        );
+
+        set_default_project(&conn, Some(" /workspace/app ")).expect("set should succeed");
+        assert_eq!(
+            get_default_project(&conn)
+                .expect("load should succeed")
+                .as_deref(),
+            Some("/workspace/app")
+        );
+        assert_eq!(
+            project_path_or_default(&conn, None)
+                .expect("resolve should succeed")
+                .as_deref(),
+            Some("/workspace/app")
+        );
+        assert_eq!(
+            project_path_or_default(&conn, Some("/workspace/other"))
+                .expect("resolve should succeed")
+                .as_deref(),
+            Some("/workspace/other")
+        );
+
+        set_default_project(&conn, None).expect("clear should succeed");
+        set_default_project(&conn, Some("  ")).expect("clear should be idempotent");
+        assert_eq!(
+            get_default_project(&conn).expect("load should succeed"),
+            None
+        );
+    }
+}
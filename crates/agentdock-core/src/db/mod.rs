@@ -1,8 +1,15 @@
+mod remote;
+
 use std::path::Path;
 
 use rusqlite::{params, Connection};
 use thiserror::Error;
 
+pub use remote::{
+    create_remote_session, list_remote_devices, list_remote_sessions, register_remote_device,
+    RemoteDevice, RemoteError, RemoteSession,
+};
+
 const MIGRATIONS: &[(&str, &str)] = &[
     ("0001_init", include_str!("../../migrations/0001_init.sql")),
     (
@@ -13,6 +20,14 @@ const MIGRATIONS: &[(&str, &str)] = &[
         "0003_mcp_management",
         include_str!("../../migrations/0003_mcp_management.sql"),
     ),
+    (
+        "0004_pinned_threads",
+        include_str!("../../migrations/0004_pinned_threads.sql"),
+    ),
+    (
+        "0005_remote_devices",
+        include_str!("../../migrations/0005_remote_devices.sql"),
+    ),
 ];
 
 #[derive(Debug, Error)]
@@ -35,6 +50,13 @@ pub fn init_db(path: &Path) -> Result<Connection, DbError> {
 }
 
 pub fn run_migrations(connection: &mut Connection) -> Result<(), DbError> {
+    apply_migrations(connection, MIGRATIONS)
+}
+
+fn apply_migrations(
+    connection: &mut Connection,
+    migrations: &[(&str, &str)],
+) -> Result<(), DbError> {
     connection.execute_batch(
         "CREATE TABLE IF NOT EXISTS schema_migrations (
             id TEXT PRIMARY KEY,
@@ -42,7 +64,7 @@ pub fn run_migrations(connection: &mut Connection) -> Result<(), DbError> {
         );",
     )?;
 
-    for (migration_id, migration_sql) in MIGRATIONS {
+    for (migration_id, migration_sql) in migrations {
         let already_applied: i64 = connection.query_row(
             "SELECT COUNT(1) FROM schema_migrations WHERE id = ?1",
             [migration_id],
@@ -67,7 +89,7 @@ pub fn run_migrations(connection: &mut Connection) -> Result<(), DbError> {
 
 #[cfg(test)]
 mod tests {
-    use super::{init_db, run_migrations};
+    use super::{apply_migrations, init_db, run_migrations, MIGRATIONS};
     use rusqlite::Connection;
 
     fn table_exists(conn: &Connection, name: &str) -> bool {
@@ -101,6 +123,7 @@ mod tests {
             "switch_events",
             "remote_devices",
             "remote_sessions",
+            "pinned_threads",
         ];
 
         for table in expected_tables {
@@ -119,7 +142,65 @@ mod tests {
                 row.get(0)
             })
             .expect("count query should succeed");
-        assert_eq!(applied, 3);
+        assert_eq!(applied, 5);
+    }
+
+    #[test]
+    fn remote_devices_migration_keeps_existing_rows() {
+        let mut conn = Connection::open_in_memory().expect("in-memory sqlite should open");
+        conn.pragma_update(None, "foreign_keys", "ON")
+            .expect("foreign keys should be enabled");
+        apply_migrations(&mut conn, &MIGRATIONS[..4]).expect("0001-0004 should run");
+        conn.execute_batch(
+            "INSERT INTO providers (id, name, status) VALUES ('codex', 'Codex', 'ok');
+             INSERT INTO threads (id, provider_id, project_path, title, last_active_at)
+               VALUES ('thread-1', 'codex', '/workspace/app', 'Thread', '2026-01-01T00:00:00Z');
+             INSERT INTO remote_devices (id, device_name, paired_at)
+               VALUES ('device-1', 'Phone', '2026-01-01T00:00:00Z');
+             INSERT INTO remote_sessions (id, device_id, thread_id, action, result)
+               VALUES ('session-1', 'device-1', 'thread-1', 'resume', 'ok');",
+        )
+        .expect("0003-era rows should insert");
+
+        run_migrations(&mut conn).expect("remaining migrations should run");
+
+        let device: (String, String, String, String) = conn
+            .query_row(
+                "SELECT device_name, platform, public_key, paired_at FROM remote_devices
+                 WHERE id = 'device-1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .expect("device should survive the migration");
+        assert_eq!(
+            device,
+            (
+                "Phone".to_string(),
+                "unknown".to_string(),
+                "legacy:device-1".to_string(),
+                "2026-01-01T00:00:00Z".to_string()
+            )
+        );
+
+        let session: (String, String, String, Option<String>) = conn
+            .query_row(
+                "SELECT device_id, provider_id, thread_id, action FROM remote_sessions
+                 WHERE id = 'session-1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .expect("session should survive the migration");
+        assert_eq!(
+            session,
+            (
+                "device-1".to_string(),
+                "codex".to_string(),
+                "thread-1".to_string(),
+                Some("resume".to_string())
+            )
+        );
+        assert!(!table_exists(&conn, "remote_devices_0001"));
+        assert!(!table_exists(&conn, "remote_sessions_0001"));
     }
 
     #[test]
@@ -0,0 +1,231 @@
+use provider_contract::ProviderId;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RemoteError {
+    #[error("sqlite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("remote device not found: {0}")]
+    DeviceNotFound(String),
+    #[error("validation error: {0}")]
+    Validation(String),
+}
+
+/// A device paired for remote access, identified by its public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteDevice {
+    pub id: String,
+    pub name: String,
+    pub platform: String,
+    pub public_key: String,
+    pub paired_at: String,
+    pub last_seen_at: Option<String>,
+    pub revoked_at: Option<String>,
+}
+
+/// A thread handed off to a remote device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteSession {
+    pub id: String,
+    pub device_id: String,
+    pub provider_id: String,
+    pub thread_id: String,
+    pub created_at: String,
+}
+
+const DEVICE_COLUMNS: &str =
+    "id, device_name, platform, public_key, paired_at, last_seen_at, revoked_at";
+const SESSION_COLUMNS: &str = "id, device_id, provider_id, thread_id, created_at";
+
+/// Registers a device. Registering a known public key again keeps its id and
+/// `paired_at`, updates the name and platform, and marks the device as seen.
+pub fn register_remote_device(
+    connection: &Connection,
+    name: &str,
+    platform: &str,
+    public_key: &str,
+) -> Result<RemoteDevice, RemoteError> {
+    let name = required("device name", name)?;
+    let platform = required("platform", platform)?;
+    let public_key = required("public key", public_key)?;
+
+    connection.execute(
+        "INSERT INTO remote_devices (id, device_name, platform, public_key)
+         VALUES (lower(hex(randomblob(16))), ?1, ?2, ?3)
+         ON CONFLICT(public_key) DO UPDATE SET
+           device_name = excluded.device_name,
+           platform = excluded.platform,
+           last_seen_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        params![name, platform, public_key],
+    )?;
+    let device = connection.query_row(
+        &format!("SELECT {DEVICE_COLUMNS} FROM remote_devices WHERE public_key = ?1"),
+        [public_key],
+        device_from_row,
+    )?;
+    Ok(device)
+}
+
+/// Registered devices, most recently paired first.
+pub fn list_remote_devices(connection: &Connection) -> Result<Vec<RemoteDevice>, RemoteError> {
+    let mut stmt = connection.prepare(&format!(
+        "SELECT {DEVICE_COLUMNS} FROM remote_devices ORDER BY paired_at DESC, rowid DESC"
+    ))?;
+    let rows = stmt
+        .query_map([], device_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Records a handoff of `thread_id` to `device_id`. Fails when the device is unknown.
+pub fn create_remote_session(
+    connection: &Connection,
+    device_id: &str,
+    provider_id: ProviderId,
+    thread_id: &str,
+) -> Result<RemoteSession, RemoteError> {
+    let thread_id = required("thread id", thread_id)?;
+    let device_exists = connection
+        .query_row(
+            "SELECT 1 FROM remote_devices WHERE id = ?1",
+            [device_id],
+            |_| Ok(()),
+        )
+        .optional()?
+        .is_some();
+    if !device_exists {
+        return Err(RemoteError::DeviceNotFound(device_id.to_string()));
+    }
+
+    let session = connection.query_row(
+        &format!(
+            "INSERT INTO remote_sessions (id, device_id, provider_id, thread_id)
+             VALUES (lower(hex(randomblob(16))), ?1, ?2, ?3)
+             RETURNING {SESSION_COLUMNS}"
+        ),
+        params![device_id, provider_id.as_str(), thread_id],
+        session_from_row,
+    )?;
+    Ok(session)
+}
+
+/// Remote sessions, newest first, limited to one device when `device_id` is given.
+pub fn list_remote_sessions(
+    connection: &Connection,
+    device_id: Option<&str>,
+) -> Result<Vec<RemoteSession>, RemoteError> {
+    let mut stmt = connection.prepare(&format!(
+        "SELECT {SESSION_COLUMNS} FROM remote_sessions
+         WHERE ?1 IS NULL OR device_id = ?1
+         ORDER BY created_at DESC, rowid DESC"
+    ))?;
+    let rows = stmt
+        .query_map([device_id], session_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+fn required<'a>(field: &str, value: &'a str) -> Result<&'a str, RemoteError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(RemoteError::Validation(format!("{field} is required")));
+    }
+    Ok(value)
+}
+
+fn device_from_row(row: &Row<'_>) -> rusqlite::Result<RemoteDevice> {
+    Ok(RemoteDevice {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        platform: row.get(2)?,
+        public_key: row.get(3)?,
+        paired_at: row.get(4)?,
+        last_seen_at: row.get(5)?,
+        revoked_at: row.get(6)?,
+    })
+}
+
+fn session_from_row(row: &Row<'_>) -> rusqlite::Result<RemoteSession> {
+    Ok(RemoteSession {
+        id: row.get(0)?,
+        device_id: row.get(1)?,
+        provider_id: row.get(2)?,
+        thread_id: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_test_db() -> Connection {
+        let mut conn = Connection::open_in_memory().expect("in-memory sqlite should open");
+        crate::db::run_migrations(&mut conn).expect("migrations should run");
+        conn
+    }
+
+    #[test]
+    fn register_remote_device_is_unique_per_public_key() {
+        let conn = setup_test_db();
+        let first = register_remote_device(&conn, "Phone", "ios", "key-a")
+            .expect("register should succeed");
+        let renamed = register_remote_device(&conn, "Work phone", "ios", "key-a")
+            .expect("re-register should succeed");
+        let other = register_remote_device(&conn, "Tablet", "android", "key-b")
+            .expect("register should succeed");
+
+        assert_eq!(renamed.id, first.id);
+        assert_eq!(renamed.paired_at, first.paired_at);
+        assert_eq!(renamed.name, "Work phone");
+        assert!(first.last_seen_at.is_none());
+        assert!(renamed.last_seen_at.is_some());
+        assert_ne!(other.id, first.id);
+
+        let devices = list_remote_devices(&conn).expect("list should succeed");
+        let names = devices
+            .iter()
+            .map(|device| device.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Tablet", "Work phone"]);
+        assert!(matches!(
+            register_remote_device(&conn, "Laptop", "macos", "  "),
+            Err(RemoteError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn list_remote_sessions_filters_by_device() {
+        let conn = setup_test_db();
+        let phone =
+            register_remote_device(&conn, "Phone", "ios", "key-a").expect("register should work");
+        let tablet = register_remote_device(&conn, "Tablet", "android", "key-b")
+            .expect("register should work");
+        create_remote_session(&conn, &phone.id, ProviderId::Codex, "a")
+            .expect("session should be created");
+        create_remote_session(&conn, &tablet.id, ProviderId::ClaudeCode, "b")
+            .expect("session should be created");
+        let latest = create_remote_session(&conn, &phone.id, ProviderId::OpenCode, "c")
+            .expect("session should be created");
+
+        let phone_sessions =
+            list_remote_sessions(&conn, Some(&phone.id)).expect("list should succeed");
+        let keys = phone_sessions
+            .iter()
+            .map(|session| format!("{}:{}", session.provider_id, session.thread_id))
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec!["opencode:c", "codex:a"]);
+        assert_eq!(phone_sessions[0], latest);
+        assert_eq!(
+            list_remote_sessions(&conn, None)
+                .expect("list should succeed")
+                .len(),
+            3
+        );
+        assert!(matches!(
+            create_remote_session(&conn, "missing", ProviderId::Codex, "a"),
+            Err(RemoteError::DeviceNotFound(_))
+        ));
+    }
+}
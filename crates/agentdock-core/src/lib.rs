@@ -1,3 +1,5 @@
+pub mod config;
 pub mod db;
 pub mod mcp;
 pub mod skills;
+pub mod threads;
@@ -383,12 +383,10 @@ fn parse_skill_md_metadata(skill_dir: &Path, skill_md_path: &Path) -> Result<Ski
     let fallback_id = slugify_skill_id(&name);
     let id = id
         .filter(|v| !v.trim().is_empty())
-        .or_else(|| {
-            if fallback_id.is_empty() {
-                None
-            } else {
-                Some(fallback_id)
-            }
+        .or(if fallback_id.is_empty() {
+            None
+        } else {
+            Some(fallback_id)
         })
         .or_else(|| {
             skill_dir
@@ -0,0 +1,256 @@
+use std::collections::{HashMap, HashSet};
+
+use provider_contract::{ProviderId, ThreadSummary};
+use rusqlite::{params, Connection};
+
+/// Changes between two thread scans, keyed by `(provider_id, id)`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThreadDiff {
+    pub added: Vec<ThreadSummary>,
+    pub updated: Vec<ThreadSummary>,
+    pub removed: Vec<ThreadSummary>,
+}
+
+impl ThreadDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// A thread counts as updated when its `last_active_at` or title changed. `added` and
+/// `updated` follow the order of `new`; `removed` follows the order of `old`.
+pub fn diff_threads(old: &[ThreadSummary], new: &[ThreadSummary]) -> ThreadDiff {
+    let old_by_key = old
+        .iter()
+        .map(|thread| (thread_key(thread), thread))
+        .collect::<HashMap<_, _>>();
+    let new_keys = new.iter().map(thread_key).collect::<HashSet<_>>();
+
+    let mut diff = ThreadDiff::default();
+    let mut seen = HashSet::new();
+    for thread in new {
+        let key = thread_key(thread);
+        if !seen.insert(key) {
+            continue;
+        }
+        match old_by_key.get(&key) {
+            None => diff.added.push(thread.clone()),
+            Some(previous) if thread_changed(previous, thread) => diff.updated.push(thread.clone()),
+            Some(_) => {}
+        }
+    }
+
+    let mut removed_seen = HashSet::new();
+    for thread in old {
+        let key = thread_key(thread);
+        if !new_keys.contains(&key) && removed_seen.insert(key) {
+            diff.removed.push(thread.clone());
+        }
+    }
+
+    diff
+}
+
+/// A thread pinned to the top of the thread list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedThread {
+    pub provider_id: String,
+    pub thread_id: String,
+    pub pinned_at: String,
+}
+
+/// Pins a thread. Pinning an already pinned thread keeps its original `pinned_at`.
+pub fn pin_thread(
+    connection: &Connection,
+    provider_id: ProviderId,
+    thread_id: &str,
+) -> Result<(), rusqlite::Error> {
+    connection.execute(
+        "INSERT INTO pinned_threads (provider_id, thread_id)
+         VALUES (?1, ?2)
+         ON CONFLICT(provider_id, thread_id) DO NOTHING",
+        params![provider_id.as_str(), thread_id],
+    )?;
+    Ok(())
+}
+
+/// Unpins a thread, returning whether it was pinned.
+pub fn unpin_thread(
+    connection: &Connection,
+    provider_id: ProviderId,
+    thread_id: &str,
+) -> Result<bool, rusqlite::Error> {
+    let rows_affected = connection.execute(
+        "DELETE FROM pinned_threads WHERE provider_id = ?1 AND thread_id = ?2",
+        params![provider_id.as_str(), thread_id],
+    )?;
+    Ok(rows_affected > 0)
+}
+
+/// Pinned threads, most recently pinned first.
+pub fn list_pinned(connection: &Connection) -> Result<Vec<PinnedThread>, rusqlite::Error> {
+    let mut stmt = connection.prepare(
+        "SELECT provider_id, thread_id, pinned_at
+         FROM pinned_threads
+         ORDER BY pinned_at DESC, rowid DESC",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(PinnedThread {
+                provider_id: row.get(0)?,
+                thread_id: row.get(1)?,
+                pinned_at: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+fn thread_key(thread: &ThreadSummary) -> (&'static str, &str) {
+    (thread.provider_id.as_str(), thread.id.as_str())
+}
+
+fn thread_changed(previous: &ThreadSummary, current: &ThreadSummary) -> bool {
+    previous.last_active_at != current.last_active_at || previous.title != current.title
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use provider_contract::Timestamp;
+
+    fn setup_test_db() -> Connection {
+        let mut conn = Connection::open_in_memory().expect("in-memory sqlite should open");
+        crate::db::run_migrations(&mut conn).expect("migrations should run");
+        conn
+    }
+
+    fn thread(
+        provider_id: ProviderId,
+        id: &str,
+        title: &str,
+        last_active_at: i64,
+    ) -> ThreadSummary {
+        ThreadSummary {
+            id: id.to_string(),
+            provider_id,
+            account_id: None,
+            project_path: "/workspace/app".to_string(),
+            original_cwd: None,
+            model: None,
+            provider_backend: None,
+            title: title.to_string(),
+            tags: Vec::new(),
+            last_active_at: Timestamp::from_epoch_millis(last_active_at),
+        }
+    }
+
+    fn ids(threads: &[ThreadSummary]) -> Vec<String> {
+        threads
+            .iter()
+            .map(|thread| format!("{}:{}", thread.provider_id.as_str(), thread.id))
+            .collect()
+    }
+
+    #[test]
+    fn diff_threads_reports_added_threads() {
+        let old = vec![thread(ProviderId::Codex, "a", "A", 100)];
+        let new = vec![
+            thread(ProviderId::Codex, "a", "A", 100),
+            thread(ProviderId::Codex, "b", "B", 200),
+        ];
+
+        let diff = diff_threads(&old, &new);
+
+        assert_eq!(ids(&diff.added), vec!["codex:b"]);
+        assert!(diff.updated.is_empty());
+        assert!(diff.removed.is_empty());
+    }
+
+    #[test]
+    fn diff_threads_reports_updates_for_activity_or_title_changes() {
+        let old = vec![
+            thread(ProviderId::Codex, "a", "A", 100),
+            thread(ProviderId::ClaudeCode, "b", "B", 100),
+            thread(ProviderId::OpenCode, "c", "C", 100),
+        ];
+        let mut moved = thread(ProviderId::OpenCode, "c", "C", 100);
+        moved.project_path = "/workspace/other".to_string();
+        let new = vec![
+            thread(ProviderId::Codex, "a", "A", 150),
+            thread(ProviderId::ClaudeCode, "b", "Renamed", 100),
+            moved,
+        ];
+
+        let diff = diff_threads(&old, &new);
+
+        assert_eq!(ids(&diff.updated), vec!["codex:a", "claude_code:b"]);
+        assert_eq!(diff.updated[1].title, "Renamed");
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+    }
+
+    #[test]
+    fn diff_threads_reports_removed_threads() {
+        let old = vec![
+            thread(ProviderId::Codex, "a", "A", 100),
+            thread(ProviderId::Codex, "b", "B", 100),
+        ];
+        let new = vec![thread(ProviderId::Codex, "b", "B", 100)];
+
+        let diff = diff_threads(&old, &new);
+
+        assert_eq!(ids(&diff.removed), vec!["codex:a"]);
+        assert!(diff.added.is_empty());
+        assert!(diff.updated.is_empty());
+    }
+
+    #[test]
+    fn diff_threads_is_empty_when_nothing_changed() {
+        let threads = vec![
+            thread(ProviderId::Codex, "a", "A", 100),
+            thread(ProviderId::ClaudeCode, "a", "A", 100),
+        ];
+
+        assert!(diff_threads(&threads, &threads).is_empty());
+        assert!(diff_threads(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn diff_threads_keys_by_provider_and_id() {
+        let old = vec![thread(ProviderId::Codex, "shared", "A", 100)];
+        let new = vec![thread(ProviderId::ClaudeCode, "shared", "A", 100)];
+
+        let diff = diff_threads(&old, &new);
+
+        assert_eq!(ids(&diff.added), vec!["claude_code:shared"]);
+        assert_eq!(ids(&diff.removed), vec!["codex:shared"]);
+        assert!(diff.updated.is_empty());
+    }
+
+    #[test]
+    fn pin_thread_round_trips_through_list_pinned() {
+        let conn = setup_test_db();
+        pin_thread(&conn, ProviderId::Codex, "a").expect("pin should succeed");
+        pin_thread(&conn, ProviderId::ClaudeCode, "b").expect("pin should succeed");
+        pin_thread(&conn, ProviderId::Codex, "a").expect("re-pin should be a no-op");
+
+        let pinned = list_pinned(&conn).expect("list should succeed");
+        let keys = pinned
+            .iter()
+            .map(|pin| format!("{}:{}", pin.provider_id, pin.thread_id))
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec!["claude_code:b", "codex:a"]);
+        assert!(!pinned[0].pinned_at.is_empty());
+    }
+
+    #[test]
+    fn unpin_thread_reports_whether_thread_was_pinned() {
+        let conn = setup_test_db();
+        pin_thread(&conn, ProviderId::OpenCode, "a").expect("pin should succeed");
+
+        assert!(!unpin_thread(&conn, ProviderId::Codex, "a").expect("unpin should succeed"));
+        assert!(unpin_thread(&conn, ProviderId::OpenCode, "a").expect("unpin should succeed"));
+        assert!(list_pinned(&conn).expect("list should succeed").is_empty());
+    }
+}
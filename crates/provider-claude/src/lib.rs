@@ -1,28 +1,37 @@
 use provider_contract::{
-    ProviderAdapter, ProviderError, ProviderErrorCode, ProviderHealthCheckRequest,
-    ProviderHealthCheckResult, ProviderHealthStatus, ProviderId, ProviderResult,
-    ResumeThreadRequest, ResumeThreadResult, ThreadSummary,
+    build_thread_tags, collect_files_with_extension, default_cli_search_dirs, default_home_dir,
+    default_max_session_file_bytes, default_scan_timeout, file_stem_lossy, find_last_line_map,
+    group_project_path, locate_cli_binary, parse_cli_version_output, partition_oversized_files,
+    redact_secrets, resolve_cli_binary, resolve_project_path, verify_session_file, Clock,
+    OversizedFile, ProviderAdapter, ProviderConfigSummary, ProviderError, ProviderErrorCode,
+    ProviderHealthCheckRequest, ProviderHealthCheckResult, ProviderHealthStatus, ProviderId,
+    ProviderResult, ResumeThreadRequest, ResumeThreadResult, ScanDeadline, ThreadActivity,
+    ThreadEvent, ThreadOverviewOptions, ThreadScan, ThreadSummary, Timestamp,
 };
 use serde_json::Value;
-use std::cmp::{Ordering, Reverse};
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::time::{SystemTime, UNIX_EPOCH};
-use time::format_description::well_known::Rfc3339;
-use time::OffsetDateTime;
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
 
 const CLAUDE_CONFIG_DIR_ENV: &str = "AGENTDOCK_CLAUDE_CONFIG_DIR";
 const CLAUDE_BINARY_ENV: &str = "AGENTDOCK_CLAUDE_BIN";
 const CLAUDE_AGENT_ACTIVITY_WINDOW_MS: i64 = 120_000;
+/// `is_dir` checks one `projects/` directory name may cost while decoding it.
+const MAX_PROJECT_DIR_DECODE_PROBES: usize = 256;
 
 #[derive(Debug, Clone)]
 struct ThreadRecord {
     summary: ThreadSummary,
     source_path: PathBuf,
     sort_key: i64,
+    has_messages: bool,
+    /// A line carried a `cwd`; a path decoded from the directory name does not count.
+    has_recorded_cwd: bool,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -36,6 +45,10 @@ struct SessionIdStats {
 pub struct ClaudeThreadOverview {
     pub summary: ThreadSummary,
     pub last_message_preview: Option<String>,
+    /// Only set when requested through `ThreadOverviewOptions::include_runtime_state`.
+    pub runtime_state: Option<ClaudeThreadRuntimeState>,
+    /// Only set when requested through `ThreadOverviewOptions::include_message_count`.
+    pub message_count: Option<usize>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -45,6 +58,23 @@ pub struct ClaudeThreadRuntimeState {
     pub last_event_at_ms: Option<i64>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaudeAuthMode {
+    AuthToken,
+    ApiKey,
+    OauthOrUnknown,
+}
+
+impl ClaudeAuthMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ClaudeAuthMode::AuthToken => "auth_token",
+            ClaudeAuthMode::ApiKey => "api_key",
+            ClaudeAuthMode::OauthOrUnknown => "oauth_or_unknown",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ClaudeSemanticEventKind {
     UserMessage,
@@ -74,6 +104,14 @@ impl ClaudeSemanticEventKind {
 pub struct ClaudeAdapter {
     config_dir_override: Option<PathBuf>,
     cli_binary_override: Option<String>,
+    cli_search_dirs: Option<Vec<PathBuf>>,
+    clock: Option<Arc<dyn Clock>>,
+    max_session_file_bytes: Option<u64>,
+    project_path_base: Option<PathBuf>,
+    group_by_git_root: bool,
+    hide_empty_threads: bool,
+    redaction_disabled: bool,
+    scan_timeout: Option<Duration>,
 }
 
 impl ClaudeAdapter {
@@ -91,61 +129,250 @@ impl ClaudeAdapter {
         self
     }
 
+    /// Install directories probed when the CLI is not on PATH. Defaults to
+    /// `default_cli_search_dirs()`.
+    pub fn with_cli_search_dirs<I, P>(mut self, dirs: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.cli_search_dirs = Some(dirs.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn with_project_path_base<P: Into<PathBuf>>(mut self, base: P) -> Self {
+        self.project_path_base = Some(base.into());
+        self
+    }
+
+    /// Group threads under the enclosing git repository root instead of the recorded cwd.
+    pub fn with_git_root_grouping(mut self, enabled: bool) -> Self {
+        self.group_by_git_root = enabled;
+        self
+    }
+
+    /// Omit never-used sessions from `list_threads`: no parseable messages and no
+    /// recorded project directory.
+    pub fn with_hide_empty_threads(mut self, enabled: bool) -> Self {
+        self.hide_empty_threads = enabled;
+        self
+    }
+
+    /// Run message previews through `redact_secrets`. On by default.
+    pub fn with_redaction(mut self, enabled: bool) -> Self {
+        self.redaction_disabled = !enabled;
+        self
+    }
+
+    /// Overrides the `Clock`; see there.
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Some(Arc::new(clock));
+        self
+    }
+
+    /// Session files larger than this are skipped instead of read. Defaults to
+    /// `default_max_session_file_bytes()`.
+    pub fn with_max_session_file_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_session_file_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Stops session scans after `timeout`, keeping the threads read so far. Defaults to
+    /// `default_scan_timeout()`.
+    pub fn with_scan_timeout(mut self, timeout: Duration) -> Self {
+        self.scan_timeout = Some(timeout);
+        self
+    }
+
     pub fn get_thread_runtime_state(
         &self,
         thread_id: &str,
     ) -> ProviderResult<ClaudeThreadRuntimeState> {
         let thread_record = self.find_thread_record(thread_id)?;
-        Ok(load_thread_runtime_state(&thread_record.source_path))
+        Ok(load_thread_runtime_state(
+            &thread_record.source_path,
+            self.now_ms(),
+        ))
+    }
+
+    /// Checks that a session file is still on disk without parsing whole transcripts:
+    /// matches Claude's `<session id>.jsonl` file name, then the first recorded
+    /// `sessionId`.
+    pub fn thread_exists(&self, thread_id: &str) -> bool {
+        self.session_file_path(thread_id).is_some()
+    }
+
+    /// Session files left out of thread scans by the size cap.
+    pub fn oversized_session_files(&self) -> Vec<OversizedFile> {
+        self.collect_session_files(&self.scan_deadline()).1
+    }
+
+    /// Last `limit` semantic events of the session, oldest first.
+    pub fn get_thread_event_log(
+        &self,
+        thread_id: &str,
+        limit: usize,
+    ) -> ProviderResult<Vec<ThreadEvent>> {
+        let thread_record = self.find_thread_record(thread_id)?;
+        Ok(load_thread_event_log(&thread_record.source_path, limit))
+    }
+
+    pub fn get_thread_activity(&self, thread_id: &str) -> ProviderResult<ThreadActivity> {
+        let thread_record = self.find_thread_record(thread_id)?;
+        Ok(load_thread_activity(&thread_record.source_path))
+    }
+
+    pub fn get_latest_message_preview(&self, thread_id: &str) -> ProviderResult<Option<String>> {
+        let thread_record = self.find_thread_record(thread_id)?;
+        Ok(self.redact(build_latest_message_preview(&thread_record.source_path)))
+    }
+
+    /// Text of the last assistant reply, skipping trailing tool calls and results. Not
+    /// truncated, unlike the latest message preview.
+    pub fn get_final_assistant_message(&self, thread_id: &str) -> ProviderResult<Option<String>> {
+        let thread_record = self.find_thread_record(thread_id)?;
+        Ok(self.redact(find_last_line_map(
+            &thread_record.source_path,
+            extract_line_assistant_text,
+        )))
+    }
+
+    /// Auth mode from `settings.json` env, falling back to the process environment when
+    /// the settings file is missing.
+    pub fn get_auth_mode(&self) -> ProviderResult<ClaudeAuthMode> {
+        self.load_auth_mode(|key| std::env::var(key).ok())
+    }
+
+    fn load_auth_mode<F>(&self, env: F) -> ProviderResult<ClaudeAuthMode>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let settings_path = self.claude_settings_path();
+        if !settings_path.exists() {
+            return Ok(detect_claude_auth_mode(&Value::Null, env));
+        }
+
+        let raw = fs::read_to_string(&settings_path).map_err(|error| {
+            provider_error(
+                ProviderErrorCode::PermissionDenied,
+                format!(
+                    "Failed to read Claude settings {}: {error}",
+                    settings_path.display()
+                ),
+                false,
+            )
+        })?;
+        let settings = serde_json::from_str::<Value>(&raw).map_err(|error| {
+            provider_error(
+                ProviderErrorCode::InvalidResponse,
+                format!(
+                    "Invalid Claude settings JSON at {}: {error}",
+                    settings_path.display()
+                ),
+                false,
+            )
+        })?;
+        Ok(detect_claude_auth_mode(&settings, env))
+    }
+
+    /// Resolved config dir, projects dir, CLI binary and credential source.
+    pub fn config_summary(&self) -> ProviderConfigSummary {
+        let cli_binary = self.claude_binary();
+        ProviderConfigSummary {
+            config_dir: self.claude_config_dir(),
+            sessions_dir: self.claude_projects_dir(),
+            cli_binary_path: locate_cli_binary(&cli_binary, &self.cli_search_dirs()),
+            cli_binary,
+            auth_source: self.claude_auth_source(),
+        }
     }
 
     pub fn list_thread_overviews(
         &self,
         project_path: Option<&str>,
     ) -> ProviderResult<Vec<ClaudeThreadOverview>> {
-        let mut records = self.scan_thread_records();
+        self.list_thread_overviews_with_options(project_path, ThreadOverviewOptions::default())
+    }
+
+    pub fn list_thread_overviews_with_options(
+        &self,
+        project_path: Option<&str>,
+        options: ThreadOverviewOptions,
+    ) -> ProviderResult<Vec<ClaudeThreadOverview>> {
+        Ok(self.scan_thread_overviews(project_path, options)?.threads)
+    }
+
+    /// Like `list_thread_overviews_with_options`, but reports whether the scan timeout cut
+    /// the list short.
+    pub fn scan_thread_overviews(
+        &self,
+        project_path: Option<&str>,
+        options: ThreadOverviewOptions,
+    ) -> ProviderResult<ThreadScan<ClaudeThreadOverview>> {
+        let now_ms = self.now_ms();
+        let deadline = self.scan_deadline();
+        let mut records = self.scan_thread_records_until(&deadline);
 
         if let Some(filter) = project_path {
             records.retain(|record| record.summary.project_path.starts_with(filter));
         }
 
-        records.sort_by_key(|record| Reverse(record.sort_key));
-        Ok(records
+        sort_thread_records(&mut records);
+        let threads = records
             .into_iter()
             .map(|record| ClaudeThreadOverview {
-                last_message_preview: build_last_message_preview(&record.source_path),
+                last_message_preview: self.redact(build_last_message_preview(&record.source_path)),
+                runtime_state: options
+                    .include_runtime_state
+                    .then(|| load_thread_runtime_state(&record.source_path, now_ms)),
+                message_count: options
+                    .include_message_count
+                    .then(|| count_thread_messages(&record.source_path)),
                 summary: record.summary,
             })
-            .collect())
+            .collect();
+        Ok(ThreadScan {
+            threads,
+            timed_out: deadline.timed_out(),
+        })
     }
 
     fn claude_binary(&self) -> String {
         if let Some(binary) = &self.cli_binary_override {
             return binary.clone();
         }
-        if let Ok(binary) = std::env::var(CLAUDE_BINARY_ENV) {
-            let trimmed = binary.trim();
-            if !trimmed.is_empty() {
-                return trimmed.to_string();
-            }
+        resolve_claude_binary(|key| std::env::var(key).ok(), &self.cli_search_dirs())
+    }
+
+    fn cli_search_dirs(&self) -> Vec<PathBuf> {
+        self.cli_search_dirs
+            .clone()
+            .unwrap_or_else(default_cli_search_dirs)
+    }
+
+    fn scan_deadline(&self) -> ScanDeadline {
+        ScanDeadline::new(self.scan_timeout.or_else(default_scan_timeout))
+    }
+
+    fn now_ms(&self) -> i64 {
+        self.clock
+            .as_ref()
+            .map_or_else(Timestamp::now, |clock| clock.now())
+            .as_millis()
+    }
+
+    fn redact(&self, text: Option<String>) -> Option<String> {
+        if self.redaction_disabled {
+            return text;
         }
-        "claude".to_string()
+        text.map(|text| redact_secrets(&text).into_owned())
     }
 
     fn claude_config_dir(&self) -> PathBuf {
         if let Some(path) = &self.config_dir_override {
             return path.clone();
         }
-        if let Ok(path) = std::env::var(CLAUDE_CONFIG_DIR_ENV) {
-            let trimmed = path.trim();
-            if !trimmed.is_empty() {
-                return PathBuf::from(trimmed);
-            }
-        }
-        if let Some(home) = default_home_dir() {
-            return home.join(".claude");
-        }
-        PathBuf::from(".claude")
+        resolve_claude_config_dir(|key| std::env::var(key).ok(), default_home_dir())
     }
 
     fn claude_projects_dir(&self) -> PathBuf {
@@ -168,14 +395,42 @@ impl ClaudeAdapter {
         settings_path
     }
 
+    // Unreadable settings count as no env credentials, leaving the OAuth credentials file.
+    fn claude_auth_source(&self) -> Option<String> {
+        let source = match self
+            .get_auth_mode()
+            .unwrap_or(ClaudeAuthMode::OauthOrUnknown)
+        {
+            ClaudeAuthMode::AuthToken => "ANTHROPIC_AUTH_TOKEN",
+            ClaudeAuthMode::ApiKey => "ANTHROPIC_API_KEY",
+            ClaudeAuthMode::OauthOrUnknown => {
+                if !self.claude_config_dir().join(".credentials.json").is_file() {
+                    return None;
+                }
+                ".credentials.json"
+            }
+        };
+        Some(source.to_string())
+    }
+
     fn scan_thread_records(&self) -> Vec<ThreadRecord> {
-        let mut files = Vec::new();
-        collect_jsonl_files(&self.claude_projects_dir(), &mut files);
+        self.scan_thread_records_until(&self.scan_deadline())
+    }
+
+    fn scan_thread_records_until(&self, deadline: &ScanDeadline) -> Vec<ThreadRecord> {
+        let (files, _) = self.collect_session_files(deadline);
         let official_titles = load_claude_history_titles(&self.claude_config_dir());
 
+        let mut decoded_project_dirs = HashMap::new();
         let mut records = Vec::new();
         for path in files {
-            if let Some(record) = parse_thread_file(&path, &official_titles) {
+            if let Some(record) = parse_thread_file(
+                &path,
+                &official_titles,
+                &mut decoded_project_dirs,
+                self.project_path_base.as_deref(),
+                self.group_by_git_root,
+            ) {
                 records.push(record);
             }
         }
@@ -183,6 +438,46 @@ impl ClaudeAdapter {
         dedupe_thread_records(records)
     }
 
+    fn collect_session_files(&self, deadline: &ScanDeadline) -> (Vec<PathBuf>, Vec<OversizedFile>) {
+        let files = collect_files_with_extension(&self.claude_projects_dir(), "jsonl", deadline);
+        let max_bytes = self
+            .max_session_file_bytes
+            .unwrap_or_else(default_max_session_file_bytes);
+        partition_oversized_files(files, max_bytes)
+    }
+
+    fn session_file_path(&self, thread_id: &str) -> Option<PathBuf> {
+        let mut files = Vec::new();
+        collect_jsonl_files(&self.claude_projects_dir(), &mut files);
+        files.into_iter().find(|path| {
+            let Some(stem) = file_stem_lossy(path) else {
+                return false;
+            };
+            !stem.starts_with("agent-")
+                && (stem == thread_id || first_session_id(path).as_deref() == Some(thread_id))
+        })
+    }
+
+    /// Command resuming the session from the requested project path, or else from the
+    /// directory it was recorded in rather than a grouped `project_path`.
+    fn resume_command(
+        &self,
+        thread_record: &ThreadRecord,
+        request: &ResumeThreadRequest,
+    ) -> String {
+        let project_path = request
+            .project_path
+            .as_deref()
+            .filter(|path| !path.trim().is_empty())
+            .or_else(|| thread_record.summary.session_cwd());
+        build_resume_command(
+            &self.claude_binary(),
+            &request.thread_id,
+            project_path,
+            request.context_summary.as_deref(),
+        )
+    }
+
     fn find_thread_record(&self, thread_id: &str) -> ProviderResult<ThreadRecord> {
         self.scan_thread_records()
             .into_iter()
@@ -195,23 +490,6 @@ impl ClaudeAdapter {
                 )
             })
     }
-
-    fn ensure_cli_reachable(&self) -> ProviderResult<()> {
-        let binary = self.claude_binary();
-        match Command::new(&binary).arg("--version").output() {
-            Ok(_) => Ok(()),
-            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Err(provider_error(
-                ProviderErrorCode::UpstreamUnavailable,
-                format!("Claude Code CLI not found in PATH: {binary}"),
-                false,
-            )),
-            Err(error) => Err(provider_error(
-                ProviderErrorCode::UpstreamUnavailable,
-                format!("Failed to execute Claude Code CLI ({binary}): {error}"),
-                true,
-            )),
-        }
-    }
 }
 
 impl ProviderAdapter for ClaudeAdapter {
@@ -223,27 +501,19 @@ impl ProviderAdapter for ClaudeAdapter {
         &self,
         request: ProviderHealthCheckRequest,
     ) -> ProviderResult<ProviderHealthCheckResult> {
-        let checked_at = now_unix_millis().to_string();
-        let binary = self.claude_binary();
+        let checked_at = Timestamp::now();
 
-        match Command::new(&binary).arg("--version").output() {
-            Ok(_) => {}
-            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+        let version = match self.provider_version() {
+            Ok(version) => version,
+            Err(error) => {
                 return Ok(ProviderHealthCheckResult {
                     provider_id: ProviderId::ClaudeCode,
                     status: ProviderHealthStatus::Offline,
                     checked_at,
-                    message: Some(format!("Claude Code CLI not found in PATH: {binary}")),
+                    message: Some(error.message),
                 });
             }
-            Err(error) => {
-                return Err(provider_error(
-                    ProviderErrorCode::UpstreamUnavailable,
-                    format!("Failed to execute Claude Code CLI ({binary}): {error}"),
-                    true,
-                ));
-            }
-        }
+        };
 
         let settings_path = self.claude_settings_path();
         if !settings_path.exists() {
@@ -287,53 +557,70 @@ impl ProviderAdapter for ClaudeAdapter {
             }
         };
 
-        let auth_mode = detect_claude_auth_mode(&settings);
+        let auth_mode = detect_claude_auth_mode(&settings, |key| std::env::var(key).ok());
         Ok(ProviderHealthCheckResult {
             provider_id: ProviderId::ClaudeCode,
             status: ProviderHealthStatus::Healthy,
             checked_at,
             message: Some(format!(
-                "Claude CLI reachable, settings loaded ({}, profile={})",
-                auth_mode, request.profile_name
+                "Claude CLI reachable{}, settings loaded ({}, profile={})",
+                version
+                    .map(|version| format!(" (v{version})"))
+                    .unwrap_or_default(),
+                auth_mode.as_str(),
+                request.profile_name
             )),
         })
     }
 
+    fn provider_version(&self) -> ProviderResult<Option<String>> {
+        let binary = self.claude_binary();
+        match Command::new(&binary).arg("--version").output() {
+            Ok(output) if !output.status.success() => Err(ProviderError::from_cli_failure(
+                format!("Claude Code CLI ({binary}) exited with {}", output.status),
+                &String::from_utf8_lossy(&output.stderr),
+            )),
+            Ok(output) => Ok(parse_cli_version_output(&output)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Err(provider_error(
+                ProviderErrorCode::UpstreamUnavailable,
+                format!("Claude Code CLI not found in PATH: {binary}"),
+                false,
+            )),
+            Err(error) => Err(provider_error(
+                ProviderErrorCode::UpstreamUnavailable,
+                format!("Failed to execute Claude Code CLI ({binary}): {error}"),
+                true,
+            )),
+        }
+    }
+
     fn list_threads(&self, project_path: Option<&str>) -> ProviderResult<Vec<ThreadSummary>> {
         let mut records = self.scan_thread_records();
 
         if let Some(filter) = project_path {
             records.retain(|record| record.summary.project_path.starts_with(filter));
         }
+        if self.hide_empty_threads {
+            records.retain(|record| !is_empty_thread_record(record));
+        }
 
-        records.sort_by_key(|record| Reverse(record.sort_key));
+        sort_thread_records(&mut records);
         Ok(records.into_iter().map(|record| record.summary).collect())
     }
 
     fn resume_thread(&self, request: ResumeThreadRequest) -> ProviderResult<ResumeThreadResult> {
-        self.ensure_cli_reachable()?;
-        let thread_record = self.find_thread_record(&request.thread_id)?;
-
-        let project_path = request
-            .project_path
-            .clone()
-            .filter(|path| !path.trim().is_empty())
-            .or_else(|| {
-                if thread_record.summary.project_path == "." {
-                    None
-                } else {
-                    Some(thread_record.summary.project_path.clone())
-                }
+        let session_path = self.session_file_path(&request.thread_id);
+        if let Err(reason) = verify_session_file("Claude", session_path.as_deref()) {
+            return Ok(ResumeThreadResult {
+                thread_id: request.thread_id,
+                resumed: false,
+                message: Some(reason),
             });
-
-        let mut command = format!(
-            "{} --resume {}",
-            self.claude_binary(),
-            shell_quote(&request.thread_id)
-        );
-        if let Some(path) = project_path {
-            command = prepend_workdir_to_command(command, &path);
         }
+        let thread_record = self.find_thread_record(&request.thread_id)?;
+        self.provider_version()?;
+
+        let command = self.resume_command(&thread_record, &request);
 
         Ok(ResumeThreadResult {
             thread_id: request.thread_id,
@@ -345,7 +632,57 @@ impl ProviderAdapter for ClaudeAdapter {
     }
 }
 
-fn detect_claude_auth_mode(settings: &Value) -> &'static str {
+fn build_resume_command(
+    binary: &str,
+    thread_id: &str,
+    project_path: Option<&str>,
+    context_summary: Option<&str>,
+) -> String {
+    let mut command = format!("{binary} --resume {}", shell_quote(thread_id));
+    if let Some(summary) = context_summary.and_then(non_empty_trimmed) {
+        command = format!("{command} {}", shell_quote(summary));
+    }
+    match project_path {
+        Some(path) => prepend_workdir_to_command(command, path),
+        None => command,
+    }
+}
+
+/// `AGENTDOCK_CLAUDE_CONFIG_DIR`, then `<home>/.claude`.
+fn resolve_claude_config_dir<F>(env_var: F, home: Option<PathBuf>) -> PathBuf
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if let Some(path) = env_var(CLAUDE_CONFIG_DIR_ENV) {
+        let trimmed = path.trim();
+        if !trimmed.is_empty() {
+            return PathBuf::from(trimmed);
+        }
+    }
+
+    home.map(|home| home.join(".claude"))
+        .unwrap_or_else(|| PathBuf::from(".claude"))
+}
+
+/// `AGENTDOCK_CLAUDE_BIN`, then `claude` resolved against the CLI search dirs.
+fn resolve_claude_binary<F>(env_var: F, search_dirs: &[PathBuf]) -> String
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if let Some(binary) = env_var(CLAUDE_BINARY_ENV) {
+        let trimmed = binary.trim();
+        if !trimmed.is_empty() {
+            return trimmed.to_string();
+        }
+    }
+    resolve_cli_binary("claude", search_dirs)
+}
+
+/// `env` looks up process environment variables, consulted after the settings env.
+fn detect_claude_auth_mode<F>(settings: &Value, env: F) -> ClaudeAuthMode
+where
+    F: Fn(&str) -> Option<String>,
+{
     let env_object = settings
         .get("env")
         .and_then(Value::as_object)
@@ -356,46 +693,24 @@ fn detect_claude_auth_mode(settings: &Value) -> &'static str {
         env_object
             .get("ANTHROPIC_AUTH_TOKEN")
             .and_then(Value::as_str),
-    ) || has_non_empty(std::env::var("ANTHROPIC_AUTH_TOKEN").ok().as_deref())
+    ) || has_non_empty(env("ANTHROPIC_AUTH_TOKEN").as_deref())
     {
-        return "auth_token";
+        return ClaudeAuthMode::AuthToken;
     }
 
     if has_non_empty(env_object.get("ANTHROPIC_API_KEY").and_then(Value::as_str))
-        || has_non_empty(std::env::var("ANTHROPIC_API_KEY").ok().as_deref())
+        || has_non_empty(env("ANTHROPIC_API_KEY").as_deref())
     {
-        return "api_key";
+        return ClaudeAuthMode::ApiKey;
     }
 
-    "oauth_or_unknown"
+    ClaudeAuthMode::OauthOrUnknown
 }
 
 fn has_non_empty(value: Option<&str>) -> bool {
     value.map(|text| !text.trim().is_empty()).unwrap_or(false)
 }
 
-fn default_home_dir() -> Option<PathBuf> {
-    if let Ok(home) = std::env::var("HOME") {
-        if !home.trim().is_empty() {
-            return Some(PathBuf::from(home));
-        }
-    }
-
-    if let Ok(home) = std::env::var("USERPROFILE") {
-        if !home.trim().is_empty() {
-            return Some(PathBuf::from(home));
-        }
-    }
-
-    let home_drive = std::env::var("HOMEDRIVE").ok()?;
-    let home_path = std::env::var("HOMEPATH").ok()?;
-    let combined = format!("{home_drive}{home_path}");
-    if combined.trim().is_empty() {
-        return None;
-    }
-    Some(PathBuf::from(combined))
-}
-
 fn provider_error(code: ProviderErrorCode, message: String, retryable: bool) -> ProviderError {
     ProviderError {
         code,
@@ -405,26 +720,11 @@ fn provider_error(code: ProviderErrorCode, message: String, retryable: bool) ->
 }
 
 fn collect_jsonl_files(root: &Path, output: &mut Vec<PathBuf>) {
-    if !root.exists() {
-        return;
-    }
-
-    let entries = match fs::read_dir(root) {
-        Ok(entries) => entries,
-        Err(_) => return,
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.is_dir() {
-            collect_jsonl_files(&path, output);
-            continue;
-        }
-
-        if path.extension().and_then(|value| value.to_str()) == Some("jsonl") {
-            output.push(path);
-        }
-    }
+    output.extend(collect_files_with_extension(
+        root,
+        "jsonl",
+        &ScanDeadline::unbounded(),
+    ));
 }
 
 fn load_claude_history_titles(config_dir: &Path) -> HashMap<String, String> {
@@ -463,15 +763,18 @@ fn load_claude_history_titles(config_dir: &Path) -> HashMap<String, String> {
     titles
 }
 
+/// `decoded_project_dirs` caches `decode_project_dir_name` per project directory, so
+/// sessions sharing a directory decode its name once.
 fn parse_thread_file(
     path: &Path,
     official_titles: &HashMap<String, String>,
+    decoded_project_dirs: &mut HashMap<PathBuf, Option<String>>,
+    project_path_base: Option<&Path>,
+    group_by_git_root: bool,
 ) -> Option<ThreadRecord> {
     if path
         .file_name()
-        .and_then(|value| value.to_str())
-        .map(|name| name.starts_with("agent-"))
-        .unwrap_or(false)
+        .is_some_and(|name| name.to_string_lossy().starts_with("agent-"))
     {
         return None;
     }
@@ -482,9 +785,10 @@ fn parse_thread_file(
     let mut session_id_stats: HashMap<String, SessionIdStats> = HashMap::new();
     let mut project_path: Option<String> = None;
     let mut first_user_title: Option<String> = None;
-    let mut created_at: Option<String> = None;
-    let mut last_active_at: Option<String> = None;
+    let mut created_at: Option<Timestamp> = None;
+    let mut last_active_at: Option<Timestamp> = None;
     let mut sort_key = file_last_modified_ms(path).unwrap_or(0);
+    let mut has_messages = false;
 
     for (line_index, line) in reader.lines().map_while(Result::ok).enumerate() {
         let parsed: Value = match serde_json::from_str(&line) {
@@ -493,7 +797,7 @@ fn parse_thread_file(
         };
 
         let timestamp = extract_timestamp(&parsed);
-        let timestamp_ms = timestamp.as_ref().map(|(_, value)| *value).unwrap_or(0);
+        let timestamp_ms = timestamp.map(Timestamp::as_millis).unwrap_or(0);
 
         if let Some(session_id) = parsed
             .get("sessionId")
@@ -516,6 +820,12 @@ fn parse_thread_file(
                 .map(ToString::to_string);
         }
 
+        if parsed.get("isMeta").and_then(Value::as_bool) != Some(true)
+            && parsed.get("message").map(Value::is_object).unwrap_or(false)
+        {
+            has_messages = true;
+        }
+
         if first_user_title.is_none()
             && parsed.get("isMeta").and_then(Value::as_bool) != Some(true)
             && parsed.get("isSidechain").and_then(Value::as_bool) != Some(true)
@@ -532,11 +842,11 @@ fn parse_thread_file(
             }
         }
 
-        if let Some((timestamp_str, timestamp_ms)) = timestamp {
+        if let Some(timestamp) = timestamp {
             if created_at.is_none() {
-                created_at = Some(timestamp_str.clone());
+                created_at = Some(timestamp);
             }
-            last_active_at = Some(timestamp_str);
+            last_active_at = Some(timestamp);
             if timestamp_ms > 0 {
                 sort_key = sort_key.max(timestamp_ms);
             }
@@ -545,7 +855,20 @@ fn parse_thread_file(
 
     let session_id = resolve_canonical_session_id(path, &session_id_stats)?;
 
-    let project_path = project_path.unwrap_or_else(|| ".".to_string());
+    let project_path = project_path.filter(|cwd| !cwd.trim().is_empty());
+    let has_recorded_cwd = project_path.is_some();
+    let project_path = project_path.or_else(|| {
+        let dir = path.parent()?;
+        decoded_project_dirs
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| {
+                dir.file_name()
+                    .and_then(|name| decode_project_dir_name(&name.to_string_lossy()))
+            })
+            .clone()
+    });
+    let project_path = resolve_project_path(project_path, project_path_base);
+    let (project_path, original_cwd) = group_project_path(project_path, group_by_git_root);
     let title = official_titles
         .get(&session_id)
         .and_then(|title| non_empty_trimmed(title))
@@ -555,32 +878,86 @@ fn parse_thread_file(
             .or_else(|| path_basename(&project_path).map(ToString::to_string)))
         .unwrap_or_else(|| format!("Claude session {}", truncate_text(&session_id, 8)));
 
+    let tags = build_thread_tags(ProviderId::ClaudeCode.as_str(), &project_path);
     let summary = ThreadSummary {
         id: session_id,
         provider_id: ProviderId::ClaudeCode,
         account_id: None,
         project_path,
+        original_cwd,
+        model: None,
+        provider_backend: None,
         title,
-        tags: vec!["claude_code".to_string()],
-        last_active_at: last_active_at
-            .or(created_at)
-            .unwrap_or_else(|| now_unix_millis().to_string()),
+        tags,
+        last_active_at: last_active_at.or(created_at).unwrap_or_else(Timestamp::now),
     };
 
     Some(ThreadRecord {
         summary,
         source_path: path.to_path_buf(),
         sort_key,
+        has_messages,
+        has_recorded_cwd,
+    })
+}
+
+/// Reverses Claude's `projects/` directory naming, which replaces `/` (and `.`) in the
+/// absolute cwd with `-`. The encoding is lossy, so splits that match existing
+/// directories win; otherwise, or once the probe budget is spent, every `-` is read
+/// as `/`.
+fn decode_project_dir_name(name: &str) -> Option<String> {
+    let encoded = name.strip_prefix('-').filter(|rest| !rest.is_empty())?;
+    let segments = encoded.split('-').collect::<Vec<&str>>();
+    let mut probes_left = MAX_PROJECT_DIR_DECODE_PROBES;
+    find_existing_decoded_path(Path::new("/"), &segments, &mut probes_left).or_else(|| {
+        Some(format!(
+            "/{}",
+            encoded.replace("--", "/.").replace('-', "/")
+        ))
     })
 }
 
+fn find_existing_decoded_path(
+    base: &Path,
+    segments: &[&str],
+    probes_left: &mut usize,
+) -> Option<String> {
+    if segments.is_empty() {
+        return Some(base.to_string_lossy().to_string());
+    }
+
+    for take in 1..=segments.len() {
+        let joined = segments[..take].join("-");
+        if joined.is_empty() {
+            continue;
+        }
+        // `--config` decodes to `.config`, `app-v2` may have been `app.v2`.
+        let dotted = (take > 1).then(|| joined.replace('-', "."));
+        for component in std::iter::once(joined).chain(dotted) {
+            if *probes_left == 0 {
+                return None;
+            }
+            *probes_left -= 1;
+            let candidate = base.join(&component);
+            if !candidate.is_dir() {
+                continue;
+            }
+            if let Some(found) =
+                find_existing_decoded_path(&candidate, &segments[take..], probes_left)
+            {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
 fn resolve_canonical_session_id(
     path: &Path,
     session_id_stats: &HashMap<String, SessionIdStats>,
 ) -> Option<String> {
-    let file_stem = path
-        .file_stem()
-        .and_then(|stem| stem.to_str())
+    let file_stem = file_stem_lossy(path)
+        .as_deref()
         .and_then(non_empty_trimmed)
         .map(ToString::to_string);
 
@@ -611,6 +988,21 @@ fn compare_session_id_stats(left: &SessionIdStats, right: &SessionIdStats) -> Or
         .then(left.last_seen_index.cmp(&right.last_seen_index))
 }
 
+fn first_session_id(path: &Path) -> Option<String> {
+    let file = File::open(path).ok()?;
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .find_map(|line| {
+            let parsed: Value = serde_json::from_str(&line).ok()?;
+            parsed
+                .get("sessionId")
+                .and_then(Value::as_str)
+                .and_then(non_empty_trimmed)
+                .map(ToString::to_string)
+        })
+}
+
 fn dedupe_thread_records(records: Vec<ThreadRecord>) -> Vec<ThreadRecord> {
     let mut deduped: HashMap<String, ThreadRecord> = HashMap::new();
 
@@ -643,6 +1035,10 @@ fn should_replace_thread_record(existing: &ThreadRecord, candidate: &ThreadRecor
     candidate_path < existing_path
 }
 
+fn is_empty_thread_record(record: &ThreadRecord) -> bool {
+    !record.has_messages && !record.has_recorded_cwd
+}
+
 fn sort_thread_records(records: &mut [ThreadRecord]) {
     records.sort_by(|left, right| {
         right
@@ -653,47 +1049,25 @@ fn sort_thread_records(records: &mut [ThreadRecord]) {
     });
 }
 
-fn extract_timestamp(value: &Value) -> Option<(String, i64)> {
+fn extract_timestamp(value: &Value) -> Option<Timestamp> {
     let timestamp = value.get("timestamp")?;
 
     match timestamp {
-        Value::Number(number) => {
-            let raw = number.as_i64()?;
-            let ms = normalize_epoch(raw);
-            Some((ms.to_string(), ms))
-        }
+        Value::Number(number) => number.as_i64().map(Timestamp::from_epoch),
         Value::String(raw) => {
             let trimmed = raw.trim();
             if trimmed.is_empty() {
                 return None;
             }
             if let Ok(parsed) = trimmed.parse::<i64>() {
-                let ms = normalize_epoch(parsed);
-                return Some((ms.to_string(), ms));
+                return Some(Timestamp::from_epoch(parsed));
             }
-            if let Some(ms) = parse_rfc3339_timestamp_ms(trimmed) {
-                return Some((ms.to_string(), ms));
-            }
-            Some((trimmed.to_string(), 0))
+            Timestamp::from_rfc3339(trimmed)
         }
         _ => None,
     }
 }
 
-fn parse_rfc3339_timestamp_ms(value: &str) -> Option<i64> {
-    let parsed = OffsetDateTime::parse(value, &Rfc3339).ok()?;
-    let nanos = parsed.unix_timestamp_nanos();
-    Some((nanos / 1_000_000) as i64)
-}
-
-fn normalize_epoch(raw: i64) -> i64 {
-    if raw.abs() < 1_000_000_000_000 {
-        raw * 1000
-    } else {
-        raw
-    }
-}
-
 fn file_last_modified_ms(path: &Path) -> Option<i64> {
     let metadata = fs::metadata(path).ok()?;
     let modified = metadata.modified().ok()?;
@@ -706,7 +1080,7 @@ fn path_basename(path: &str) -> Option<&str> {
 }
 
 fn parse_timestamp_ms(value: &Value) -> Option<i64> {
-    let (_, timestamp_ms) = extract_timestamp(value)?;
+    let timestamp_ms = extract_timestamp(value)?.as_millis();
     if timestamp_ms > 0 {
         Some(timestamp_ms)
     } else {
@@ -714,7 +1088,16 @@ fn parse_timestamp_ms(value: &Value) -> Option<i64> {
     }
 }
 
-fn load_thread_runtime_state(path: &Path) -> ClaudeThreadRuntimeState {
+/// `ThreadEvent::timestamp_raw` for a `timestamp` field.
+fn raw_timestamp(value: Option<&Value>) -> Option<String> {
+    value?
+        .as_str()
+        .map(str::trim)
+        .filter(|raw| !raw.is_empty())
+        .map(ToString::to_string)
+}
+
+fn load_thread_runtime_state(path: &Path, now_ms: i64) -> ClaudeThreadRuntimeState {
     let file = match File::open(path) {
         Ok(file) => file,
         Err(_) => {
@@ -744,9 +1127,7 @@ fn load_thread_runtime_state(path: &Path) -> ClaudeThreadRuntimeState {
     }
 
     let is_recent = last_event_at_ms
-        .map(|timestamp_ms| {
-            now_unix_millis().saturating_sub(timestamp_ms) <= CLAUDE_AGENT_ACTIVITY_WINDOW_MS
-        })
+        .map(|timestamp_ms| now_ms.saturating_sub(timestamp_ms) <= CLAUDE_AGENT_ACTIVITY_WINDOW_MS)
         .unwrap_or(false);
     let agent_answering = is_recent
         && matches!(
@@ -766,16 +1147,122 @@ fn load_thread_runtime_state(path: &Path) -> ClaudeThreadRuntimeState {
     }
 }
 
-fn extract_semantic_event_kind(record: &Value) -> Option<ClaudeSemanticEventKind> {
-    if record.get("type").and_then(Value::as_str) == Some("queue-operation")
-        && record.get("operation").and_then(Value::as_str) == Some("dequeue")
-    {
-        return Some(ClaudeSemanticEventKind::QueueDequeue);
-    }
+fn load_thread_event_log(path: &Path, limit: usize) -> Vec<ThreadEvent> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(_) => return Vec::new(),
+    };
+    let reader = BufReader::new(file);
+    let mut events = VecDeque::new();
 
-    if record
-        .get("toolUseResult")
-        .and_then(|value| value.get("status"))
+    for line in reader.lines().map_while(Result::ok) {
+        let parsed: Value = match serde_json::from_str(&line) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+
+        if let Some(kind) = extract_semantic_event_kind(&parsed) {
+            events.push_back(ThreadEvent {
+                kind: kind.as_str().to_string(),
+                at_ms: parse_timestamp_ms(&parsed),
+                timestamp_raw: raw_timestamp(parsed.get("timestamp")),
+            });
+            if events.len() > limit {
+                events.pop_front();
+            }
+        }
+    }
+
+    events.into()
+}
+
+/// Counts visible user/assistant messages and collects `file_path` inputs of the
+/// edit tools (`Edit`, `MultiEdit`, `Write`, `NotebookEdit`). Sidechain records are
+/// skipped like in previews.
+fn load_thread_activity(path: &Path) -> ThreadActivity {
+    let mut activity = ThreadActivity::default();
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(_) => return activity,
+    };
+
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        let parsed: Value = match serde_json::from_str(&line) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+        if parsed.get("isSidechain").and_then(Value::as_bool) == Some(true) {
+            continue;
+        }
+        activity.record_timestamp(parse_timestamp_ms(&parsed));
+
+        if is_counted_message(&parsed) {
+            activity.message_count += 1;
+        }
+        let Some(message) = parsed.get("message") else {
+            continue;
+        };
+        if let Some(Value::Array(items)) = message.get("content") {
+            for item in items {
+                if let Some(path) = edit_tool_file_path(item) {
+                    activity.record_touched_file(path);
+                }
+            }
+        }
+    }
+
+    activity
+}
+
+/// Lines without a `message` key are skipped before parsing.
+fn count_thread_messages(path: &Path) -> usize {
+    let Ok(file) = File::open(path) else {
+        return 0;
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter(|line| line.contains("\"message\""))
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok())
+        .filter(|parsed| {
+            parsed.get("isSidechain").and_then(Value::as_bool) != Some(true)
+                && is_counted_message(parsed)
+        })
+        .count()
+}
+
+fn is_counted_message(record: &Value) -> bool {
+    record.get("isMeta").and_then(Value::as_bool) != Some(true)
+        && record
+            .get("message")
+            .is_some_and(|message| extract_preview_text(message).is_some())
+}
+
+fn edit_tool_file_path(block: &Value) -> Option<&str> {
+    if block.get("type").and_then(Value::as_str) != Some("tool_use") {
+        return None;
+    }
+    let name = block.get("name").and_then(Value::as_str)?;
+    if !matches!(name, "Edit" | "MultiEdit" | "Write" | "NotebookEdit") {
+        return None;
+    }
+    let input = block.get("input")?;
+    input
+        .get("file_path")
+        .or_else(|| input.get("notebook_path"))
+        .and_then(Value::as_str)
+}
+
+fn extract_semantic_event_kind(record: &Value) -> Option<ClaudeSemanticEventKind> {
+    if record.get("type").and_then(Value::as_str) == Some("queue-operation")
+        && record.get("operation").and_then(Value::as_str) == Some("dequeue")
+    {
+        return Some(ClaudeSemanticEventKind::QueueDequeue);
+    }
+
+    if record
+        .get("toolUseResult")
+        .and_then(|value| value.get("status"))
         .and_then(Value::as_str)
         == Some("completed")
     {
@@ -824,7 +1311,7 @@ fn extract_semantic_event_kind_from_message(
         .or(role_hint)
         .unwrap_or("unknown");
 
-    let content = message.get("content");
+    let content = message_content(message);
 
     if role == "assistant" {
         if let Some(content) = content {
@@ -929,24 +1416,7 @@ fn build_last_message_preview(path: &Path) -> Option<String> {
     let mut last_visible_text: Option<String> = None;
 
     for line in reader.lines().map_while(Result::ok) {
-        let parsed: Value = match serde_json::from_str(&line) {
-            Ok(value) => value,
-            Err(_) => continue,
-        };
-
-        if parsed.get("isMeta").and_then(Value::as_bool) == Some(true) {
-            continue;
-        }
-        if parsed.get("isSidechain").and_then(Value::as_bool) == Some(true) {
-            continue;
-        }
-
-        let message = match parsed.get("message") {
-            Some(value) => value,
-            None => continue,
-        };
-
-        if let Some(text) = extract_preview_text(message) {
+        if let Some(text) = extract_line_preview_text(&line) {
             last_visible_text = Some(text);
         }
     }
@@ -954,9 +1424,38 @@ fn build_last_message_preview(path: &Path) -> Option<String> {
     last_visible_text.map(|text| truncate_text(&text, 140))
 }
 
+fn build_latest_message_preview(path: &Path) -> Option<String> {
+    find_last_line_map(path, extract_line_preview_text).map(|text| truncate_text(&text, 140))
+}
+
+fn extract_line_preview_text(line: &str) -> Option<String> {
+    let parsed: Value = serde_json::from_str(line).ok()?;
+    if parsed.get("isMeta").and_then(Value::as_bool) == Some(true) {
+        return None;
+    }
+    if parsed.get("isSidechain").and_then(Value::as_bool) == Some(true) {
+        return None;
+    }
+
+    extract_preview_text(parsed.get("message")?)
+}
+
+fn extract_line_assistant_text(line: &str) -> Option<String> {
+    let parsed: Value = serde_json::from_str(line).ok()?;
+    if parsed.get("isSidechain").and_then(Value::as_bool) == Some(true) {
+        return None;
+    }
+
+    let message = parsed.get("message")?;
+    if message.get("role").and_then(Value::as_str) != Some("assistant") {
+        return None;
+    }
+    extract_preview_text(message)
+}
+
 /// Extract visible text from a message content value for preview purposes.
 fn extract_preview_text(message: &Value) -> Option<String> {
-    let content = message.get("content")?;
+    let content = message_content(message)?;
     match content {
         Value::String(text) => sanitize_preview_text(text),
         Value::Array(items) => {
@@ -986,14 +1485,68 @@ fn extract_preview_text(message: &Value) -> Option<String> {
     }
 }
 
+/// Some Claude log variants put plain text under `message.text` or `message.message`
+/// instead of a `content` field.
+fn message_content(message: &Value) -> Option<&Value> {
+    message.get("content").or_else(|| {
+        ["text", "message"]
+            .into_iter()
+            .filter_map(|key| message.get(key))
+            .find(|value| value.is_string())
+    })
+}
+
 fn sanitize_preview_text(raw: &str) -> Option<String> {
-    let trimmed = raw.trim();
+    let stripped = strip_ansi_escapes(raw);
+    let trimmed = stripped.trim();
     if trimmed.is_empty() || is_internal_command_text(trimmed) {
         return None;
     }
     normalize_preview_text(trimmed)
 }
 
+/// Removes terminal escape sequences that tool output leaks into message text: CSI
+/// (colors, cursor movement, erase), OSC (window titles, hyperlinks) terminated by BEL or
+/// ST, and other two-byte escapes.
+fn strip_ansi_escapes(raw: &str) -> String {
+    if !raw.contains('\u{1b}') {
+        return raw.to_string();
+    }
+
+    let mut output = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '\u{1b}' {
+            output.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            Some('[') => {
+                // Parameters and intermediates, then a final byte in @..~.
+                for next in chars.by_ref() {
+                    if ('@'..='~').contains(&next) {
+                        break;
+                    }
+                }
+            }
+            Some(']') => {
+                while let Some(next) = chars.next() {
+                    if next == '\u{7}' {
+                        break;
+                    }
+                    if next == '\u{1b}' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    output
+}
+
 fn is_internal_command_text(raw: &str) -> bool {
     raw.contains("<local-command-")
         || raw.contains("<command-")
@@ -1059,17 +1612,11 @@ fn shell_quote(path: &str) -> String {
     format!("'{}'", path.replace('\'', "'\"'\"'"))
 }
 
-fn now_unix_millis() -> i64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|duration| duration.as_millis() as i64)
-        .unwrap_or(0)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use provider_contract::{FixedClock, UNRESOLVED_PROJECT_PATH_TAG};
     use std::sync::atomic::{AtomicU64, Ordering};
     use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -1147,6 +1694,23 @@ mod tests {
         assert_eq!(threads[0].title, "Implement provider adapter");
     }
 
+    #[test]
+    fn default_provider_tag_matches_provider_id() {
+        let config_dir = test_temp_dir("provider-tag").join(".claude");
+        write_lines(
+            &config_dir.join("projects/workspace-a/session-tag.jsonl"),
+            &[
+                r#"{"sessionId":"session-tag","cwd":"/workspace/a","timestamp":"1700000000000","message":{"role":"user","content":"Tag me"}}"#,
+            ],
+        );
+        let adapter = ClaudeAdapter::new().with_config_dir(&config_dir);
+
+        let threads = adapter.list_threads(None).expect("threads should load");
+
+        assert_eq!(threads[0].tags, vec![adapter.provider_id().as_str()]);
+        assert_eq!(threads[0].provider_id, adapter.provider_id());
+    }
+
     #[test]
     fn list_threads_prefers_canonical_session_id_when_file_contains_mixed_session_ids() {
         let config_dir = test_temp_dir("mixed-session-ids").join(".claude");
@@ -1318,6 +1882,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn latest_message_preview_matches_full_parse_across_chunk_boundaries() {
+        let config_dir = test_temp_dir("latest-preview").join(".claude");
+        let session_path = config_dir.join("projects/demo/session-latest.jsonl");
+        let long_text = "x".repeat(80 * 1024);
+
+        write_owned_lines(
+            &session_path,
+            &[
+                r#"{"sessionId":"session-latest","cwd":"/workspace/demo","timestamp":"1700000000000","message":{"role":"user","content":"First question"}}"#.to_string(),
+                format!(
+                    r#"{{"sessionId":"session-latest","cwd":"/workspace/demo","timestamp":"1700000000050","message":{{"role":"assistant","content":[{{"type":"text","text":"Answer {long_text}"}}]}}}}"#
+                ),
+                r#"{"sessionId":"session-latest","cwd":"/workspace/demo","timestamp":"1700000000100","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash","input":{"command":"ls"}}]}}"#.to_string(),
+            ],
+        );
+
+        let adapter = ClaudeAdapter::new().with_config_dir(&config_dir);
+        let latest = adapter
+            .get_latest_message_preview("session-latest")
+            .expect("latest preview should work");
+
+        assert_eq!(latest, build_last_message_preview(&session_path));
+        assert!(latest
+            .expect("preview should exist")
+            .starts_with("Answer xxx"));
+    }
+
     #[test]
     fn health_check_reports_offline_when_cli_missing() {
         let config_dir = test_temp_dir("health-offline").join(".claude");
@@ -1363,7 +1955,7 @@ mod tests {
     fn runtime_state_marks_recent_progress_as_answering() {
         let config_dir = test_temp_dir("runtime-answering").join(".claude");
         let session_path = config_dir.join("projects/demo/session-runtime.jsonl");
-        let now = now_unix_millis();
+        let now = 1_700_000_000_000;
 
         write_owned_lines(
             &session_path,
@@ -1379,7 +1971,9 @@ mod tests {
             ],
         );
 
-        let adapter = ClaudeAdapter::new().with_config_dir(&config_dir);
+        let adapter = ClaudeAdapter::new()
+            .with_config_dir(&config_dir)
+            .with_clock(FixedClock(Timestamp::from_epoch_millis(now)));
         let state = adapter
             .get_thread_runtime_state("session-runtime")
             .expect("runtime state should be readable");
@@ -1392,7 +1986,7 @@ mod tests {
     fn runtime_state_marks_recent_assistant_text_as_not_answering() {
         let config_dir = test_temp_dir("runtime-idle-text").join(".claude");
         let session_path = config_dir.join("projects/demo/session-runtime-idle.jsonl");
-        let now = now_unix_millis();
+        let now = 1_700_000_000_000;
 
         write_owned_lines(
             &session_path,
@@ -1408,7 +2002,9 @@ mod tests {
             ],
         );
 
-        let adapter = ClaudeAdapter::new().with_config_dir(&config_dir);
+        let adapter = ClaudeAdapter::new()
+            .with_config_dir(&config_dir)
+            .with_clock(FixedClock(Timestamp::from_epoch_millis(now)));
         let state = adapter
             .get_thread_runtime_state("session-runtime-idle")
             .expect("runtime state should be readable");
@@ -1421,7 +2017,7 @@ mod tests {
     fn runtime_state_marks_old_progress_as_not_answering() {
         let config_dir = test_temp_dir("runtime-old-progress").join(".claude");
         let session_path = config_dir.join("projects/demo/session-runtime-old.jsonl");
-        let now = now_unix_millis();
+        let now = 1_700_000_000_000;
 
         write_owned_lines(
             &session_path,
@@ -1431,7 +2027,9 @@ mod tests {
             )],
         );
 
-        let adapter = ClaudeAdapter::new().with_config_dir(&config_dir);
+        let adapter = ClaudeAdapter::new()
+            .with_config_dir(&config_dir)
+            .with_clock(FixedClock(Timestamp::from_epoch_millis(now)));
         let state = adapter
             .get_thread_runtime_state("session-runtime-old")
             .expect("runtime state should be readable");
@@ -1446,4 +2044,793 @@ mod tests {
             .expect("json should parse");
         assert!(parse_timestamp_ms(&value).is_some());
     }
+
+    #[test]
+    fn build_resume_command_appends_context_summary_as_prompt() {
+        let command = build_resume_command(
+            "claude",
+            "session-a",
+            None,
+            Some("Objective: ship the parser. Pending: add tests."),
+        );
+        assert_eq!(
+            command,
+            format!(
+                "claude --resume {} {}",
+                shell_quote("session-a"),
+                shell_quote("Objective: ship the parser. Pending: add tests.")
+            )
+        );
+
+        let without_summary = build_resume_command("claude", "session-a", None, Some("  "));
+        assert_eq!(
+            without_summary,
+            format!("claude --resume {}", shell_quote("session-a"))
+        );
+    }
+
+    #[test]
+    fn resume_command_uses_configured_cli_binary() {
+        let config_dir = test_temp_dir("resume-present").join(".claude");
+        write_lines(
+            &config_dir.join("projects/demo/session-resume.jsonl"),
+            &[
+                r#"{"sessionId":"session-resume","cwd":"/workspace/demo","timestamp":"1700000000000","message":{"role":"user","content":"hello"}}"#,
+            ],
+        );
+
+        let adapter = ClaudeAdapter::new()
+            .with_config_dir(&config_dir)
+            .with_cli_binary("custom-claude");
+        let request = ResumeThreadRequest {
+            thread_id: "session-resume".to_string(),
+            project_path: None,
+            context_summary: None,
+        };
+        let record = adapter
+            .find_thread_record(&request.thread_id)
+            .expect("thread record should exist");
+
+        assert!(adapter.resume_command(&record, &request).contains(&format!(
+            "custom-claude --resume {}",
+            shell_quote("session-resume")
+        )));
+    }
+
+    #[test]
+    fn resume_thread_reports_removed_session_as_not_resumable() {
+        let config_dir = test_temp_dir("resume-removed").join(".claude");
+        let session_path = config_dir.join("projects/demo/session-removed.jsonl");
+        write_lines(
+            &session_path,
+            &[
+                r#"{"sessionId":"session-removed","cwd":"/workspace/demo","timestamp":"1700000000000","message":{"role":"user","content":"hello"}}"#,
+            ],
+        );
+        let adapter = ClaudeAdapter::new()
+            .with_config_dir(&config_dir)
+            .with_cli_binary("missing-claude-binary-123");
+        assert!(adapter.thread_exists("session-removed"));
+
+        fs::remove_file(&session_path).expect("session file should be removable");
+        let result = adapter
+            .resume_thread(ResumeThreadRequest {
+                thread_id: "session-removed".to_string(),
+                project_path: None,
+                context_summary: None,
+            })
+            .expect("resume should report instead of failing");
+
+        assert!(!result.resumed);
+        assert!(result
+            .message
+            .expect("reason should exist")
+            .contains("no longer exists"));
+    }
+
+    #[test]
+    fn list_threads_resolves_relative_cwd_against_project_path_base() {
+        let config_dir = test_temp_dir("relative-cwd").join(".claude");
+        write_lines(
+            &config_dir.join("projects/demo/session-relative.jsonl"),
+            &[
+                r#"{"sessionId":"session-relative","cwd":"./apps/../web","timestamp":"1700000000000","message":{"role":"user","content":"hi"}}"#,
+            ],
+        );
+        write_lines(
+            &config_dir.join("projects/demo/session-absolute.jsonl"),
+            &[
+                r#"{"sessionId":"session-absolute","cwd":"/workspace/absolute","timestamp":"1700000000000","message":{"role":"user","content":"hi"}}"#,
+            ],
+        );
+        write_lines(
+            &config_dir.join("projects/demo/session-missing.jsonl"),
+            &[
+                r#"{"sessionId":"session-missing","timestamp":"1700000000000","message":{"role":"user","content":"hi"}}"#,
+            ],
+        );
+
+        let base = PathBuf::from("/workspace/monorepo");
+        let adapter = ClaudeAdapter::new()
+            .with_config_dir(&config_dir)
+            .with_project_path_base(&base);
+        let threads = adapter
+            .list_threads(None)
+            .expect("list_threads should work");
+        let find = |id: &str| {
+            threads
+                .iter()
+                .find(|thread| thread.id == id)
+                .expect("thread should exist")
+        };
+
+        assert_eq!(
+            find("session-relative").project_path,
+            base.join("web").to_string_lossy()
+        );
+        assert_eq!(find("session-absolute").project_path, "/workspace/absolute");
+        let missing = find("session-missing");
+        assert_eq!(missing.project_path, ".");
+        assert!(missing
+            .tags
+            .contains(&UNRESOLVED_PROJECT_PATH_TAG.to_string()));
+    }
+
+    #[test]
+    fn list_thread_overviews_reads_text_when_message_content_is_absent() {
+        let config_dir = test_temp_dir("message-text-fallback").join(".claude");
+        write_lines(
+            &config_dir.join("projects/demo/session-text.jsonl"),
+            &[
+                r#"{"sessionId":"session-text","cwd":"/workspace/demo","timestamp":"1700000000000","message":{"role":"user","text":"Question without content array"}}"#,
+                r#"{"sessionId":"session-text","cwd":"/workspace/demo","timestamp":"1700000000100","message":{"role":"assistant","message":"Answer stored under message"}}"#,
+            ],
+        );
+
+        let adapter = ClaudeAdapter::new().with_config_dir(&config_dir);
+        let overviews = adapter
+            .list_thread_overviews(None)
+            .expect("thread overviews should work");
+
+        assert_eq!(overviews.len(), 1);
+        assert_eq!(overviews[0].summary.title, "Question without content array");
+        assert_eq!(
+            overviews[0].last_message_preview.as_deref(),
+            Some("Answer stored under message")
+        );
+    }
+
+    #[test]
+    fn get_auth_mode_reads_settings_env() {
+        let cases = [
+            (
+                "auth-token",
+                r#"{"env":{"ANTHROPIC_AUTH_TOKEN":"token-123"}}"#,
+                ClaudeAuthMode::AuthToken,
+            ),
+            (
+                "api-key",
+                r#"{"env":{"ANTHROPIC_API_KEY":"sk-123"}}"#,
+                ClaudeAuthMode::ApiKey,
+            ),
+            ("neither", r#"{"env":{}}"#, ClaudeAuthMode::OauthOrUnknown),
+        ];
+
+        for (name, settings, expected) in cases {
+            let config_dir = test_temp_dir(&format!("auth-mode-{name}")).join(".claude");
+            fs::create_dir_all(&config_dir).expect("config dir should be creatable");
+            fs::write(config_dir.join("settings.json"), settings)
+                .expect("settings should be writable");
+
+            let mode = ClaudeAdapter::new()
+                .with_config_dir(config_dir)
+                .load_auth_mode(|_| None)
+                .expect("auth mode should load");
+            assert_eq!(mode, expected, "unexpected auth mode for {name}");
+        }
+    }
+
+    #[test]
+    fn get_auth_mode_falls_back_to_process_env() {
+        let adapter =
+            ClaudeAdapter::new().with_config_dir(test_temp_dir("auth-mode-env").join(".claude"));
+        let api_key_env = |key: &str| (key == "ANTHROPIC_API_KEY").then(|| "sk-123".to_string());
+
+        assert_eq!(
+            adapter
+                .load_auth_mode(api_key_env)
+                .expect("auth mode should load"),
+            ClaudeAuthMode::ApiKey
+        );
+        assert_eq!(
+            detect_claude_auth_mode(
+                &serde_json::json!({"env": {"ANTHROPIC_AUTH_TOKEN": "token-123"}}),
+                api_key_env
+            ),
+            ClaudeAuthMode::AuthToken
+        );
+    }
+
+    #[test]
+    fn list_threads_groups_subdirectory_cwd_under_git_root() {
+        let root = test_temp_dir("git-root");
+        let repo = root.join("repo");
+        let subdir = repo.join("packages").join("web");
+        fs::create_dir_all(repo.join(".git")).expect("git dir should be creatable");
+        fs::create_dir_all(&subdir).expect("subdir should be creatable");
+        let plain = root.join("plain");
+        fs::create_dir_all(&plain).expect("plain dir should be creatable");
+
+        let config_dir = root.join(".claude");
+        write_owned_lines(
+            &config_dir.join("projects/demo/session-repo.jsonl"),
+            &[format!(
+                r#"{{"sessionId":"session-repo","cwd":"{}","timestamp":"1700000000000","message":{{"role":"user","content":"hi"}}}}"#,
+                subdir.display()
+            )],
+        );
+        write_owned_lines(
+            &config_dir.join("projects/demo/session-plain.jsonl"),
+            &[format!(
+                r#"{{"sessionId":"session-plain","cwd":"{}","timestamp":"1700000000000","message":{{"role":"user","content":"hi"}}}}"#,
+                plain.display()
+            )],
+        );
+
+        let threads = ClaudeAdapter::new()
+            .with_config_dir(&config_dir)
+            .with_git_root_grouping(true)
+            .list_threads(None)
+            .expect("list_threads should work");
+        let find = |id: &str| {
+            threads
+                .iter()
+                .find(|thread| thread.id == id)
+                .expect("thread should exist")
+        };
+
+        let grouped = find("session-repo");
+        assert_eq!(grouped.project_path, repo.to_string_lossy());
+        assert_eq!(
+            grouped.original_cwd.as_deref(),
+            Some(subdir.to_string_lossy().as_ref())
+        );
+        let passthrough = find("session-plain");
+        assert_eq!(passthrough.project_path, plain.to_string_lossy());
+        assert!(passthrough.original_cwd.is_none());
+    }
+
+    #[test]
+    fn resume_command_runs_grouped_session_from_its_original_cwd() {
+        let root = test_temp_dir("git-root-resume");
+        let repo = root.join("repo");
+        let subdir = repo.join("packages").join("web");
+        fs::create_dir_all(repo.join(".git")).expect("git dir should be creatable");
+        fs::create_dir_all(&subdir).expect("subdir should be creatable");
+        let config_dir = root.join(".claude");
+        write_owned_lines(
+            &config_dir.join("projects/demo/session-repo.jsonl"),
+            &[format!(
+                r#"{{"sessionId":"session-repo","cwd":"{}","timestamp":"1700000000000","message":{{"role":"user","content":"hi"}}}}"#,
+                subdir.display()
+            )],
+        );
+
+        let adapter = ClaudeAdapter::new()
+            .with_config_dir(&config_dir)
+            .with_git_root_grouping(true);
+        let record = adapter
+            .find_thread_record("session-repo")
+            .expect("record should exist");
+        let command = adapter.resume_command(
+            &record,
+            &ResumeThreadRequest {
+                thread_id: "session-repo".to_string(),
+                project_path: None,
+                context_summary: None,
+            },
+        );
+
+        assert_eq!(record.summary.project_path, repo.to_string_lossy());
+        assert!(
+            command.starts_with(&prepend_workdir_to_command(
+                String::new(),
+                &subdir.to_string_lossy()
+            )),
+            "unexpected resume command: {command}"
+        );
+    }
+
+    #[test]
+    fn runtime_state_activity_window_includes_exact_edge() {
+        let config_dir = test_temp_dir("runtime-window-edge").join(".claude");
+        let session_path = config_dir.join("projects/demo/session-runtime-edge.jsonl");
+        let event_at = 1_700_000_000_000;
+
+        write_owned_lines(
+            &session_path,
+            &[format!(
+                r#"{{"sessionId":"session-runtime-edge","cwd":"/workspace/demo","timestamp":{event_at},"type":"progress","data":{{"type":"bash_progress","output":"running..."}}}}"#
+            )],
+        );
+
+        let state_at = |now: i64| {
+            ClaudeAdapter::new()
+                .with_config_dir(&config_dir)
+                .with_clock(FixedClock(Timestamp::from_epoch_millis(now)))
+                .get_thread_runtime_state("session-runtime-edge")
+                .expect("runtime state should be readable")
+        };
+
+        assert!(state_at(event_at + CLAUDE_AGENT_ACTIVITY_WINDOW_MS).agent_answering);
+        assert!(!state_at(event_at + CLAUDE_AGENT_ACTIVITY_WINDOW_MS + 1).agent_answering);
+    }
+
+    #[test]
+    fn strip_ansi_escapes_removes_osc_and_cursor_sequences() {
+        assert_eq!(
+            strip_ansi_escapes("\u{1b}]0;claude: build\u{7}Build finished"),
+            "Build finished"
+        );
+        assert_eq!(
+            strip_ansi_escapes("\u{1b}]8;;https://example.com\u{1b}\\link\u{1b}]8;;\u{1b}\\ done"),
+            "link done"
+        );
+        assert_eq!(
+            strip_ansi_escapes("\u{1b}[2K\u{1b}[1G\u{1b}[?25l\u{1b}[31mError\u{1b}[0m: failed"),
+            "Error: failed"
+        );
+        assert_eq!(strip_ansi_escapes("plain text"), "plain text");
+    }
+
+    #[test]
+    fn sanitize_preview_text_drops_terminal_title_noise() {
+        assert_eq!(
+            sanitize_preview_text("\u{1b}]0;✳ Claude Code\u{7}  All tests pass  ").as_deref(),
+            Some("All tests pass")
+        );
+        assert_eq!(sanitize_preview_text("\u{1b}]0;title\u{7}\u{1b}[2K"), None);
+    }
+
+    #[test]
+    fn thread_event_log_returns_last_events_in_order() {
+        let config_dir = test_temp_dir("event-log").join(".claude");
+        let session_path = config_dir.join("projects/demo/session-events.jsonl");
+
+        write_lines(
+            &session_path,
+            &[
+                r#"{"sessionId":"session-events","cwd":"/workspace/demo","timestamp":1700000001000,"type":"user","message":{"role":"user","content":[{"type":"text","text":"fix the build"}]}}"#,
+                r#"{"sessionId":"session-events","cwd":"/workspace/demo","timestamp":1700000002000,"type":"assistant","message":{"role":"assistant","content":[{"type":"thinking","thinking":"look at the logs"}]}}"#,
+                r#"{"sessionId":"session-events","cwd":"/workspace/demo","timestamp":1700000003000,"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash","input":{"command":"cargo build"}}]}}"#,
+                r#"{"sessionId":"session-events","cwd":"/workspace/demo","timestamp":1700000004000,"type":"user","message":{"role":"user","content":[{"type":"tool_result","content":"ok"}]}}"#,
+                r#"{"sessionId":"session-events","cwd":"/workspace/demo","timestamp":1700000005000,"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Build fixed."}]}}"#,
+            ],
+        );
+
+        let adapter = ClaudeAdapter::new().with_config_dir(&config_dir);
+        let events = adapter
+            .get_thread_event_log("session-events", 10)
+            .expect("event log should be readable");
+        let kinds: Vec<&str> = events.iter().map(|event| event.kind.as_str()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "user_message",
+                "agent_reasoning",
+                "agent_tool",
+                "agent_tool",
+                "agent_message"
+            ]
+        );
+        assert_eq!(events[0].at_ms, Some(1_700_000_001_000));
+
+        let tail = adapter
+            .get_thread_event_log("session-events", 2)
+            .expect("event log should be readable");
+        let kinds: Vec<&str> = tail.iter().map(|event| event.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["agent_tool", "agent_message"]);
+        assert_eq!(tail[1].at_ms, Some(1_700_000_005_000));
+    }
+
+    #[test]
+    fn thread_exists_tracks_session_files_on_disk() {
+        let config_dir = test_temp_dir("thread-exists").join(".claude");
+        let named_path = config_dir.join("projects/demo/session-named.jsonl");
+        let renamed_path = config_dir.join("projects/demo/renamed.jsonl");
+        write_lines(
+            &named_path,
+            &[
+                r#"{"sessionId":"session-named","cwd":"/workspace/demo","type":"user","message":{"role":"user","content":"hello"}}"#,
+            ],
+        );
+        write_lines(
+            &renamed_path,
+            &[
+                r#"{"sessionId":"session-renamed","cwd":"/workspace/demo","type":"user","message":{"role":"user","content":"hello"}}"#,
+            ],
+        );
+        let adapter = ClaudeAdapter::new().with_config_dir(&config_dir);
+
+        assert!(adapter.thread_exists("session-named"));
+        assert!(adapter.thread_exists("session-renamed"));
+        assert!(!adapter.thread_exists("session-missing"));
+
+        fs::remove_file(&named_path).expect("session file should be removed");
+        assert!(!adapter.thread_exists("session-named"));
+    }
+
+    #[test]
+    fn list_threads_hides_empty_placeholder_threads_when_enabled() {
+        let config_dir = test_temp_dir("hide-empty").join(".claude");
+        let project_dir = config_dir.join("projects").join("-workspace-a");
+        write_lines(
+            &project_dir.join("session-real.jsonl"),
+            &[
+                r#"{"sessionId":"session-real","cwd":"/workspace/a","timestamp":"1700000000500","message":{"role":"user","content":"Fix the build"}}"#,
+            ],
+        );
+        write_lines(
+            &project_dir.join("session-empty.jsonl"),
+            &[r#"{"sessionId":"session-empty","timestamp":"1700000000000","isMeta":true}"#],
+        );
+
+        let all = ClaudeAdapter::new()
+            .with_config_dir(&config_dir)
+            .list_threads(None)
+            .expect("list_threads should work");
+        assert_eq!(all.len(), 2);
+
+        let threads = ClaudeAdapter::new()
+            .with_config_dir(&config_dir)
+            .with_hide_empty_threads(true)
+            .list_threads(None)
+            .expect("list_threads should work");
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].id, "session-real");
+    }
+
+    #[test]
+    fn list_threads_skips_session_files_over_the_size_cap() {
+        let config_dir = test_temp_dir("oversized").join(".claude");
+        let project_dir = config_dir.join("projects").join("workspace-a");
+        write_lines(
+            &project_dir.join("session-small.jsonl"),
+            &[
+                r#"{"sessionId":"session-small","cwd":"/workspace/a","timestamp":"1700000000500","message":{"role":"user","content":"Hi"}}"#,
+            ],
+        );
+        let padding = "x".repeat(512);
+        let large_line = format!(
+            r#"{{"sessionId":"session-large","cwd":"/workspace/a","timestamp":"1700000000500","message":{{"role":"user","content":"{padding}"}}}}"#
+        );
+        write_lines(&project_dir.join("session-large.jsonl"), &[&large_line]);
+
+        let adapter = ClaudeAdapter::new()
+            .with_config_dir(&config_dir)
+            .with_max_session_file_bytes(256);
+        let threads = adapter
+            .list_threads(None)
+            .expect("list_threads should work");
+
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].id, "session-small");
+        let oversized = adapter.oversized_session_files();
+        assert_eq!(oversized.len(), 1);
+        assert_eq!(oversized[0].path, project_dir.join("session-large.jsonl"));
+        assert_eq!(oversized[0].max_bytes, 256);
+    }
+
+    #[test]
+    fn final_assistant_message_skips_trailing_tool_records() {
+        let config_dir = test_temp_dir("final-assistant").join(".claude");
+        write_lines(
+            &config_dir.join("projects/demo/session-final.jsonl"),
+            &[
+                r#"{"sessionId":"session-final","cwd":"/workspace/demo","timestamp":"1700000000000","message":{"role":"user","content":"Run the tests"}}"#,
+                r#"{"sessionId":"session-final","cwd":"/workspace/demo","timestamp":"1700000000050","message":{"role":"assistant","content":[{"type":"text","text":"All 42 tests pass."}]}}"#,
+                r#"{"sessionId":"session-final","cwd":"/workspace/demo","timestamp":"1700000000100","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash","input":{"command":"git status"}}]}}"#,
+                r#"{"sessionId":"session-final","cwd":"/workspace/demo","timestamp":"1700000000150","message":{"role":"user","content":[{"type":"tool_result","content":"clean"}]}}"#,
+            ],
+        );
+
+        let adapter = ClaudeAdapter::new().with_config_dir(&config_dir);
+        let message = adapter
+            .get_final_assistant_message("session-final")
+            .expect("final assistant message should load");
+
+        assert_eq!(message.as_deref(), Some("All 42 tests pass."));
+    }
+
+    #[test]
+    fn list_threads_decodes_project_dir_name_when_cwd_is_missing() {
+        let root = test_temp_dir("decoded-project-dir");
+        let repo = root.join("my-app");
+        fs::create_dir_all(&repo).expect("repo dir should be creatable");
+        let encoded = repo.to_string_lossy().replace(['/', '.'], "-");
+        let config_dir = root.join(".claude");
+        let projects_dir = config_dir.join("projects");
+        write_lines(
+            &projects_dir.join(&encoded).join("session-existing.jsonl"),
+            &[
+                r#"{"sessionId":"session-existing","timestamp":"1700000000500","message":{"role":"user","content":"Where am I?"}}"#,
+            ],
+        );
+        write_lines(
+            &projects_dir
+                .join("-workspace-missing-app")
+                .join("session-missing.jsonl"),
+            &[
+                r#"{"sessionId":"session-missing","timestamp":"1700000000400","message":{"role":"user","content":"Still here?"}}"#,
+            ],
+        );
+
+        let threads = ClaudeAdapter::new()
+            .with_config_dir(&config_dir)
+            .list_threads(None)
+            .expect("list_threads should work");
+        let project_of = |id: &str| {
+            threads
+                .iter()
+                .find(|thread| thread.id == id)
+                .map(|thread| thread.project_path.clone())
+        };
+
+        assert_eq!(
+            project_of("session-existing"),
+            Some(repo.to_string_lossy().to_string())
+        );
+        assert_eq!(
+            project_of("session-missing").as_deref(),
+            Some("/workspace/missing/app")
+        );
+    }
+
+    #[test]
+    fn find_existing_decoded_path_stops_once_probe_budget_is_spent() {
+        let root = test_temp_dir("decode-budget");
+        let repo = root.join("my-app");
+        fs::create_dir_all(&repo).expect("repo dir should be creatable");
+        let segments = ["my", "app"];
+
+        let mut probes_left = MAX_PROJECT_DIR_DECODE_PROBES;
+        assert_eq!(
+            find_existing_decoded_path(&root, &segments, &mut probes_left),
+            Some(repo.to_string_lossy().to_string())
+        );
+        assert!(probes_left < MAX_PROJECT_DIR_DECODE_PROBES);
+
+        let mut probes_left = 1;
+        assert_eq!(
+            find_existing_decoded_path(&root, &segments, &mut probes_left),
+            None
+        );
+        assert_eq!(probes_left, 0);
+    }
+
+    #[test]
+    fn thread_activity_collects_edited_files_per_thread() {
+        let config_dir = test_temp_dir("thread-activity").join(".claude");
+        write_lines(
+            &config_dir.join("projects/demo/session-left.jsonl"),
+            &[
+                r#"{"sessionId":"session-left","cwd":"/workspace/demo","timestamp":"1700000000000","message":{"role":"user","content":"Fix the parser"}}"#,
+                r#"{"sessionId":"session-left","cwd":"/workspace/demo","timestamp":"1700000001000","message":{"role":"assistant","content":[{"type":"tool_use","name":"Read","input":{"file_path":"/workspace/demo/README.md"}},{"type":"tool_use","name":"Edit","input":{"file_path":"/workspace/demo/src/parser.rs","old_string":"a","new_string":"b"}}]}}"#,
+                r#"{"sessionId":"session-left","cwd":"/workspace/demo","timestamp":"1700000002000","message":{"role":"user","content":[{"type":"tool_result","content":"ok"}]}}"#,
+                r#"{"sessionId":"session-left","cwd":"/workspace/demo","timestamp":"1700000003000","message":{"role":"assistant","content":[{"type":"tool_use","name":"Write","input":{"file_path":"/workspace/demo/tests/parser.rs","content":"x"}},{"type":"tool_use","name":"Edit","input":{"file_path":"/workspace/demo/src/parser.rs"}}]}}"#,
+                r#"{"sessionId":"session-left","cwd":"/workspace/demo","timestamp":"1700000009000","message":{"role":"assistant","content":[{"type":"text","text":"Parser fixed with a regression test."}]}}"#,
+            ],
+        );
+        write_lines(
+            &config_dir.join("projects/demo/session-right.jsonl"),
+            &[
+                r#"{"sessionId":"session-right","cwd":"/workspace/demo","timestamp":"1700000000000","message":{"role":"user","content":"Fix the parser"}}"#,
+                r#"{"sessionId":"session-right","cwd":"/workspace/demo","timestamp":"1700000004000","isSidechain":true,"message":{"role":"assistant","content":[{"type":"tool_use","name":"Edit","input":{"file_path":"/workspace/demo/src/ignored.rs"}}]}}"#,
+                r#"{"sessionId":"session-right","cwd":"/workspace/demo","timestamp":"1700000005000","message":{"role":"assistant","content":[{"type":"tool_use","name":"MultiEdit","input":{"file_path":"/workspace/demo/src/lexer.rs","edits":[]}},{"type":"text","text":"Fixed in the lexer instead."}]}}"#,
+            ],
+        );
+
+        let adapter = ClaudeAdapter::new().with_config_dir(&config_dir);
+        let left = adapter
+            .get_thread_activity("session-left")
+            .expect("activity should load");
+        let right = adapter
+            .get_thread_activity("session-right")
+            .expect("activity should load");
+
+        assert_eq!(
+            left.touched_files,
+            vec![
+                "/workspace/demo/src/parser.rs",
+                "/workspace/demo/tests/parser.rs"
+            ]
+        );
+        assert_eq!(left.message_count, 2);
+        assert_eq!(left.duration_ms(), Some(9_000));
+        assert_eq!(right.touched_files, vec!["/workspace/demo/src/lexer.rs"]);
+        assert_eq!(right.message_count, 2);
+        assert_eq!(right.duration_ms(), Some(5_000));
+        assert_eq!(
+            adapter
+                .get_final_assistant_message("session-left")
+                .expect("final assistant message should load")
+                .as_deref(),
+            Some("Parser fixed with a regression test.")
+        );
+        assert_eq!(
+            adapter
+                .get_final_assistant_message("session-right")
+                .expect("final assistant message should load")
+                .as_deref(),
+            Some("Fixed in the lexer instead.")
+        );
+    }
+
+    #[test]
+    fn overviews_include_runtime_state_only_when_requested() {
+        let config_dir = test_temp_dir("overview-runtime").join(".claude");
+        let now = 1_700_000_000_000;
+        write_owned_lines(
+            &config_dir.join("projects/demo/session-overview.jsonl"),
+            &[
+                format!(
+                    r#"{{"sessionId":"session-overview","cwd":"/workspace/demo","timestamp":{},"type":"user","message":{{"role":"user","content":[{{"type":"text","text":"hello"}}]}}}}"#,
+                    now - 5_000
+                ),
+                format!(
+                    r#"{{"sessionId":"session-overview","cwd":"/workspace/demo","timestamp":{},"type":"progress","data":{{"type":"agent_progress","message":{{"type":"assistant","message":{{"role":"assistant","content":[{{"type":"tool_use","name":"Bash","input":{{"command":"ls"}}}}]}}}}}}}}"#,
+                    now - 1_500
+                ),
+            ],
+        );
+        let adapter = ClaudeAdapter::new()
+            .with_config_dir(&config_dir)
+            .with_clock(FixedClock(Timestamp::from_epoch_millis(now)));
+
+        let plain = adapter
+            .list_thread_overviews(None)
+            .expect("overviews should load");
+        assert_eq!(plain[0].runtime_state, None);
+
+        let overviews = adapter
+            .list_thread_overviews_with_options(
+                None,
+                ThreadOverviewOptions {
+                    include_runtime_state: true,
+                    ..ThreadOverviewOptions::default()
+                },
+            )
+            .expect("overviews should load");
+        let standalone = adapter
+            .get_thread_runtime_state("session-overview")
+            .expect("runtime state should be readable");
+        assert_eq!(overviews[0].runtime_state.as_ref(), Some(&standalone));
+        assert_eq!(standalone.last_event_kind.as_deref(), Some("agent_tool"));
+    }
+
+    #[test]
+    fn config_summary_reflects_builder_overrides() {
+        let config_dir = test_temp_dir("config-summary").join(".claude");
+        fs::create_dir_all(&config_dir).expect("config dir should be creatable");
+        fs::write(
+            config_dir.join("settings.json"),
+            r#"{"env":{"ANTHROPIC_AUTH_TOKEN":"token-123"}}"#,
+        )
+        .expect("settings should be writable");
+
+        let summary = ClaudeAdapter::new()
+            .with_config_dir(&config_dir)
+            .with_cli_binary("missing-claude-binary-123")
+            .config_summary();
+
+        assert_eq!(summary.config_dir, config_dir);
+        assert!(summary.config_dir_exists());
+        assert_eq!(summary.sessions_dir, config_dir.join("projects"));
+        assert!(!summary.sessions_dir_exists());
+        assert_eq!(summary.cli_binary, "missing-claude-binary-123");
+        assert_eq!(summary.cli_binary_path, None);
+        assert_eq!(summary.auth_source.as_deref(), Some("ANTHROPIC_AUTH_TOKEN"));
+        assert!(!format!("{summary:?}").contains("token-123"));
+    }
+
+    #[test]
+    fn resolve_claude_config_dir_prefers_env_then_home() {
+        let home = Some(PathBuf::from("/home/tester"));
+
+        let from_env = resolve_claude_config_dir(
+            |key| (key == CLAUDE_CONFIG_DIR_ENV).then(|| " /env/.claude ".to_string()),
+            home.clone(),
+        );
+        assert_eq!(from_env, PathBuf::from("/env/.claude"));
+
+        let blank_env = resolve_claude_config_dir(|_| Some("  ".to_string()), home.clone());
+        assert_eq!(blank_env, PathBuf::from("/home/tester/.claude"));
+
+        assert_eq!(
+            resolve_claude_config_dir(|_| None, None),
+            PathBuf::from(".claude")
+        );
+    }
+
+    #[test]
+    fn resolve_claude_binary_prefers_env_then_search_dirs() {
+        let search_dir = test_temp_dir("claude-binary-env");
+        let binary = search_dir.join("claude-wrapper");
+        fs::write(&binary, "").expect("binary should be writable");
+        let binary_env = binary.display().to_string();
+
+        let from_env = resolve_claude_binary(
+            |key| (key == CLAUDE_BINARY_ENV).then(|| binary_env.clone()),
+            &[],
+        );
+        assert_eq!(PathBuf::from(&from_env), binary);
+        assert_eq!(locate_cli_binary(&from_env, &[]), Some(binary));
+
+        let blank_env = resolve_claude_binary(|_| Some(" ".to_string()), &[]);
+        assert_eq!(blank_env, resolve_cli_binary("claude", &[]));
+    }
+
+    #[test]
+    fn overviews_include_message_count_only_when_requested() {
+        let config_dir = test_temp_dir("overview-message-count").join(".claude");
+        write_lines(
+            &config_dir.join("projects/demo/session-count.jsonl"),
+            &[
+                r#"{"sessionId":"session-count","cwd":"/workspace/demo","timestamp":"1700000000000","message":{"role":"user","content":"Count these"}}"#,
+                r#"{"sessionId":"session-count","cwd":"/workspace/demo","timestamp":"1700000001000","isMeta":true,"message":{"role":"user","content":"Caveat: meta"}}"#,
+                r#"{"sessionId":"session-count","cwd":"/workspace/demo","timestamp":"1700000002000","message":{"role":"assistant","content":[{"type":"text","text":"Counting."}]}}"#,
+                r#"{"sessionId":"session-count","cwd":"/workspace/demo","timestamp":"1700000003000","message":{"role":"user","content":[{"type":"tool_result","content":"ok"}]}}"#,
+                r#"{"sessionId":"session-count","cwd":"/workspace/demo","timestamp":"1700000004000","isSidechain":true,"message":{"role":"assistant","content":[{"type":"text","text":"Subagent"}]}}"#,
+                r#"{"type":"summary","summary":"Counting messages","leafUuid":"leaf-1"}"#,
+                r#"{"sessionId":"session-count","cwd":"/workspace/demo","timestamp":"1700000005000","message":{"role":"assistant","content":[{"type":"text","text":"Done."}]}}"#,
+            ],
+        );
+        let adapter = ClaudeAdapter::new().with_config_dir(&config_dir);
+
+        let plain = adapter
+            .list_thread_overviews(None)
+            .expect("overviews should load");
+        assert_eq!(plain[0].message_count, None);
+
+        let overviews = adapter
+            .list_thread_overviews_with_options(
+                None,
+                ThreadOverviewOptions {
+                    include_message_count: true,
+                    ..ThreadOverviewOptions::default()
+                },
+            )
+            .expect("overviews should load");
+        let activity = adapter
+            .get_thread_activity("session-count")
+            .expect("activity should load");
+        assert_eq!(overviews[0].message_count, Some(activity.message_count));
+        assert_eq!(activity.message_count, 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn list_threads_keeps_sessions_with_non_utf8_file_names() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let config_dir = test_temp_dir("non-utf8-name").join(".claude");
+        let session_path = config_dir
+            .join("projects/demo")
+            .join(OsStr::from_bytes(b"session-\xff.jsonl"));
+        write_lines(
+            &session_path,
+            &[
+                r#"{"cwd":"/workspace/demo","timestamp":"1700000000000","message":{"role":"user","content":"Exotic file name"}}"#,
+            ],
+        );
+        let adapter = ClaudeAdapter::new().with_config_dir(&config_dir);
+
+        let threads = adapter.list_threads(None).expect("threads should load");
+
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].id, "session-\u{fffd}");
+        assert_eq!(threads[0].title, "Exotic file name");
+        assert!(adapter.thread_exists("session-\u{fffd}"));
+    }
 }
@@ -1,20 +1,29 @@
 use provider_contract::{
-    ProviderAdapter, ProviderError, ProviderErrorCode, ProviderHealthCheckRequest,
-    ProviderHealthCheckResult, ProviderHealthStatus, ProviderId, ProviderResult,
-    ResumeThreadRequest, ResumeThreadResult, ThreadSummary,
+    build_thread_tags, collect_files_with_extension, default_cli_search_dirs, default_home_dir,
+    default_max_session_file_bytes, default_scan_timeout, file_stem_lossy, find_last_line_map,
+    group_project_path, locate_cli_binary, parse_cli_version_output, partition_oversized_files,
+    redact_secrets, resolve_cli_binary, resolve_project_path, verify_session_file, Clock,
+    OversizedFile, ProviderAdapter, ProviderConfigSummary, ProviderError, ProviderErrorCode,
+    ProviderHealthCheckRequest, ProviderHealthCheckResult, ProviderHealthStatus, ProviderId,
+    ProviderResult, ResumeThreadRequest, ResumeThreadResult, ScanDeadline, ThreadActivity,
+    ThreadEvent, ThreadOverviewOptions, ThreadScan, ThreadSummary, Timestamp,
 };
 use serde_json::Value;
-use std::cmp::{Ordering, Reverse};
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::time::{SystemTime, UNIX_EPOCH};
-use time::format_description::well_known::Rfc3339;
-use time::OffsetDateTime;
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
 
 const CODEX_HOME_DIR_ENV: &str = "AGENTDOCK_CODEX_HOME_DIR";
+const CODEX_ARCHIVED_SESSIONS_DIR: &str = "archived_sessions";
+const CODEX_BINARY_ENV: &str = "AGENTDOCK_CODEX_BIN";
+// Codex's own override for where config, sessions and history live.
+const CODEX_NATIVE_HOME_ENV: &str = "CODEX_HOME";
+const CODEX_API_KEY_ENV: &str = "OPENAI_API_KEY";
 const CODEX_AGENT_ACTIVITY_WINDOW_MS: i64 = 120_000;
 
 #[derive(Debug, Clone)]
@@ -22,6 +31,19 @@ struct ThreadRecord {
     summary: ThreadSummary,
     source_path: PathBuf,
     sort_key: i64,
+    has_messages: bool,
+    /// `session_meta` carried a non-blank `cwd`, even one that resolves to `.`.
+    has_recorded_cwd: bool,
+    launch_settings: CodexLaunchSettings,
+}
+
+/// Model and policy flags the session last ran with, read from `turn_context` records
+/// so a resumed session keeps the same configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct CodexLaunchSettings {
+    model: Option<String>,
+    sandbox_mode: Option<String>,
+    approval_policy: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -35,6 +57,10 @@ struct SessionIdStats {
 pub struct CodexThreadOverview {
     pub summary: ThreadSummary,
     pub last_message_preview: Option<String>,
+    /// Only set when requested through `ThreadOverviewOptions::include_runtime_state`.
+    pub runtime_state: Option<CodexThreadRuntimeState>,
+    /// Only set when requested through `ThreadOverviewOptions::include_message_count`.
+    pub message_count: Option<usize>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -44,9 +70,25 @@ pub struct CodexThreadRuntimeState {
     pub last_event_at_ms: Option<i64>,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CodexThreadUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub total_tokens: u64,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct CodexAdapter {
     home_dir_override: Option<PathBuf>,
+    cli_binary_override: Option<String>,
+    cli_search_dirs: Option<Vec<PathBuf>>,
+    clock: Option<Arc<dyn Clock>>,
+    max_session_file_bytes: Option<u64>,
+    project_path_base: Option<PathBuf>,
+    group_by_git_root: bool,
+    hide_empty_threads: bool,
+    redaction_disabled: bool,
+    scan_timeout: Option<Duration>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -55,6 +97,7 @@ enum CodexSemanticEventKind {
     AgentReasoning,
     AgentTool,
     AgentMessage,
+    InstructionMessage,
     TurnAborted,
 }
 
@@ -65,6 +108,7 @@ impl CodexSemanticEventKind {
             CodexSemanticEventKind::AgentReasoning => "agent_reasoning",
             CodexSemanticEventKind::AgentTool => "agent_tool",
             CodexSemanticEventKind::AgentMessage => "agent_message",
+            CodexSemanticEventKind::InstructionMessage => "instruction_message",
             CodexSemanticEventKind::TurnAborted => "turn_aborted",
         }
     }
@@ -80,32 +124,183 @@ impl CodexAdapter {
         self
     }
 
+    pub fn with_cli_binary<S: Into<String>>(mut self, cli_binary: S) -> Self {
+        self.cli_binary_override = Some(cli_binary.into());
+        self
+    }
+
+    /// Install directories probed when the CLI is not on PATH. Defaults to
+    /// `default_cli_search_dirs()`.
+    pub fn with_cli_search_dirs<I, P>(mut self, dirs: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.cli_search_dirs = Some(dirs.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn with_project_path_base<P: Into<PathBuf>>(mut self, base: P) -> Self {
+        self.project_path_base = Some(base.into());
+        self
+    }
+
+    /// Group threads under the enclosing git repository root instead of the recorded cwd.
+    pub fn with_git_root_grouping(mut self, enabled: bool) -> Self {
+        self.group_by_git_root = enabled;
+        self
+    }
+
+    /// Omit never-used sessions from `list_threads`: no parseable messages and no
+    /// recorded project directory.
+    pub fn with_hide_empty_threads(mut self, enabled: bool) -> Self {
+        self.hide_empty_threads = enabled;
+        self
+    }
+
+    /// Run message previews through `redact_secrets`. On by default.
+    pub fn with_redaction(mut self, enabled: bool) -> Self {
+        self.redaction_disabled = !enabled;
+        self
+    }
+
+    /// Overrides the `Clock`; see there.
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Some(Arc::new(clock));
+        self
+    }
+
+    /// Session files larger than this are skipped instead of read. Defaults to
+    /// `default_max_session_file_bytes()`.
+    pub fn with_max_session_file_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_session_file_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Stops session scans after `timeout`, keeping the threads read so far. Defaults to
+    /// `default_scan_timeout()`.
+    pub fn with_scan_timeout(mut self, timeout: Duration) -> Self {
+        self.scan_timeout = Some(timeout);
+        self
+    }
+
     pub fn get_thread_runtime_state(
         &self,
         thread_id: &str,
     ) -> ProviderResult<CodexThreadRuntimeState> {
         let thread_record = self.find_thread_record(thread_id)?;
-        Ok(load_thread_runtime_state(&thread_record.source_path))
+        Ok(load_thread_runtime_state(
+            &thread_record.source_path,
+            self.now_ms(),
+        ))
+    }
+
+    pub fn get_thread_usage(&self, thread_id: &str) -> ProviderResult<CodexThreadUsage> {
+        let thread_record = self.find_thread_record(thread_id)?;
+        Ok(load_thread_usage(&thread_record.source_path))
+    }
+
+    /// Checks that a session file is still on disk by reading only up to each file's
+    /// `session_meta` record.
+    pub fn thread_exists(&self, thread_id: &str) -> bool {
+        find_rollout_file(&self.codex_sessions_dir(), thread_id).is_some()
+    }
+
+    /// Session files left out of thread scans by the size cap.
+    pub fn oversized_session_files(&self) -> Vec<OversizedFile> {
+        self.collect_session_files(&self.scan_deadline()).1
+    }
+
+    /// Last `limit` semantic events of the session, oldest first.
+    pub fn get_thread_event_log(
+        &self,
+        thread_id: &str,
+        limit: usize,
+    ) -> ProviderResult<Vec<ThreadEvent>> {
+        let thread_record = self.find_thread_record(thread_id)?;
+        Ok(load_thread_event_log(&thread_record.source_path, limit))
+    }
+
+    pub fn get_thread_activity(&self, thread_id: &str) -> ProviderResult<ThreadActivity> {
+        let thread_record = self.find_thread_record(thread_id)?;
+        Ok(load_thread_activity(&thread_record.source_path))
+    }
+
+    pub fn get_latest_message_preview(&self, thread_id: &str) -> ProviderResult<Option<String>> {
+        let thread_record = self.find_thread_record(thread_id)?;
+        Ok(self.redact(build_latest_message_preview(&thread_record.source_path)))
+    }
+
+    /// Text of the last assistant reply, skipping trailing tool calls and results. Not
+    /// truncated, unlike the latest message preview.
+    pub fn get_final_assistant_message(&self, thread_id: &str) -> ProviderResult<Option<String>> {
+        let thread_record = self.find_thread_record(thread_id)?;
+        Ok(self.redact(find_last_line_map(
+            &thread_record.source_path,
+            extract_line_assistant_text,
+        )))
+    }
+
+    /// Resolved Codex home, sessions dir, CLI binary and credential source.
+    pub fn config_summary(&self) -> ProviderConfigSummary {
+        let cli_binary = self.codex_binary();
+        ProviderConfigSummary {
+            config_dir: self.codex_home_dir(),
+            sessions_dir: self.codex_sessions_dir(),
+            cli_binary_path: locate_cli_binary(&cli_binary, &self.cli_search_dirs()),
+            cli_binary,
+            auth_source: self.codex_auth_source(),
+        }
     }
 
     pub fn list_thread_overviews(
         &self,
         project_path: Option<&str>,
     ) -> ProviderResult<Vec<CodexThreadOverview>> {
-        let mut records = self.scan_thread_records();
+        self.list_thread_overviews_with_options(project_path, ThreadOverviewOptions::default())
+    }
+
+    pub fn list_thread_overviews_with_options(
+        &self,
+        project_path: Option<&str>,
+        options: ThreadOverviewOptions,
+    ) -> ProviderResult<Vec<CodexThreadOverview>> {
+        Ok(self.scan_thread_overviews(project_path, options)?.threads)
+    }
+
+    /// Like `list_thread_overviews_with_options`, but reports whether the scan timeout cut
+    /// the list short.
+    pub fn scan_thread_overviews(
+        &self,
+        project_path: Option<&str>,
+        options: ThreadOverviewOptions,
+    ) -> ProviderResult<ThreadScan<CodexThreadOverview>> {
+        let now_ms = self.now_ms();
+        let deadline = self.scan_deadline();
+        let mut records = self.scan_thread_records_until(&deadline);
 
         if let Some(filter) = project_path {
             records.retain(|record| record.summary.project_path.starts_with(filter));
         }
 
-        records.sort_by_key(|record| Reverse(record.sort_key));
-        Ok(records
+        sort_thread_records(&mut records);
+        let threads = records
             .into_iter()
             .map(|record| CodexThreadOverview {
-                last_message_preview: build_last_message_preview(&record.source_path),
+                last_message_preview: self.redact(build_last_message_preview(&record.source_path)),
+                runtime_state: options
+                    .include_runtime_state
+                    .then(|| load_thread_runtime_state(&record.source_path, now_ms)),
+                message_count: options
+                    .include_message_count
+                    .then(|| count_thread_messages(&record.source_path)),
                 summary: record.summary,
             })
-            .collect())
+            .collect();
+        Ok(ThreadScan {
+            threads,
+            timed_out: deadline.timed_out(),
+        })
     }
 
     fn codex_home_dir(&self) -> PathBuf {
@@ -113,33 +308,40 @@ impl CodexAdapter {
             return path.clone();
         }
 
-        if let Ok(path) = std::env::var(CODEX_HOME_DIR_ENV) {
-            let trimmed = path.trim();
-            if !trimmed.is_empty() {
-                return PathBuf::from(trimmed);
-            }
-        }
-
-        if let Some(home) = default_home_dir() {
-            return home.join(".codex");
-        }
-
-        PathBuf::from(".codex")
+        resolve_codex_home_dir(|key| std::env::var(key).ok(), default_home_dir())
     }
 
     fn codex_sessions_dir(&self) -> PathBuf {
         self.codex_home_dir().join("sessions")
     }
 
+    fn codex_auth_source(&self) -> Option<String> {
+        if self.codex_home_dir().join("auth.json").is_file() {
+            return Some("auth.json".to_string());
+        }
+        std::env::var(CODEX_API_KEY_ENV)
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .map(|_| CODEX_API_KEY_ENV.to_string())
+    }
+
     fn scan_thread_records(&self) -> Vec<ThreadRecord> {
+        self.scan_thread_records_until(&self.scan_deadline())
+    }
+
+    fn scan_thread_records_until(&self, deadline: &ScanDeadline) -> Vec<ThreadRecord> {
         let codex_home_dir = self.codex_home_dir();
-        let mut files = Vec::new();
-        collect_jsonl_files(&codex_home_dir.join("sessions"), &mut files);
+        let (files, _) = self.collect_session_files(deadline);
         let official_titles = load_codex_thread_titles(&codex_home_dir);
 
         let mut records = Vec::new();
         for path in files {
-            if let Some(record) = parse_thread_file(&path, &official_titles) {
+            if let Some(record) = parse_thread_file(
+                &path,
+                &official_titles,
+                self.project_path_base.as_deref(),
+                self.group_by_git_root,
+            ) {
                 records.push(record);
             }
         }
@@ -150,6 +352,35 @@ impl CodexAdapter {
         dedupe_thread_records(records)
     }
 
+    fn collect_session_files(&self, deadline: &ScanDeadline) -> (Vec<PathBuf>, Vec<OversizedFile>) {
+        let files = collect_files_with_extension(&self.codex_sessions_dir(), "jsonl", deadline);
+        let max_bytes = self
+            .max_session_file_bytes
+            .unwrap_or_else(default_max_session_file_bytes);
+        partition_oversized_files(files, max_bytes)
+    }
+
+    /// Command resuming the session from the requested project path, or else from the
+    /// directory it was recorded in rather than a grouped `project_path`.
+    fn resume_command(
+        &self,
+        thread_record: &ThreadRecord,
+        request: &ResumeThreadRequest,
+    ) -> String {
+        let project_path = request
+            .project_path
+            .as_deref()
+            .filter(|path| !path.trim().is_empty())
+            .or_else(|| thread_record.summary.session_cwd());
+        build_resume_command(
+            &self.codex_binary(),
+            &request.thread_id,
+            project_path,
+            request.context_summary.as_deref(),
+            &thread_record.launch_settings,
+        )
+    }
+
     fn find_thread_record(&self, thread_id: &str) -> ProviderResult<ThreadRecord> {
         self.scan_thread_records()
             .into_iter()
@@ -163,20 +394,41 @@ impl CodexAdapter {
             })
     }
 
-    fn ensure_cli_reachable(&self) -> ProviderResult<()> {
-        match Command::new("codex").arg("--version").output() {
-            Ok(_) => Ok(()),
-            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Err(provider_error(
-                ProviderErrorCode::UpstreamUnavailable,
-                "Codex CLI not found in PATH: codex".to_string(),
-                false,
-            )),
-            Err(error) => Err(provider_error(
-                ProviderErrorCode::UpstreamUnavailable,
-                format!("Failed to execute Codex CLI (codex): {error}"),
-                true,
-            )),
+    fn codex_binary(&self) -> String {
+        if let Some(binary) = &self.cli_binary_override {
+            return binary.clone();
+        }
+        if let Ok(binary) = std::env::var(CODEX_BINARY_ENV) {
+            let trimmed = binary.trim();
+            if !trimmed.is_empty() {
+                return trimmed.to_string();
+            }
+        }
+        resolve_cli_binary("codex", &self.cli_search_dirs())
+    }
+
+    fn cli_search_dirs(&self) -> Vec<PathBuf> {
+        self.cli_search_dirs
+            .clone()
+            .unwrap_or_else(default_cli_search_dirs)
+    }
+
+    fn scan_deadline(&self) -> ScanDeadline {
+        ScanDeadline::new(self.scan_timeout.or_else(default_scan_timeout))
+    }
+
+    fn now_ms(&self) -> i64 {
+        self.clock
+            .as_ref()
+            .map_or_else(Timestamp::now, |clock| clock.now())
+            .as_millis()
+    }
+
+    fn redact(&self, text: Option<String>) -> Option<String> {
+        if self.redaction_disabled {
+            return text;
         }
+        text.map(|text| redact_secrets(&text).into_owned())
     }
 }
 
@@ -189,26 +441,19 @@ impl ProviderAdapter for CodexAdapter {
         &self,
         request: ProviderHealthCheckRequest,
     ) -> ProviderResult<ProviderHealthCheckResult> {
-        let checked_at = now_unix_millis().to_string();
+        let checked_at = Timestamp::now();
 
-        match Command::new("codex").arg("--version").output() {
-            Ok(_) => {}
-            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+        let version = match self.provider_version() {
+            Ok(version) => version,
+            Err(error) => {
                 return Ok(ProviderHealthCheckResult {
                     provider_id: ProviderId::Codex,
                     status: ProviderHealthStatus::Offline,
                     checked_at,
-                    message: Some("Codex CLI not found in PATH: codex".to_string()),
+                    message: Some(error.message),
                 });
             }
-            Err(error) => {
-                return Err(provider_error(
-                    ProviderErrorCode::UpstreamUnavailable,
-                    format!("Failed to execute Codex CLI (codex): {error}"),
-                    true,
-                ));
-            }
-        }
+        };
 
         let sessions_dir = self.codex_sessions_dir();
         if !sessions_dir.exists() {
@@ -229,43 +474,69 @@ impl ProviderAdapter for CodexAdapter {
             status: ProviderHealthStatus::Healthy,
             checked_at,
             message: Some(format!(
-                "Codex CLI reachable, sessions directory loaded ({})",
+                "Codex CLI reachable{}, sessions directory loaded ({})",
+                version
+                    .map(|version| format!(" (v{version})"))
+                    .unwrap_or_default(),
                 request.profile_name
             )),
         })
     }
 
+    fn provider_version(&self) -> ProviderResult<Option<String>> {
+        let binary = self.codex_binary();
+        match Command::new(&binary).arg("--version").output() {
+            Ok(output) if !output.status.success() => Err(ProviderError::from_cli_failure(
+                format!("Codex CLI ({binary}) exited with {}", output.status),
+                &String::from_utf8_lossy(&output.stderr),
+            )),
+            Ok(output) => Ok(parse_cli_version_output(&output)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Err(provider_error(
+                ProviderErrorCode::UpstreamUnavailable,
+                format!("Codex CLI not found in PATH: {binary}"),
+                false,
+            )),
+            Err(error) => Err(provider_error(
+                ProviderErrorCode::UpstreamUnavailable,
+                format!("Failed to execute Codex CLI ({binary}): {error}"),
+                true,
+            )),
+        }
+    }
+
     fn list_threads(&self, project_path: Option<&str>) -> ProviderResult<Vec<ThreadSummary>> {
         let mut records = self.scan_thread_records();
 
         if let Some(filter) = project_path {
             records.retain(|record| record.summary.project_path.starts_with(filter));
         }
+        if self.hide_empty_threads {
+            records.retain(|record| !is_empty_thread_record(record));
+        }
 
-        records.sort_by_key(|record| Reverse(record.sort_key));
+        sort_thread_records(&mut records);
         Ok(records.into_iter().map(|record| record.summary).collect())
     }
 
     fn resume_thread(&self, request: ResumeThreadRequest) -> ProviderResult<ResumeThreadResult> {
-        self.ensure_cli_reachable()?;
-        let thread_record = self.find_thread_record(&request.thread_id)?;
-
-        let project_path = request
-            .project_path
-            .clone()
-            .filter(|path| !path.trim().is_empty())
-            .or_else(|| {
-                if thread_record.summary.project_path == "." {
-                    None
-                } else {
-                    Some(thread_record.summary.project_path.clone())
-                }
+        let session_path = find_rollout_file(&self.codex_sessions_dir(), &request.thread_id);
+        if let Err(reason) = verify_session_file("Codex", session_path.as_deref()) {
+            // `codex` moves archived rollouts out of `sessions/`; it cannot resume them.
+            let archived_dir = self.codex_home_dir().join(CODEX_ARCHIVED_SESSIONS_DIR);
+            let reason = match find_rollout_file(&archived_dir, &request.thread_id) {
+                Some(path) => format!("Codex session is archived: {}", path.display()),
+                None => reason,
+            };
+            return Ok(ResumeThreadResult {
+                thread_id: request.thread_id,
+                resumed: false,
+                message: Some(reason),
             });
-
-        let mut command = format!("codex resume {}", shell_quote(&request.thread_id));
-        if let Some(path) = project_path {
-            command = prepend_workdir_to_command(command, &path);
         }
+        let thread_record = self.find_thread_record(&request.thread_id)?;
+        self.provider_version()?;
+
+        let command = self.resume_command(&thread_record, &request);
 
         Ok(ResumeThreadResult {
             thread_id: request.thread_id,
@@ -277,6 +548,33 @@ impl ProviderAdapter for CodexAdapter {
     }
 }
 
+fn build_resume_command(
+    binary: &str,
+    thread_id: &str,
+    project_path: Option<&str>,
+    context_summary: Option<&str>,
+    launch_settings: &CodexLaunchSettings,
+) -> String {
+    let mut command = format!("{binary} resume");
+    if let Some(model) = &launch_settings.model {
+        command = format!("{command} --model {}", shell_quote(model));
+    }
+    if let Some(sandbox_mode) = &launch_settings.sandbox_mode {
+        command = format!("{command} --sandbox {sandbox_mode}");
+    }
+    if let Some(approval_policy) = &launch_settings.approval_policy {
+        command = format!("{command} --ask-for-approval {approval_policy}");
+    }
+    command = format!("{command} {}", shell_quote(thread_id));
+    if let Some(summary) = context_summary.and_then(non_empty_trimmed) {
+        command = format!("{command} {}", shell_quote(summary));
+    }
+    match project_path {
+        Some(path) => prepend_workdir_to_command(command, path),
+        None => command,
+    }
+}
+
 fn provider_error(code: ProviderErrorCode, message: String, retryable: bool) -> ProviderError {
     ProviderError {
         code,
@@ -285,27 +583,21 @@ fn provider_error(code: ProviderErrorCode, message: String, retryable: bool) ->
     }
 }
 
-fn collect_jsonl_files(root: &Path, output: &mut Vec<PathBuf>) {
-    if !root.exists() {
-        return;
-    }
-
-    let entries = match fs::read_dir(root) {
-        Ok(entries) => entries,
-        Err(_) => return,
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.is_dir() {
-            collect_jsonl_files(&path, output);
-            continue;
-        }
+/// Rollout under `root` whose `session_meta` names `thread_id`.
+fn find_rollout_file(root: &Path, thread_id: &str) -> Option<PathBuf> {
+    let mut files = Vec::new();
+    collect_jsonl_files(root, &mut files);
+    files
+        .into_iter()
+        .find(|path| first_session_meta_id(path).as_deref() == Some(thread_id))
+}
 
-        if path.extension().and_then(|value| value.to_str()) == Some("jsonl") {
-            output.push(path);
-        }
-    }
+fn collect_jsonl_files(root: &Path, output: &mut Vec<PathBuf>) {
+    output.extend(collect_files_with_extension(
+        root,
+        "jsonl",
+        &ScanDeadline::unbounded(),
+    ));
 }
 
 fn load_codex_thread_titles(codex_home_dir: &Path) -> HashMap<String, String> {
@@ -345,6 +637,8 @@ fn load_codex_thread_titles(codex_home_dir: &Path) -> HashMap<String, String> {
 fn parse_thread_file(
     path: &Path,
     official_titles: &HashMap<String, String>,
+    project_path_base: Option<&Path>,
+    group_by_git_root: bool,
 ) -> Option<ThreadRecord> {
     let file = File::open(path).ok()?;
     let reader = BufReader::new(file);
@@ -353,13 +647,24 @@ fn parse_thread_file(
     let mut project_path: Option<String> = None;
     let mut is_subagent_session = false;
     let mut first_user_title: Option<String> = None;
-    let mut last_active_at: Option<String> = None;
-    let mut sort_key = file_last_modified_ms(path).unwrap_or(0);
+    let mut last_active_at: Option<Timestamp> = None;
+    let modified_ms = file_last_modified_ms(path);
+    let mut sort_key = modified_ms.unwrap_or(0);
+    let mut has_messages = false;
+    let mut launch_settings = CodexLaunchSettings::default();
+    // Codex may be mid-write, leaving a truncated JSON object as the last line.
+    let mut ends_with_partial_line = false;
 
     for (line_index, line) in reader.lines().map_while(Result::ok).enumerate() {
         let parsed: Value = match serde_json::from_str(&line) {
-            Ok(value) => value,
-            Err(_) => continue,
+            Ok(value) => {
+                ends_with_partial_line = false;
+                value
+            }
+            Err(_) => {
+                ends_with_partial_line |= !line.trim().is_empty();
+                continue;
+            }
         };
 
         let timestamp_ms = parse_timestamp_ms(parsed.get("timestamp")).unwrap_or(0);
@@ -396,6 +701,16 @@ fn parse_thread_file(
             }
         }
 
+        if parsed.get("type").and_then(Value::as_str) == Some("turn_context") {
+            if let Some(payload) = parsed.get("payload") {
+                update_launch_settings(&mut launch_settings, payload);
+            }
+        }
+
+        if is_conversation_message_line(&parsed) {
+            has_messages = true;
+        }
+
         if first_user_title.is_none() {
             let record_type = parsed.get("type").and_then(Value::as_str);
             match record_type {
@@ -428,7 +743,7 @@ fn parse_thread_file(
         }
 
         if timestamp_ms > 0 {
-            last_active_at = Some(timestamp_ms.to_string());
+            last_active_at = Some(Timestamp::from_epoch_millis(timestamp_ms));
             sort_key = sort_key.max(timestamp_ms);
         }
     }
@@ -437,8 +752,21 @@ fn parse_thread_file(
     if is_subagent_session {
         return None;
     }
+    if ends_with_partial_line {
+        // The in-progress record has no readable timestamp; the write itself is the
+        // latest activity.
+        if let Some(modified_ms) = modified_ms {
+            if last_active_at.map(Timestamp::as_millis).unwrap_or(0) < modified_ms {
+                last_active_at = Some(Timestamp::from_epoch_millis(modified_ms));
+            }
+        }
+    }
 
-    let project_path = project_path.unwrap_or_else(|| ".".to_string());
+    let has_recorded_cwd = project_path
+        .as_deref()
+        .is_some_and(|cwd| !cwd.trim().is_empty());
+    let project_path = resolve_project_path(project_path, project_path_base);
+    let (project_path, original_cwd) = group_project_path(project_path, group_by_git_root);
     let title = official_titles
         .get(&session_id)
         .and_then(|title| non_empty_trimmed(title))
@@ -447,30 +775,75 @@ fn parse_thread_file(
         .or_else(|| path_basename(&project_path).map(ToString::to_string))
         .unwrap_or_else(|| format!("Codex session {}", truncate_text(&session_id, 8)));
 
+    let tags = build_thread_tags(ProviderId::Codex.as_str(), &project_path);
     let summary = ThreadSummary {
         id: session_id,
         provider_id: ProviderId::Codex,
         account_id: None,
         project_path,
+        original_cwd,
+        model: launch_settings.model.clone(),
+        provider_backend: None,
         title,
-        tags: vec!["codex".to_string()],
-        last_active_at: last_active_at.unwrap_or_else(|| now_unix_millis().to_string()),
+        tags,
+        last_active_at: last_active_at.unwrap_or_else(Timestamp::now),
     };
 
     Some(ThreadRecord {
         summary,
         source_path: path.to_path_buf(),
         sort_key,
+        has_messages,
+        has_recorded_cwd,
+        launch_settings,
     })
 }
 
+/// Later `turn_context` records win, matching the settings of the most recent turn.
+/// Sandbox and approval values are only kept when they are flags the CLI accepts.
+fn update_launch_settings(settings: &mut CodexLaunchSettings, payload: &Value) {
+    if let Some(model) = payload
+        .get("model")
+        .and_then(Value::as_str)
+        .and_then(non_empty_trimmed)
+    {
+        settings.model = Some(model.to_string());
+    }
+
+    let sandbox_policy = payload.get("sandbox_policy");
+    let sandbox_mode = sandbox_policy
+        .and_then(Value::as_str)
+        .or_else(|| {
+            sandbox_policy
+                .and_then(|policy| policy.get("mode").or_else(|| policy.get("type")))
+                .and_then(Value::as_str)
+        })
+        .map(str::trim);
+    if let Some(mode) = sandbox_mode.filter(|mode| {
+        matches!(
+            *mode,
+            "read-only" | "workspace-write" | "danger-full-access"
+        )
+    }) {
+        settings.sandbox_mode = Some(mode.to_string());
+    }
+
+    if let Some(policy) = payload
+        .get("approval_policy")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|policy| matches!(*policy, "untrusted" | "on-failure" | "on-request" | "never"))
+    {
+        settings.approval_policy = Some(policy.to_string());
+    }
+}
+
 fn resolve_canonical_session_id(
     path: &Path,
     session_id_stats: &HashMap<String, SessionIdStats>,
 ) -> Option<String> {
-    let file_stem = path
-        .file_stem()
-        .and_then(|stem| stem.to_str())
+    let file_stem = file_stem_lossy(path)
+        .as_deref()
         .and_then(non_empty_trimmed)
         .map(ToString::to_string);
 
@@ -533,6 +906,32 @@ fn should_replace_thread_record(existing: &ThreadRecord, candidate: &ThreadRecor
     candidate_path < existing_path
 }
 
+/// User or assistant turns; session metadata and developer instructions do not count.
+fn is_conversation_message_line(parsed: &Value) -> bool {
+    let payload = match parsed.get("payload") {
+        Some(payload) => payload,
+        None => return false,
+    };
+    match parsed.get("type").and_then(Value::as_str) {
+        Some("response_item") => {
+            payload.get("type").and_then(Value::as_str) == Some("message")
+                && matches!(
+                    payload.get("role").and_then(Value::as_str),
+                    Some("user" | "assistant")
+                )
+        }
+        Some("event_msg") => matches!(
+            payload.get("type").and_then(Value::as_str),
+            Some("user_message" | "agent_message")
+        ),
+        _ => false,
+    }
+}
+
+fn is_empty_thread_record(record: &ThreadRecord) -> bool {
+    !record.has_messages && !record.has_recorded_cwd
+}
+
 fn sort_thread_records(records: &mut [ThreadRecord]) {
     records.sort_by(|left, right| {
         right
@@ -544,41 +943,98 @@ fn sort_thread_records(records: &mut [ThreadRecord]) {
 }
 
 fn parse_timestamp_ms(value: Option<&Value>) -> Option<i64> {
-    let value = value?;
-    match value {
-        Value::Number(number) => {
-            let raw = number.as_i64()?;
-            Some(normalize_epoch(raw))
-        }
+    let timestamp = match value? {
+        Value::Number(number) => number.as_i64().map(Timestamp::from_epoch),
         Value::String(raw) => {
             let trimmed = raw.trim();
             if trimmed.is_empty() {
                 return None;
             }
             if let Ok(numeric) = trimmed.parse::<i64>() {
-                return Some(normalize_epoch(numeric));
+                return Some(Timestamp::from_epoch(numeric).as_millis());
             }
-            parse_rfc3339_timestamp_ms(trimmed)
+            Timestamp::from_rfc3339(trimmed)
         }
         _ => None,
+    };
+    timestamp.map(Timestamp::as_millis)
+}
+
+/// `ThreadEvent::timestamp_raw` for a `timestamp` field.
+fn raw_timestamp(value: Option<&Value>) -> Option<String> {
+    value?
+        .as_str()
+        .map(str::trim)
+        .filter(|raw| !raw.is_empty())
+        .map(ToString::to_string)
+}
+
+fn load_thread_usage(path: &Path) -> CodexThreadUsage {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(_) => return CodexThreadUsage::default(),
+    };
+    let reader = BufReader::new(file);
+    let mut usage = CodexThreadUsage::default();
+
+    for line in reader.lines().map_while(Result::ok) {
+        let parsed: Value = match serde_json::from_str(&line) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+        if parsed.get("type").and_then(Value::as_str) != Some("event_msg") {
+            continue;
+        }
+        let payload = match parsed.get("payload") {
+            Some(payload) => payload,
+            None => continue,
+        };
+        if payload.get("type").and_then(Value::as_str) != Some("token_count") {
+            continue;
+        }
+
+        let info = payload.get("info").unwrap_or(payload);
+        // `total_token_usage` is cumulative, so a re-emitted event must not be added twice.
+        if let Some(total_usage) = info.get("total_token_usage").and_then(read_token_usage) {
+            usage = total_usage;
+        } else if let Some(turn_usage) = extract_turn_token_usage(info) {
+            usage.input_tokens += turn_usage.input_tokens;
+            usage.output_tokens += turn_usage.output_tokens;
+            usage.total_tokens += turn_usage.total_tokens;
+        }
     }
+
+    usage
 }
 
-fn parse_rfc3339_timestamp_ms(value: &str) -> Option<i64> {
-    let parsed = OffsetDateTime::parse(value, &Rfc3339).ok()?;
-    let nanos = parsed.unix_timestamp_nanos();
-    Some((nanos / 1_000_000) as i64)
+/// Read the per-turn usage of a token_count event without a running total
+/// (`info.last_token_usage`, or inline counts).
+fn extract_turn_token_usage(info: &Value) -> Option<CodexThreadUsage> {
+    let usage = info
+        .get("last_token_usage")
+        .filter(|value| value.is_object())
+        .unwrap_or(info);
+    read_token_usage(usage)
 }
 
-fn normalize_epoch(raw: i64) -> i64 {
-    if raw.abs() < 1_000_000_000_000 {
-        raw * 1000
-    } else {
-        raw
+fn read_token_usage(usage: &Value) -> Option<CodexThreadUsage> {
+    let read_count = |key: &str| usage.get(key).and_then(Value::as_u64);
+    let input_tokens = read_count("input_tokens");
+    let output_tokens = read_count("output_tokens");
+    if input_tokens.is_none() && output_tokens.is_none() {
+        return None;
     }
+
+    let input_tokens = input_tokens.unwrap_or(0);
+    let output_tokens = output_tokens.unwrap_or(0);
+    Some(CodexThreadUsage {
+        input_tokens,
+        output_tokens,
+        total_tokens: read_count("total_tokens").unwrap_or(input_tokens + output_tokens),
+    })
 }
 
-fn load_thread_runtime_state(path: &Path) -> CodexThreadRuntimeState {
+fn load_thread_runtime_state(path: &Path, now_ms: i64) -> CodexThreadRuntimeState {
     let file = match File::open(path) {
         Ok(file) => file,
         Err(_) => {
@@ -608,9 +1064,7 @@ fn load_thread_runtime_state(path: &Path) -> CodexThreadRuntimeState {
     }
 
     let is_recent = last_event_at_ms
-        .map(|timestamp_ms| {
-            now_unix_millis().saturating_sub(timestamp_ms) <= CODEX_AGENT_ACTIVITY_WINDOW_MS
-        })
+        .map(|timestamp_ms| now_ms.saturating_sub(timestamp_ms) <= CODEX_AGENT_ACTIVITY_WINDOW_MS)
         .unwrap_or(false);
     let agent_answering = is_recent
         && matches!(
@@ -625,79 +1079,216 @@ fn load_thread_runtime_state(path: &Path) -> CodexThreadRuntimeState {
     }
 }
 
-fn extract_semantic_event_kind(record: &Value) -> Option<CodexSemanticEventKind> {
-    let record_type = record.get("type").and_then(Value::as_str)?;
-    match record_type {
-        "event_msg" => extract_semantic_event_kind_from_event_msg(record.get("payload")?),
-        "response_item" => extract_semantic_event_kind_from_response_item(record.get("payload")?),
-        _ => None,
-    }
+fn first_session_meta_id(path: &Path) -> Option<String> {
+    let file = File::open(path).ok()?;
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .find_map(|line| {
+            let parsed: Value = serde_json::from_str(&line).ok()?;
+            if parsed.get("type").and_then(Value::as_str) != Some("session_meta") {
+                return None;
+            }
+            parsed
+                .get("payload")?
+                .get("id")
+                .and_then(Value::as_str)
+                .and_then(non_empty_trimmed)
+                .map(ToString::to_string)
+        })
 }
 
-fn extract_semantic_event_kind_from_event_msg(payload: &Value) -> Option<CodexSemanticEventKind> {
-    let event_type = payload.get("type").and_then(Value::as_str)?;
-    match event_type {
-        "user_message" => Some(CodexSemanticEventKind::UserMessage),
-        "agent_reasoning" => Some(CodexSemanticEventKind::AgentReasoning),
-        "agent_message" => Some(CodexSemanticEventKind::AgentMessage),
-        "turn_aborted" => Some(CodexSemanticEventKind::TurnAborted),
-        _ => None,
-    }
-}
+fn load_thread_event_log(path: &Path, limit: usize) -> Vec<ThreadEvent> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(_) => return Vec::new(),
+    };
+    let reader = BufReader::new(file);
+    let mut events = VecDeque::new();
 
-fn extract_semantic_event_kind_from_response_item(
-    payload: &Value,
-) -> Option<CodexSemanticEventKind> {
-    let item_type = payload.get("type").and_then(Value::as_str)?;
-    match item_type {
-        "reasoning" => Some(CodexSemanticEventKind::AgentReasoning),
-        "function_call"
-        | "function_call_output"
-        | "custom_tool_call"
-        | "custom_tool_call_output" => Some(CodexSemanticEventKind::AgentTool),
-        "message" => {
-            let role = payload
-                .get("role")
-                .and_then(Value::as_str)
-                .unwrap_or("assistant");
-            if role == "user" {
-                Some(CodexSemanticEventKind::UserMessage)
-            } else {
-                Some(CodexSemanticEventKind::AgentMessage)
+    for line in reader.lines().map_while(Result::ok) {
+        let parsed: Value = match serde_json::from_str(&line) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+
+        if let Some(kind) = extract_semantic_event_kind(&parsed) {
+            events.push_back(ThreadEvent {
+                kind: kind.as_str().to_string(),
+                at_ms: parse_timestamp_ms(parsed.get("timestamp")),
+                timestamp_raw: raw_timestamp(parsed.get("timestamp")),
+            });
+            if events.len() > limit {
+                events.pop_front();
             }
         }
-        _ => None,
     }
+
+    events.into()
 }
 
-/// Lightweight last-message preview: scans the JSONL file and extracts the last
-/// visible text content from response_item messages without full message parsing.
-fn build_last_message_preview(path: &Path) -> Option<String> {
-    let file = File::open(path).ok()?;
-    let reader = BufReader::new(file);
-    let mut last_visible_text: Option<String> = None;
+/// Counts visible user/assistant `response_item` messages (the mirrored `event_msg`
+/// copies are skipped) and collects the files named in `apply_patch` calls.
+fn load_thread_activity(path: &Path) -> ThreadActivity {
+    let mut activity = ThreadActivity::default();
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(_) => return activity,
+    };
 
-    for line in reader.lines().map_while(Result::ok) {
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
         let parsed: Value = match serde_json::from_str(&line) {
             Ok(value) => value,
             Err(_) => continue,
         };
-
+        activity.record_timestamp(parse_timestamp_ms(parsed.get("timestamp")));
         if parsed.get("type").and_then(Value::as_str) != Some("response_item") {
             continue;
         }
-
-        let payload = match parsed.get("payload") {
-            Some(value) => value,
-            None => continue,
+        let Some(payload) = parsed.get("payload") else {
+            continue;
         };
 
-        let item_type = payload.get("type").and_then(Value::as_str);
-        if item_type != Some("message") {
-            continue;
+        match payload.get("type").and_then(Value::as_str) {
+            Some("message") if is_counted_message(payload) => {
+                activity.message_count += 1;
+            }
+            Some("function_call" | "custom_tool_call") => {
+                for patch in tool_call_patch_texts(payload) {
+                    for path in patch_file_paths(&patch) {
+                        activity.record_touched_file(path);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    activity
+}
+
+/// Lines that are not `response_item` records are skipped before parsing.
+fn count_thread_messages(path: &Path) -> usize {
+    let Ok(file) = File::open(path) else {
+        return 0;
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter(|line| line.contains("\"response_item\""))
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok())
+        .filter(|parsed| {
+            parsed.get("type").and_then(Value::as_str) == Some("response_item")
+                && parsed.get("payload").is_some_and(is_counted_message)
+        })
+        .count()
+}
+
+fn is_counted_message(payload: &Value) -> bool {
+    payload.get("type").and_then(Value::as_str) == Some("message")
+        && matches!(
+            payload.get("role").and_then(Value::as_str),
+            Some("user" | "assistant")
+        )
+        && extract_codex_preview_text(payload).is_some()
+}
+
+/// Patch bodies passed to `apply_patch`, either as the tool's own input or as an
+/// argument of a `shell` call. `arguments` is a JSON-encoded string.
+fn tool_call_patch_texts(payload: &Value) -> Vec<String> {
+    fn collect_strings(value: &Value, output: &mut Vec<String>) {
+        match value {
+            Value::String(text) => output.push(text.clone()),
+            Value::Array(items) => items.iter().for_each(|item| collect_strings(item, output)),
+            Value::Object(object) => object
+                .values()
+                .for_each(|item| collect_strings(item, output)),
+            _ => {}
+        }
+    }
+
+    let mut texts = Vec::new();
+    if let Some(input) = payload.get("input") {
+        collect_strings(input, &mut texts);
+    }
+    if let Some(arguments) = payload.get("arguments").and_then(Value::as_str) {
+        match serde_json::from_str::<Value>(arguments) {
+            Ok(parsed) => collect_strings(&parsed, &mut texts),
+            Err(_) => texts.push(arguments.to_string()),
         }
+    }
+    texts.retain(|text| text.contains("*** Begin Patch"));
+    texts
+}
+
+fn patch_file_paths(patch: &str) -> Vec<&str> {
+    patch
+        .lines()
+        .filter_map(|line| {
+            [
+                "*** Add File: ",
+                "*** Update File: ",
+                "*** Delete File: ",
+                "*** Move to: ",
+            ]
+            .into_iter()
+            .find_map(|prefix| line.trim_start().strip_prefix(prefix))
+        })
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .collect()
+}
+
+fn extract_semantic_event_kind(record: &Value) -> Option<CodexSemanticEventKind> {
+    let record_type = record.get("type").and_then(Value::as_str)?;
+    match record_type {
+        "event_msg" => extract_semantic_event_kind_from_event_msg(record.get("payload")?),
+        "response_item" => extract_semantic_event_kind_from_response_item(record.get("payload")?),
+        _ => None,
+    }
+}
+
+fn extract_semantic_event_kind_from_event_msg(payload: &Value) -> Option<CodexSemanticEventKind> {
+    let event_type = payload.get("type").and_then(Value::as_str)?;
+    match event_type {
+        "user_message" => Some(CodexSemanticEventKind::UserMessage),
+        "agent_reasoning" => Some(CodexSemanticEventKind::AgentReasoning),
+        "agent_message" => Some(CodexSemanticEventKind::AgentMessage),
+        "turn_aborted" => Some(CodexSemanticEventKind::TurnAborted),
+        _ => None,
+    }
+}
+
+fn extract_semantic_event_kind_from_response_item(
+    payload: &Value,
+) -> Option<CodexSemanticEventKind> {
+    let item_type = payload.get("type").and_then(Value::as_str)?;
+    match item_type {
+        "reasoning" => Some(CodexSemanticEventKind::AgentReasoning),
+        "function_call"
+        | "function_call_output"
+        | "custom_tool_call"
+        | "custom_tool_call_output" => Some(CodexSemanticEventKind::AgentTool),
+        "message" => match payload.get("role").and_then(Value::as_str) {
+            Some("user") => Some(CodexSemanticEventKind::UserMessage),
+            Some(role) if is_instruction_role(role) => {
+                Some(CodexSemanticEventKind::InstructionMessage)
+            }
+            _ => Some(CodexSemanticEventKind::AgentMessage),
+        },
+        _ => None,
+    }
+}
+
+/// Lightweight last-message preview: scans the JSONL file and extracts the last
+/// visible text content from response_item messages without full message parsing.
+fn build_last_message_preview(path: &Path) -> Option<String> {
+    let file = File::open(path).ok()?;
+    let reader = BufReader::new(file);
+    let mut last_visible_text: Option<String> = None;
 
-        if let Some(text) = extract_codex_preview_text(payload) {
+    for line in reader.lines().map_while(Result::ok) {
+        if let Some(text) = extract_line_preview_text(&line) {
             last_visible_text = Some(text);
         }
     }
@@ -705,6 +1296,52 @@ fn build_last_message_preview(path: &Path) -> Option<String> {
     last_visible_text.map(|text| truncate_text(&text, 140))
 }
 
+fn build_latest_message_preview(path: &Path) -> Option<String> {
+    find_last_line_map(path, extract_line_preview_text).map(|text| truncate_text(&text, 140))
+}
+
+fn extract_line_preview_text(line: &str) -> Option<String> {
+    let parsed: Value = serde_json::from_str(line).ok()?;
+    if parsed.get("type").and_then(Value::as_str) != Some("response_item") {
+        return None;
+    }
+
+    let payload = parsed.get("payload")?;
+    if payload.get("type").and_then(Value::as_str) != Some("message") {
+        return None;
+    }
+    if payload
+        .get("role")
+        .and_then(Value::as_str)
+        .map(is_instruction_role)
+        .unwrap_or(false)
+    {
+        return None;
+    }
+
+    extract_codex_preview_text(payload)
+}
+
+fn extract_line_assistant_text(line: &str) -> Option<String> {
+    let parsed: Value = serde_json::from_str(line).ok()?;
+    if parsed.get("type").and_then(Value::as_str) != Some("response_item") {
+        return None;
+    }
+
+    let payload = parsed.get("payload")?;
+    if payload.get("type").and_then(Value::as_str) != Some("message")
+        || payload.get("role").and_then(Value::as_str) != Some("assistant")
+    {
+        return None;
+    }
+    extract_codex_preview_text(payload)
+}
+
+/// Roles Codex uses for injected instructions rather than conversation turns.
+fn is_instruction_role(role: &str) -> bool {
+    matches!(role, "developer" | "system")
+}
+
 /// Extract visible text from a Codex response_item message payload for preview.
 fn extract_codex_preview_text(payload: &Value) -> Option<String> {
     let content = payload.get("content")?;
@@ -829,34 +1466,22 @@ fn normalize_path_for_match(raw: &str) -> String {
         .to_string()
 }
 
-fn now_unix_millis() -> i64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|duration| duration.as_millis() as i64)
-        .unwrap_or(0)
-}
-
-fn default_home_dir() -> Option<PathBuf> {
-    if let Ok(home) = std::env::var("HOME") {
-        if !home.trim().is_empty() {
-            return Some(PathBuf::from(home));
-        }
-    }
-
-    if let Ok(home) = std::env::var("USERPROFILE") {
-        if !home.trim().is_empty() {
-            return Some(PathBuf::from(home));
+/// `AGENTDOCK_CODEX_HOME_DIR`, then Codex's `CODEX_HOME`, then `<home>/.codex`.
+fn resolve_codex_home_dir<F>(env_var: F, home: Option<PathBuf>) -> PathBuf
+where
+    F: Fn(&str) -> Option<String>,
+{
+    for key in [CODEX_HOME_DIR_ENV, CODEX_NATIVE_HOME_ENV] {
+        if let Some(path) = env_var(key) {
+            let trimmed = path.trim();
+            if !trimmed.is_empty() {
+                return PathBuf::from(trimmed);
+            }
         }
     }
 
-    let home_drive = std::env::var("HOMEDRIVE").ok()?;
-    let home_path = std::env::var("HOMEPATH").ok()?;
-    let combined = format!("{home_drive}{home_path}");
-    if combined.trim().is_empty() {
-        return None;
-    }
-
-    Some(PathBuf::from(combined))
+    home.map(|home| home.join(".codex"))
+        .unwrap_or_else(|| PathBuf::from(".codex"))
 }
 
 fn prepend_workdir_to_command(command: String, path: &str) -> String {
@@ -885,7 +1510,9 @@ fn shell_quote(path: &str) -> String {
 mod tests {
     use super::*;
 
+    use provider_contract::{FixedClock, UNRESOLVED_PROJECT_PATH_TAG};
     use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::SystemTime;
 
     static TEST_DIR_COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -949,6 +1576,23 @@ mod tests {
         assert_eq!(threads[0].title, "a");
     }
 
+    #[test]
+    fn default_provider_tag_matches_provider_id() {
+        let codex_home = test_temp_dir("provider-tag").join(".codex");
+        write_lines(
+            &codex_home.join("sessions/2026/02/12/session-tag.jsonl"),
+            &[
+                r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-tag","cwd":"/workspace/a"}}"#,
+            ],
+        );
+        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+
+        let threads = adapter.list_threads(None).expect("threads should load");
+
+        assert_eq!(threads[0].tags, vec![adapter.provider_id().as_str()]);
+        assert_eq!(threads[0].provider_id, adapter.provider_id());
+    }
+
     #[test]
     fn list_threads_prefers_canonical_session_id_when_file_contains_mixed_session_ids() {
         let codex_home = test_temp_dir("mixed-session-ids").join(".codex");
@@ -1288,7 +1932,7 @@ mod tests {
             .join("12")
             .join("session-c.jsonl");
 
-        let now = now_unix_millis();
+        let now = 1_700_000_000_000;
         let lines = vec![
             format!(
                 r#"{{"timestamp":{},"type":"session_meta","payload":{{"id":"codex-c","cwd":"/workspace/c"}}}}"#,
@@ -1305,7 +1949,9 @@ mod tests {
         ];
         write_owned_lines(&session_file, &lines);
 
-        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+        let adapter = CodexAdapter::new()
+            .with_home_dir(&codex_home)
+            .with_clock(FixedClock(Timestamp::from_epoch_millis(now)));
         let state = adapter
             .get_thread_runtime_state("codex-c")
             .expect("runtime state should be readable");
@@ -1324,7 +1970,7 @@ mod tests {
             .join("12")
             .join("session-d.jsonl");
 
-        let now = now_unix_millis();
+        let now = 1_700_000_000_000;
         let lines = vec![
             format!(
                 r#"{{"timestamp":{},"type":"session_meta","payload":{{"id":"codex-d","cwd":"/workspace/d"}}}}"#,
@@ -1341,7 +1987,9 @@ mod tests {
         ];
         write_owned_lines(&session_file, &lines);
 
-        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+        let adapter = CodexAdapter::new()
+            .with_home_dir(&codex_home)
+            .with_clock(FixedClock(Timestamp::from_epoch_millis(now)));
         let state = adapter
             .get_thread_runtime_state("codex-d")
             .expect("runtime state should be readable");
@@ -1349,4 +1997,949 @@ mod tests {
         assert!(!state.agent_answering);
         assert_eq!(state.last_event_kind.as_deref(), Some("agent_reasoning"));
     }
+
+    #[test]
+    fn build_resume_command_appends_context_summary_as_prompt() {
+        let command = build_resume_command(
+            "codex",
+            "codex-a",
+            Some("/workspace/a"),
+            Some("Objective: fix the build"),
+            &CodexLaunchSettings::default(),
+        );
+        assert!(command.contains(&shell_quote("Objective: fix the build")));
+        assert!(command.contains(&format!("codex resume {}", shell_quote("codex-a"))));
+
+        let without_summary = build_resume_command(
+            "codex",
+            "codex-a",
+            None,
+            None,
+            &CodexLaunchSettings::default(),
+        );
+        assert_eq!(
+            without_summary,
+            format!("codex resume {}", shell_quote("codex-a"))
+        );
+    }
+
+    #[test]
+    fn latest_message_preview_matches_full_parse() {
+        let codex_home = test_temp_dir("latest-preview").join(".codex");
+        let session_file = codex_home
+            .join("sessions")
+            .join("2026")
+            .join("02")
+            .join("12")
+            .join("session-latest.jsonl");
+
+        write_lines(
+            &session_file,
+            &[
+                r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-latest","cwd":"/workspace/latest"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:01.000Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"run the tests"}]}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:03.000Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"All tests pass"}]}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:04.000Z","type":"event_msg","payload":{"type":"token_count"}}"#,
+            ],
+        );
+
+        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+        let latest = adapter
+            .get_latest_message_preview("codex-latest")
+            .expect("latest preview should work");
+
+        assert_eq!(latest.as_deref(), Some("All tests pass"));
+        assert_eq!(latest, build_last_message_preview(&session_file));
+    }
+
+    #[test]
+    fn resume_thread_reports_archived_rollout_as_not_resumable() {
+        let codex_home = test_temp_dir("resume-archived").join(".codex");
+        let session_file = codex_home
+            .join("sessions")
+            .join("2026")
+            .join("02")
+            .join("12")
+            .join("rollout-2026-02-12T10-00-00-codex-archived.jsonl");
+        let session_meta = r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-archived","cwd":"/workspace/archived"}}"#;
+        write_lines(&session_file, &[session_meta]);
+        let adapter = CodexAdapter::new()
+            .with_home_dir(&codex_home)
+            .with_cli_binary("missing-codex-binary-123");
+        let request = || ResumeThreadRequest {
+            thread_id: "codex-archived".to_string(),
+            project_path: None,
+            context_summary: None,
+        };
+
+        fs::remove_file(&session_file).expect("session file should be removable");
+        let removed = adapter
+            .resume_thread(request())
+            .expect("resume should report instead of failing");
+        assert!(!removed.resumed);
+        assert!(removed
+            .message
+            .expect("reason should exist")
+            .contains("no longer exists"));
+
+        write_lines(
+            &codex_home
+                .join(CODEX_ARCHIVED_SESSIONS_DIR)
+                .join("rollout-2026-02-12T10-00-00-codex-archived.jsonl"),
+            &[session_meta],
+        );
+        let archived = adapter
+            .resume_thread(request())
+            .expect("resume should report instead of failing");
+        assert!(!archived.resumed);
+        assert!(archived
+            .message
+            .expect("reason should exist")
+            .starts_with("Codex session is archived"));
+    }
+
+    #[test]
+    fn thread_usage_uses_latest_total_plus_later_turns() {
+        let codex_home = test_temp_dir("thread-usage").join(".codex");
+        let session_file = codex_home
+            .join("sessions")
+            .join("2026")
+            .join("02")
+            .join("12")
+            .join("session-usage.jsonl");
+
+        write_lines(
+            &session_file,
+            &[
+                r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-usage","cwd":"/workspace/usage"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:01.000Z","type":"event_msg","payload":{"type":"token_count","info":null}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:02.000Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":1200,"output_tokens":300,"total_tokens":1500},"last_token_usage":{"input_tokens":1200,"output_tokens":300,"total_tokens":1500}}}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:03.000Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":2000,"output_tokens":450,"total_tokens":2450},"last_token_usage":{"input_tokens":800,"output_tokens":150,"total_tokens":950}}}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:04.000Z","type":"event_msg","payload":{"type":"token_count","input_tokens":10,"output_tokens":5}}"#,
+            ],
+        );
+
+        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+        let usage = adapter
+            .get_thread_usage("codex-usage")
+            .expect("usage should be readable");
+
+        assert_eq!(
+            usage,
+            CodexThreadUsage {
+                input_tokens: 2010,
+                output_tokens: 455,
+                total_tokens: 2465,
+            }
+        );
+    }
+
+    #[test]
+    fn thread_usage_ignores_re_emitted_token_count_events() {
+        let codex_home = test_temp_dir("thread-usage-repeated").join(".codex");
+        let token_count = r#"{"timestamp":"2026-02-12T10:00:01.000Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":1200,"output_tokens":300,"total_tokens":1500},"last_token_usage":{"input_tokens":1200,"output_tokens":300,"total_tokens":1500}}}}"#;
+        write_lines(
+            &codex_home
+                .join("sessions")
+                .join("2026")
+                .join("02")
+                .join("12")
+                .join("session-repeated.jsonl"),
+            &[
+                r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-repeated","cwd":"/workspace/usage"}}"#,
+                token_count,
+                token_count,
+            ],
+        );
+
+        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+        let usage = adapter
+            .get_thread_usage("codex-repeated")
+            .expect("usage should be readable");
+
+        assert_eq!(
+            usage,
+            CodexThreadUsage {
+                input_tokens: 1200,
+                output_tokens: 300,
+                total_tokens: 1500,
+            }
+        );
+    }
+
+    #[test]
+    fn thread_usage_is_zero_without_token_count_events() {
+        let codex_home = test_temp_dir("thread-usage-empty").join(".codex");
+        write_lines(
+            &codex_home
+                .join("sessions")
+                .join("2026")
+                .join("02")
+                .join("12")
+                .join("session-empty.jsonl"),
+            &[
+                r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-empty","cwd":"/workspace/empty"}}"#,
+            ],
+        );
+
+        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+        let usage = adapter
+            .get_thread_usage("codex-empty")
+            .expect("usage should be readable");
+
+        assert_eq!(usage, CodexThreadUsage::default());
+    }
+
+    #[test]
+    fn list_threads_resolves_relative_cwd_against_project_path_base() {
+        let codex_home = test_temp_dir("relative-cwd").join(".codex");
+        let sessions_dir = codex_home
+            .join("sessions")
+            .join("2026")
+            .join("02")
+            .join("12");
+        write_lines(
+            &sessions_dir.join("session-relative.jsonl"),
+            &[
+                r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-relative","cwd":"services/api"}}"#,
+            ],
+        );
+        write_lines(
+            &sessions_dir.join("session-absolute.jsonl"),
+            &[
+                r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-absolute","cwd":"/workspace/absolute"}}"#,
+            ],
+        );
+
+        let base = PathBuf::from("/workspace/monorepo");
+        let adapter = CodexAdapter::new()
+            .with_home_dir(&codex_home)
+            .with_project_path_base(&base);
+        let threads = adapter
+            .list_threads(None)
+            .expect("list_threads should work");
+        let find = |id: &str| {
+            threads
+                .iter()
+                .find(|thread| thread.id == id)
+                .expect("thread should exist")
+        };
+
+        assert_eq!(
+            find("codex-relative").project_path,
+            base.join("services").join("api").to_string_lossy()
+        );
+        assert_eq!(find("codex-absolute").project_path, "/workspace/absolute");
+        assert!(!find("codex-absolute")
+            .tags
+            .contains(&UNRESOLVED_PROJECT_PATH_TAG.to_string()));
+    }
+
+    #[test]
+    fn list_threads_groups_subdirectory_cwd_under_git_root() {
+        let root = test_temp_dir("git-root");
+        let repo = root.join("repo");
+        let subdir = repo.join("services").join("api");
+        fs::create_dir_all(repo.join(".git")).expect("git dir should be creatable");
+        fs::create_dir_all(&subdir).expect("subdir should be creatable");
+        let plain = root.join("plain");
+        fs::create_dir_all(&plain).expect("plain dir should be creatable");
+
+        let codex_home = root.join(".codex");
+        let sessions_dir = codex_home.join("sessions/2026/02/12");
+        write_owned_lines(
+            &sessions_dir.join("session-repo.jsonl"),
+            &[format!(
+                r#"{{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{{"id":"codex-repo","cwd":"{}"}}}}"#,
+                subdir.display()
+            )],
+        );
+        write_owned_lines(
+            &sessions_dir.join("session-plain.jsonl"),
+            &[format!(
+                r#"{{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{{"id":"codex-plain","cwd":"{}"}}}}"#,
+                plain.display()
+            )],
+        );
+
+        let threads = CodexAdapter::new()
+            .with_home_dir(&codex_home)
+            .with_git_root_grouping(true)
+            .list_threads(None)
+            .expect("list_threads should work");
+        let find = |id: &str| {
+            threads
+                .iter()
+                .find(|thread| thread.id == id)
+                .expect("thread should exist")
+        };
+
+        let grouped = find("codex-repo");
+        assert_eq!(grouped.project_path, repo.to_string_lossy());
+        assert_eq!(
+            grouped.original_cwd.as_deref(),
+            Some(subdir.to_string_lossy().as_ref())
+        );
+        let passthrough = find("codex-plain");
+        assert_eq!(passthrough.project_path, plain.to_string_lossy());
+        assert!(passthrough.original_cwd.is_none());
+    }
+
+    #[test]
+    fn runtime_state_activity_window_includes_exact_edge() {
+        let codex_home = test_temp_dir("runtime-window-edge").join(".codex");
+        let session_file = codex_home
+            .join("sessions")
+            .join("2026")
+            .join("02")
+            .join("12")
+            .join("session-edge.jsonl");
+        let event_at = 1_700_000_000_000;
+
+        let lines = vec![
+            format!(
+                r#"{{"timestamp":{},"type":"session_meta","payload":{{"id":"codex-edge","cwd":"/workspace/edge"}}}}"#,
+                event_at - 1_000
+            ),
+            format!(
+                r#"{{"timestamp":{event_at},"type":"event_msg","payload":{{"type":"agent_reasoning","text":"thinking"}}}}"#
+            ),
+        ];
+        write_owned_lines(&session_file, &lines);
+
+        let state_at = |now: i64| {
+            CodexAdapter::new()
+                .with_home_dir(&codex_home)
+                .with_clock(FixedClock(Timestamp::from_epoch_millis(now)))
+                .get_thread_runtime_state("codex-edge")
+                .expect("runtime state should be readable")
+        };
+
+        assert!(state_at(event_at + CODEX_AGENT_ACTIVITY_WINDOW_MS).agent_answering);
+        assert!(!state_at(event_at + CODEX_AGENT_ACTIVITY_WINDOW_MS + 1).agent_answering);
+    }
+
+    #[test]
+    fn thread_event_log_returns_last_events_in_order() {
+        let codex_home = test_temp_dir("event-log").join(".codex");
+        let session_file = codex_home
+            .join("sessions")
+            .join("2026")
+            .join("02")
+            .join("12")
+            .join("session-events.jsonl");
+
+        write_lines(
+            &session_file,
+            &[
+                r#"{"timestamp":1700000000000,"type":"session_meta","payload":{"id":"codex-events","cwd":"/workspace/events"}}"#,
+                r#"{"timestamp":1700000001000,"type":"event_msg","payload":{"type":"user_message","message":"fix the build"}}"#,
+                r#"{"timestamp":1700000002000,"type":"response_item","payload":{"type":"reasoning","summary":[]}}"#,
+                r#"{"timestamp":1700000003000,"type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{}"}}"#,
+                r#"{"timestamp":1700000004000,"type":"response_item","payload":{"type":"function_call_output","output":"ok"}}"#,
+                r#"{"timestamp":1700000005000,"type":"event_msg","payload":{"type":"agent_message","message":"Build fixed."}}"#,
+            ],
+        );
+
+        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+        let events = adapter
+            .get_thread_event_log("codex-events", 10)
+            .expect("event log should be readable");
+        let kinds: Vec<&str> = events.iter().map(|event| event.kind.as_str()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "user_message",
+                "agent_reasoning",
+                "agent_tool",
+                "agent_tool",
+                "agent_message"
+            ]
+        );
+        assert_eq!(events[0].at_ms, Some(1_700_000_001_000));
+
+        let tail = adapter
+            .get_thread_event_log("codex-events", 2)
+            .expect("event log should be readable");
+        let kinds: Vec<&str> = tail.iter().map(|event| event.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["agent_tool", "agent_message"]);
+    }
+
+    #[test]
+    fn developer_and_system_messages_are_not_labeled_as_agent_messages() {
+        let codex_home = test_temp_dir("instruction-roles").join(".codex");
+        let session_file = codex_home
+            .join("sessions")
+            .join("2026")
+            .join("02")
+            .join("12")
+            .join("session-roles.jsonl");
+
+        write_lines(
+            &session_file,
+            &[
+                r#"{"timestamp":1700000000000,"type":"session_meta","payload":{"id":"codex-roles","cwd":"/workspace/roles"}}"#,
+                r#"{"timestamp":1700000001000,"type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"run the tests"}]}}"#,
+                r#"{"timestamp":1700000002000,"type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"All tests pass"}]}}"#,
+                r#"{"timestamp":1700000003000,"type":"response_item","payload":{"type":"message","role":"developer","content":[{"type":"input_text","text":"Approval policy changed to never"}]}}"#,
+                r#"{"timestamp":1700000004000,"type":"response_item","payload":{"type":"message","role":"system","content":"Sandbox mode: workspace-write"}}"#,
+            ],
+        );
+
+        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+        let events = adapter
+            .get_thread_event_log("codex-roles", 10)
+            .expect("event log should be readable");
+        let kinds: Vec<&str> = events.iter().map(|event| event.kind.as_str()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "user_message",
+                "agent_message",
+                "instruction_message",
+                "instruction_message"
+            ]
+        );
+
+        let preview = adapter
+            .get_latest_message_preview("codex-roles")
+            .expect("preview should be readable");
+        assert_eq!(preview.as_deref(), Some("All tests pass"));
+    }
+
+    #[test]
+    fn thread_exists_tracks_session_files_on_disk() {
+        let codex_home = test_temp_dir("thread-exists").join(".codex");
+        let session_file = codex_home
+            .join("sessions")
+            .join("2026")
+            .join("02")
+            .join("12")
+            .join("rollout-2026-02-12T10-00-00-codex-exists.jsonl");
+        write_lines(
+            &session_file,
+            &[
+                r#"{"timestamp":1700000000000,"type":"session_meta","payload":{"id":"codex-exists","cwd":"/workspace/exists"}}"#,
+                r#"{"timestamp":1700000001000,"type":"event_msg","payload":{"type":"user_message","message":"hello"}}"#,
+            ],
+        );
+        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+
+        assert!(adapter.thread_exists("codex-exists"));
+        assert!(!adapter.thread_exists("codex-missing"));
+
+        fs::remove_file(&session_file).expect("session file should be removed");
+        assert!(!adapter.thread_exists("codex-exists"));
+    }
+
+    #[test]
+    fn list_threads_hides_empty_placeholder_threads_when_enabled() {
+        let codex_home = test_temp_dir("hide-empty").join(".codex");
+        let sessions_dir = codex_home.join("sessions/2026/02/12");
+        write_lines(
+            &sessions_dir.join("session-real.jsonl"),
+            &[
+                r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-real","cwd":"/workspace/a"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:01.000Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"Fix the build"}]}}"#,
+            ],
+        );
+        write_lines(
+            &sessions_dir.join("session-empty.jsonl"),
+            &[
+                r#"{"timestamp":"2026-02-12T11:00:00.000Z","type":"session_meta","payload":{"id":"codex-empty"}}"#,
+                r#"{"timestamp":"2026-02-12T11:00:00.000Z","type":"response_item","payload":{"type":"message","role":"developer","content":[{"type":"input_text","text":"<permissions instructions>"}]}}"#,
+            ],
+        );
+        write_lines(
+            &sessions_dir.join("session-cwd-dot.jsonl"),
+            &[
+                r#"{"timestamp":"2026-02-12T09:00:00.000Z","type":"session_meta","payload":{"id":"codex-cwd-dot","cwd":"."}}"#,
+            ],
+        );
+
+        let all = CodexAdapter::new()
+            .with_home_dir(&codex_home)
+            .list_threads(None)
+            .expect("list_threads should work");
+        assert_eq!(all.len(), 3);
+
+        let threads = CodexAdapter::new()
+            .with_home_dir(&codex_home)
+            .with_hide_empty_threads(true)
+            .list_threads(None)
+            .expect("list_threads should work");
+        let mut ids = threads
+            .iter()
+            .map(|thread| thread.id.as_str())
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        assert_eq!(ids, vec!["codex-cwd-dot", "codex-real"]);
+    }
+
+    #[test]
+    fn resolve_codex_home_dir_prefers_agentdock_env_then_codex_home() {
+        let home = Some(PathBuf::from("/home/user"));
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                pairs
+                    .iter()
+                    .find(|(name, _)| *name == key)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        assert_eq!(
+            resolve_codex_home_dir(
+                env(&[
+                    (CODEX_HOME_DIR_ENV, "/agentdock/codex"),
+                    (CODEX_NATIVE_HOME_ENV, "/relocated/codex"),
+                ]),
+                home.clone(),
+            ),
+            PathBuf::from("/agentdock/codex")
+        );
+        assert_eq!(
+            resolve_codex_home_dir(
+                env(&[
+                    (CODEX_HOME_DIR_ENV, " "),
+                    (CODEX_NATIVE_HOME_ENV, "/relocated/codex"),
+                ]),
+                home.clone(),
+            ),
+            PathBuf::from("/relocated/codex")
+        );
+        assert_eq!(
+            resolve_codex_home_dir(env(&[]), home),
+            PathBuf::from("/home/user/.codex")
+        );
+    }
+
+    #[test]
+    fn final_assistant_message_skips_trailing_tool_records() {
+        let codex_home = test_temp_dir("final-assistant").join(".codex");
+        write_lines(
+            &codex_home.join("sessions/2026/02/12/session-final.jsonl"),
+            &[
+                r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-final","cwd":"/workspace/a"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:01.000Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"Run the tests"}]}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:02.000Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"All 42 tests pass."}]}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:03.000Z","type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{\"command\":[\"git\",\"status\"]}"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:04.000Z","type":"response_item","payload":{"type":"function_call_output","output":"clean"}}"#,
+            ],
+        );
+
+        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+        let message = adapter
+            .get_final_assistant_message("codex-final")
+            .expect("final assistant message should load");
+
+        assert_eq!(message.as_deref(), Some("All 42 tests pass."));
+    }
+
+    #[test]
+    fn list_threads_uses_mtime_when_last_line_is_partial() {
+        let codex_home = test_temp_dir("partial-last-line").join(".codex");
+        let sessions_dir = codex_home.join("sessions/2026/02/12");
+        let idle_path = sessions_dir.join("session-idle.jsonl");
+        let active_path = sessions_dir.join("session-active.jsonl");
+        write_lines(
+            &idle_path,
+            &[
+                r#"{"timestamp":"2026-02-12T12:00:00.000Z","type":"session_meta","payload":{"id":"codex-idle","cwd":"/workspace/a"}}"#,
+            ],
+        );
+        write_lines(
+            &active_path,
+            &[
+                r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-active","cwd":"/workspace/a"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:05.000Z","type":"response_item","payload":{"type":"message","role":"assist"#,
+            ],
+        );
+        let idle_ms = 1_770_897_600_000;
+        let active_ms = 1_770_901_200_000;
+        for (path, modified_ms) in [(&idle_path, idle_ms), (&active_path, active_ms)] {
+            File::options()
+                .write(true)
+                .open(path)
+                .and_then(|file| {
+                    file.set_modified(UNIX_EPOCH + std::time::Duration::from_millis(modified_ms))
+                })
+                .expect("mtime should be settable");
+        }
+
+        let threads = CodexAdapter::new()
+            .with_home_dir(&codex_home)
+            .list_threads(None)
+            .expect("list_threads should work");
+
+        assert_eq!(threads[0].id, "codex-active");
+        assert_eq!(threads[0].last_active_at.as_millis(), active_ms as i64);
+        assert_eq!(threads[1].id, "codex-idle");
+        assert_eq!(threads[1].last_active_at.as_millis(), idle_ms as i64);
+    }
+
+    #[test]
+    fn previews_and_final_message_redact_secrets_unless_disabled() {
+        let codex_home = test_temp_dir("redaction").join(".codex");
+        write_lines(
+            &codex_home.join("sessions/2026/02/12/session-secret.jsonl"),
+            &[
+                r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-secret","cwd":"/workspace/a"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:01.000Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"Set the key sk-proj-0123456789abcdefXYZ and rerun."}]}}"#,
+            ],
+        );
+
+        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+        let masked = Some("Set the key [REDACTED] and rerun.");
+        assert_eq!(
+            adapter
+                .get_final_assistant_message("codex-secret")
+                .expect("final assistant message should load")
+                .as_deref(),
+            masked
+        );
+        assert_eq!(
+            adapter
+                .get_latest_message_preview("codex-secret")
+                .expect("preview should load")
+                .as_deref(),
+            masked
+        );
+        let overviews = adapter
+            .list_thread_overviews(None)
+            .expect("overviews should load");
+        assert_eq!(overviews[0].last_message_preview.as_deref(), masked);
+
+        let unredacted = adapter
+            .with_redaction(false)
+            .get_final_assistant_message("codex-secret")
+            .expect("final assistant message should load");
+        assert_eq!(
+            unredacted.as_deref(),
+            Some("Set the key sk-proj-0123456789abcdefXYZ and rerun.")
+        );
+    }
+
+    #[test]
+    fn resume_command_carries_recorded_sandbox_and_approval_flags() {
+        let codex_home = test_temp_dir("launch-settings").join(".codex");
+        write_lines(
+            &codex_home.join("sessions/2026/02/12/session-sandbox.jsonl"),
+            &[
+                r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-sandbox","cwd":"/workspace/a"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:01.000Z","type":"turn_context","payload":{"cwd":"/workspace/a","approval_policy":"on-request","sandbox_policy":{"mode":"read-only"},"model":"gpt-5-codex"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:02.000Z","type":"turn_context","payload":{"cwd":"/workspace/a","approval_policy":"never","sandbox_policy":{"mode":"workspace-write","network_access":false},"model":"gpt-5-codex"}}"#,
+            ],
+        );
+
+        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+        let record = adapter
+            .find_thread_record("codex-sandbox")
+            .expect("thread should be found");
+        assert_eq!(
+            record.launch_settings,
+            CodexLaunchSettings {
+                model: Some("gpt-5-codex".to_string()),
+                sandbox_mode: Some("workspace-write".to_string()),
+                approval_policy: Some("never".to_string()),
+            }
+        );
+        assert_eq!(record.summary.model.as_deref(), Some("gpt-5-codex"));
+
+        let command = build_resume_command(
+            "codex",
+            "codex-sandbox",
+            None,
+            None,
+            &record.launch_settings,
+        );
+        assert_eq!(
+            command,
+            format!(
+                "codex resume --model {} --sandbox workspace-write --ask-for-approval never {}",
+                shell_quote("gpt-5-codex"),
+                shell_quote("codex-sandbox")
+            )
+        );
+    }
+
+    #[test]
+    fn update_launch_settings_ignores_unknown_policies() {
+        let mut settings = CodexLaunchSettings::default();
+        update_launch_settings(
+            &mut settings,
+            &serde_json::json!({"sandbox_policy": "danger-full-access", "approval_policy": "; rm -rf /"}),
+        );
+
+        assert_eq!(settings.sandbox_mode.as_deref(), Some("danger-full-access"));
+        assert_eq!(settings.approval_policy, None);
+        assert_eq!(settings.model, None);
+    }
+
+    #[test]
+    fn thread_activity_collects_patched_files_per_thread() {
+        let codex_home = test_temp_dir("thread-activity").join(".codex");
+        write_lines(
+            &codex_home.join("sessions/2026/02/12/session-left.jsonl"),
+            &[
+                r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-left","cwd":"/workspace/a"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:01.000Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"Fix the parser"}]}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:01.000Z","type":"event_msg","payload":{"type":"user_message","message":"Fix the parser"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:02.000Z","type":"response_item","payload":{"type":"custom_tool_call","name":"apply_patch","input":"*** Begin Patch\n*** Update File: src/parser.rs\n@@\n-a\n+b\n*** Add File: tests/parser.rs\n+x\n*** End Patch"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:03.000Z","type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{\"command\":[\"apply_patch\",\"*** Begin Patch\\n*** Update File: src/parser.rs\\n*** Move to: src/parse.rs\\n*** End Patch\"]}"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:30.000Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"Parser fixed."}]}}"#,
+            ],
+        );
+        write_lines(
+            &codex_home.join("sessions/2026/02/12/session-right.jsonl"),
+            &[
+                r#"{"timestamp":"2026-02-12T11:00:00.000Z","type":"session_meta","payload":{"id":"codex-right","cwd":"/workspace/a"}}"#,
+                r#"{"timestamp":"2026-02-12T11:00:01.000Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"Fix the parser"}]}}"#,
+                r#"{"timestamp":"2026-02-12T11:00:02.000Z","type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{\"command\":[\"cat\",\"src/lexer.rs\"]}"}}"#,
+                r#"{"timestamp":"2026-02-12T11:00:05.000Z","type":"response_item","payload":{"type":"function_call","name":"apply_patch","arguments":"{\"input\":\"*** Begin Patch\\n*** Delete File: src/lexer_old.rs\\n*** End Patch\"}"}}"#,
+                r#"{"timestamp":"2026-02-12T11:00:10.000Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"Removed the old lexer."}]}}"#,
+            ],
+        );
+
+        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+        let left = adapter
+            .get_thread_activity("codex-left")
+            .expect("activity should load");
+        let right = adapter
+            .get_thread_activity("codex-right")
+            .expect("activity should load");
+
+        assert_eq!(
+            left.touched_files,
+            vec!["src/parser.rs", "tests/parser.rs", "src/parse.rs"]
+        );
+        assert_eq!(left.message_count, 2);
+        assert_eq!(left.duration_ms(), Some(30_000));
+        assert_eq!(right.touched_files, vec!["src/lexer_old.rs"]);
+        assert_eq!(right.message_count, 2);
+        assert_eq!(
+            adapter
+                .get_final_assistant_message("codex-left")
+                .expect("final assistant message should load")
+                .as_deref(),
+            Some("Parser fixed.")
+        );
+        assert_eq!(
+            adapter
+                .get_final_assistant_message("codex-right")
+                .expect("final assistant message should load")
+                .as_deref(),
+            Some("Removed the old lexer.")
+        );
+    }
+
+    #[test]
+    fn overviews_include_runtime_state_only_when_requested() {
+        let codex_home = test_temp_dir("overview-runtime").join(".codex");
+        let now = 1_700_000_000_000;
+        write_owned_lines(
+            &codex_home.join("sessions/2026/02/12/session-overview.jsonl"),
+            &[
+                format!(
+                    r#"{{"timestamp":{},"type":"session_meta","payload":{{"id":"codex-overview","cwd":"/workspace/a"}}}}"#,
+                    now - 10_000
+                ),
+                format!(
+                    r#"{{"timestamp":{},"type":"event_msg","payload":{{"type":"agent_reasoning","text":"thinking"}}}}"#,
+                    now - 2_000
+                ),
+            ],
+        );
+        let adapter = CodexAdapter::new()
+            .with_home_dir(&codex_home)
+            .with_clock(FixedClock(Timestamp::from_epoch_millis(now)));
+
+        let plain = adapter
+            .list_thread_overviews(None)
+            .expect("overviews should load");
+        assert_eq!(plain[0].runtime_state, None);
+
+        let overviews = adapter
+            .list_thread_overviews_with_options(
+                None,
+                ThreadOverviewOptions {
+                    include_runtime_state: true,
+                    ..ThreadOverviewOptions::default()
+                },
+            )
+            .expect("overviews should load");
+        let standalone = adapter
+            .get_thread_runtime_state("codex-overview")
+            .expect("runtime state should be readable");
+        assert_eq!(overviews[0].runtime_state.as_ref(), Some(&standalone));
+        assert_eq!(
+            standalone.last_event_kind.as_deref(),
+            Some("agent_reasoning")
+        );
+    }
+
+    #[test]
+    fn thread_event_log_keeps_raw_rfc3339_timestamp() {
+        let codex_home = test_temp_dir("event-log-raw").join(".codex");
+        write_lines(
+            &codex_home.join("sessions/2026/02/12/session-raw.jsonl"),
+            &[
+                r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-raw","cwd":"/workspace/a"}}"#,
+                r#"{"timestamp":"2026-02-12T18:30:01.250+08:00","type":"event_msg","payload":{"type":"agent_message","message":"Done."}}"#,
+                r#"{"timestamp":1770890402000,"type":"event_msg","payload":{"type":"user_message","message":"thanks"}}"#,
+            ],
+        );
+
+        let events = CodexAdapter::new()
+            .with_home_dir(&codex_home)
+            .get_thread_event_log("codex-raw", 10)
+            .expect("event log should be readable");
+
+        assert_eq!(events[0].kind, "agent_message");
+        assert_eq!(
+            events[0].timestamp_raw.as_deref(),
+            Some("2026-02-12T18:30:01.250+08:00")
+        );
+        assert_eq!(events[0].at_ms, Some(1_770_892_201_250));
+        assert_eq!(events[1].timestamp_raw, None);
+        assert_eq!(events[1].at_ms, Some(1_770_890_402_000));
+    }
+
+    #[test]
+    fn scan_thread_overviews_flags_scans_cut_short_by_timeout() {
+        let codex_home = test_temp_dir("scan-timeout").join(".codex");
+        for id in ["codex-one", "codex-two"] {
+            write_lines(
+                &codex_home.join(format!("sessions/2026/02/12/{id}.jsonl")),
+                &[&format!(
+                    r#"{{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{{"id":"{id}","cwd":"/workspace/a"}}}}"#
+                )],
+            );
+        }
+        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+
+        let full = adapter
+            .scan_thread_overviews(None, ThreadOverviewOptions::default())
+            .expect("scan should succeed");
+        assert_eq!(full.threads.len(), 2);
+        assert!(!full.timed_out);
+
+        let cut_short = adapter
+            .with_scan_timeout(Duration::ZERO)
+            .scan_thread_overviews(None, ThreadOverviewOptions::default())
+            .expect("scan should succeed");
+        assert!(cut_short.threads.is_empty());
+        assert!(cut_short.timed_out);
+    }
+
+    #[test]
+    fn config_summary_reflects_builder_overrides() {
+        let home_dir = test_temp_dir("config-summary").join(".codex");
+        fs::create_dir_all(home_dir.join("sessions")).expect("sessions dir should be creatable");
+        fs::write(
+            home_dir.join("auth.json"),
+            r#"{"OPENAI_API_KEY":"sk-secret"}"#,
+        )
+        .expect("auth file should be writable");
+        let binary = home_dir.join("codex-wrapper");
+        fs::write(&binary, "").expect("binary should be writable");
+
+        let summary = CodexAdapter::new()
+            .with_home_dir(&home_dir)
+            .with_cli_binary(binary.to_string_lossy())
+            .config_summary();
+
+        assert_eq!(summary.config_dir, home_dir);
+        assert_eq!(summary.sessions_dir, home_dir.join("sessions"));
+        assert!(summary.sessions_dir_exists());
+        assert_eq!(summary.cli_binary_path, Some(binary));
+        assert_eq!(summary.auth_source.as_deref(), Some("auth.json"));
+        assert!(!format!("{summary:?}").contains("sk-secret"));
+    }
+
+    #[test]
+    fn overviews_include_message_count_only_when_requested() {
+        let codex_home = test_temp_dir("overview-message-count").join(".codex");
+        write_lines(
+            &codex_home.join("sessions/2026/02/12/session-count.jsonl"),
+            &[
+                r#"{"timestamp":"2026-02-12T10:00:00.000Z","type":"session_meta","payload":{"id":"codex-count","cwd":"/workspace/a"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:01.000Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"Count these"}]}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:01.000Z","type":"event_msg","payload":{"type":"user_message","message":"Count these"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:02.000Z","type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{\"command\":[\"ls\"]}"}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:03.000Z","type":"response_item","payload":{"type":"message","role":"developer","content":[{"type":"input_text","text":"Sandbox rules"}]}}"#,
+                r#"{"timestamp":"2026-02-12T10:00:04.000Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"Done."}]}}"#,
+            ],
+        );
+        let adapter = CodexAdapter::new().with_home_dir(&codex_home);
+
+        let plain = adapter
+            .list_thread_overviews(None)
+            .expect("overviews should load");
+        assert_eq!(plain[0].message_count, None);
+
+        let overviews = adapter
+            .list_thread_overviews_with_options(
+                None,
+                ThreadOverviewOptions {
+                    include_message_count: true,
+                    ..ThreadOverviewOptions::default()
+                },
+            )
+            .expect("overviews should load");
+        let activity = adapter
+            .get_thread_activity("codex-count")
+            .expect("activity should load");
+        assert_eq!(overviews[0].message_count, Some(activity.message_count));
+        assert_eq!(activity.message_count, 2);
+    }
+
+    #[test]
+    fn health_check_runs_custom_cli_binary() {
+        let codex_home = test_temp_dir("health-custom-binary").join(".codex");
+        let adapter = CodexAdapter::new()
+            .with_home_dir(&codex_home)
+            .with_cli_binary("missing-codex-binary-123");
+
+        let result = adapter
+            .health_check(ProviderHealthCheckRequest {
+                profile_name: "default".to_string(),
+                project_path: None,
+            })
+            .expect("health check should return status");
+
+        assert_eq!(result.status, ProviderHealthStatus::Offline);
+        assert!(result
+            .message
+            .expect("message should exist")
+            .contains("missing-codex-binary-123"));
+    }
+
+    #[test]
+    fn resume_command_uses_custom_cli_binary() {
+        let codex_home = test_temp_dir("resume-custom-binary").join(".codex");
+        write_lines(
+            &codex_home.join("sessions/2026/02/12/rollout-2026-02-12T10-00-00-codex-resume.jsonl"),
+            &[
+                r#"{"timestamp":1700000000000,"type":"session_meta","payload":{"id":"codex-resume","cwd":"/workspace/resume"}}"#,
+                r#"{"timestamp":1700000001000,"type":"event_msg","payload":{"type":"user_message","message":"hello"}}"#,
+            ],
+        );
+        let adapter = CodexAdapter::new()
+            .with_home_dir(&codex_home)
+            .with_cli_binary("custom-codex");
+        let request = ResumeThreadRequest {
+            thread_id: "codex-resume".to_string(),
+            project_path: None,
+            context_summary: None,
+        };
+        let record = adapter
+            .find_thread_record(&request.thread_id)
+            .expect("thread record should exist");
+
+        assert!(adapter.resume_command(&record, &request).contains(&format!(
+            "custom-codex resume {}",
+            shell_quote("codex-resume")
+        )));
+    }
 }
@@ -0,0 +1,274 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+
+use crate::home_dirs::default_home_dir;
+
+/// Overrides the fallback install directories, using the platform PATH separator.
+pub const CLI_SEARCH_PATH_ENV: &str = "AGENTDOCK_CLI_SEARCH_PATH";
+
+/// Resolves a provider CLI name for spawning. Returns the bare name when it is on PATH
+/// (or cannot be found anywhere) and an absolute path when it only exists in one of
+/// `search_dirs`, e.g. a Homebrew install missing from the GUI PATH.
+pub fn resolve_cli_binary(binary: &str, search_dirs: &[PathBuf]) -> String {
+    if Path::new(binary).components().count() > 1 {
+        return binary.to_string();
+    }
+
+    if find_binary_in_dirs(binary, &path_env_dirs()).is_some() {
+        return binary.to_string();
+    }
+
+    find_binary_in_dirs(binary, search_dirs)
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|| binary.to_string())
+}
+
+/// Full path of a provider CLI, checking PATH before `search_dirs`. Unlike
+/// `resolve_cli_binary` this also expands binaries found on PATH, so a shell with a
+/// different PATH runs the same file.
+pub fn find_cli_binary_path(binary: &str, search_dirs: &[PathBuf]) -> Option<PathBuf> {
+    find_binary_in_dirs(binary, &path_env_dirs())
+        .or_else(|| find_binary_in_dirs(binary, search_dirs))
+}
+
+/// File a resolved CLI binary refers to: the path itself when it names a directory,
+/// otherwise the first match on PATH or in `search_dirs`.
+pub fn locate_cli_binary(binary: &str, search_dirs: &[PathBuf]) -> Option<PathBuf> {
+    let path = Path::new(binary);
+    if path.components().count() > 1 {
+        return path.is_file().then(|| path.to_path_buf());
+    }
+    find_cli_binary_path(binary, search_dirs)
+}
+
+/// First `dir/binary` that exists as a file, in `dirs` order.
+pub fn find_binary_in_dirs(binary: &str, dirs: &[PathBuf]) -> Option<PathBuf> {
+    dirs.iter().find_map(|dir| {
+        binary_file_names(binary)
+            .into_iter()
+            .map(|name| dir.join(name))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+/// Well-known install locations probed when a CLI is not on PATH. Replaced entirely by
+/// `AGENTDOCK_CLI_SEARCH_PATH` when that is set.
+pub fn default_cli_search_dirs() -> Vec<PathBuf> {
+    if let Some(value) = env::var_os(CLI_SEARCH_PATH_ENV) {
+        let dirs: Vec<PathBuf> = env::split_paths(&value)
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .collect();
+        if !dirs.is_empty() {
+            return dirs;
+        }
+    }
+
+    let mut dirs = vec![
+        PathBuf::from("/opt/homebrew/bin"),
+        PathBuf::from("/usr/local/bin"),
+    ];
+    if let Some(prefix) = env::var_os("NPM_CONFIG_PREFIX").filter(|value| !value.is_empty()) {
+        dirs.push(PathBuf::from(prefix).join("bin"));
+    }
+    if let Some(home) = default_home_dir() {
+        dirs.push(home.join(".local").join("bin"));
+        dirs.push(home.join(".npm-global").join("bin"));
+        dirs.push(home.join(".bun").join("bin"));
+    }
+    dirs
+}
+
+/// First version-like token (`1.2`, `v1.2.3`, `1.2.3-beta.1+abc`) in `--version`
+/// output, skipping banner words, build hashes and dates.
+pub fn parse_cli_version(output: &str) -> Option<String> {
+    output
+        .split(|character: char| {
+            character.is_whitespace() || matches!(character, '(' | ')' | '[' | ']' | ',' | ';')
+        })
+        .map(|token| {
+            token
+                .trim_start_matches(['v', 'V'])
+                .trim_end_matches(['.', ':'])
+        })
+        .find(|token| is_version_token(token))
+        .map(ToString::to_string)
+}
+
+/// `parse_cli_version` over stdout, falling back to stderr for CLIs that print there.
+pub fn parse_cli_version_output(output: &Output) -> Option<String> {
+    parse_cli_version(&String::from_utf8_lossy(&output.stdout))
+        .or_else(|| parse_cli_version(&String::from_utf8_lossy(&output.stderr)))
+}
+
+fn is_version_token(token: &str) -> bool {
+    let core = token.split(['-', '+']).next().unwrap_or_default();
+    core.contains('.')
+        && core.split('.').all(|part| {
+            !part.is_empty() && part.chars().all(|character| character.is_ascii_digit())
+        })
+}
+
+fn path_env_dirs() -> Vec<PathBuf> {
+    env::var_os("PATH")
+        .map(|value| env::split_paths(&value).collect())
+        .unwrap_or_default()
+}
+
+fn binary_file_names(binary: &str) -> Vec<String> {
+    if cfg!(target_os = "windows") && Path::new(binary).extension().is_none() {
+        vec![
+            format!("{binary}.exe"),
+            format!("{binary}.cmd"),
+            binary.to_string(),
+        ]
+    } else {
+        vec![binary.to_string()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::process::ExitStatus;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn unique_temp_dir(label: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or(0);
+        let dir = env::temp_dir().join(format!(
+            "agentdock-cli-discovery-{label}-{}-{nanos}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).expect("temp dir should be created");
+        dir
+    }
+
+    #[test]
+    fn find_binary_in_dirs_returns_first_matching_dir() {
+        let empty = unique_temp_dir("empty");
+        let first = unique_temp_dir("first");
+        let second = unique_temp_dir("second");
+        let name = binary_file_names("agentdock-fake-cli")[0].clone();
+        fs::write(first.join(&name), "").expect("binary should be written");
+        fs::write(second.join(&name), "").expect("binary should be written");
+
+        let found = find_binary_in_dirs(
+            "agentdock-fake-cli",
+            &[empty.clone(), first.clone(), second.clone()],
+        );
+
+        assert_eq!(found, Some(first.join(&name)));
+        for dir in [empty, first, second] {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+
+    #[test]
+    fn find_binary_in_dirs_ignores_directories_and_missing_binaries() {
+        let dir = unique_temp_dir("dir-only");
+        fs::create_dir_all(dir.join("agentdock-fake-cli")).expect("dir should be created");
+
+        assert_eq!(
+            find_binary_in_dirs("agentdock-fake-cli", std::slice::from_ref(&dir)),
+            None
+        );
+        assert_eq!(
+            find_binary_in_dirs("missing-cli", std::slice::from_ref(&dir)),
+            None
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn resolve_cli_binary_uses_absolute_path_from_search_dirs() {
+        let dir = unique_temp_dir("resolve");
+        let name = binary_file_names("agentdock-offpath-cli")[0].clone();
+        fs::write(dir.join(&name), "").expect("binary should be written");
+
+        let resolved = resolve_cli_binary("agentdock-offpath-cli", std::slice::from_ref(&dir));
+
+        assert_eq!(PathBuf::from(resolved), dir.join(&name));
+        assert_eq!(
+            resolve_cli_binary("agentdock-missing-cli", std::slice::from_ref(&dir)),
+            "agentdock-missing-cli"
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn locate_cli_binary_checks_explicit_paths_and_search_dirs() {
+        let dir = unique_temp_dir("locate");
+        let name = binary_file_names("agentdock-located-cli")[0].clone();
+        fs::write(dir.join(&name), "").expect("binary should be written");
+        let explicit = dir.join(&name).to_string_lossy().to_string();
+
+        assert_eq!(locate_cli_binary(&explicit, &[]), Some(dir.join(&name)));
+        assert_eq!(
+            locate_cli_binary(
+                &dir.join("missing-cli").to_string_lossy(),
+                std::slice::from_ref(&dir)
+            ),
+            None
+        );
+        assert_eq!(
+            locate_cli_binary("agentdock-located-cli", std::slice::from_ref(&dir)),
+            Some(dir.join(&name))
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn parse_cli_version_finds_version_in_noisy_output() {
+        assert_eq!(
+            parse_cli_version("1.0.30 (Claude Code)").as_deref(),
+            Some("1.0.30")
+        );
+        assert_eq!(
+            parse_cli_version("codex-cli 0.46.0").as_deref(),
+            Some("0.46.0")
+        );
+        assert_eq!(
+            parse_cli_version("Update available!\nopencode v0.15.2\n").as_deref(),
+            Some("0.15.2")
+        );
+        assert_eq!(
+            parse_cli_version("tool 2.1.0-beta.1+build.5 (3f9c2ab 2025-01-01)").as_deref(),
+            Some("2.1.0-beta.1+build.5")
+        );
+        assert_eq!(
+            parse_cli_version("build 3f9c2ab, released 2025-01-01").as_deref(),
+            None
+        );
+        assert_eq!(parse_cli_version(""), None);
+    }
+
+    #[test]
+    fn parse_cli_version_output_falls_back_to_stderr() {
+        let output = |stdout: &str, stderr: &str| Output {
+            status: ExitStatus::default(),
+            stdout: stdout.as_bytes().to_vec(),
+            stderr: stderr.as_bytes().to_vec(),
+        };
+
+        assert_eq!(
+            parse_cli_version_output(&output("rustc 1.82.0 (f6e511eec 2024-10-15)\n", ""))
+                .as_deref(),
+            Some("1.82.0")
+        );
+        assert_eq!(
+            parse_cli_version_output(&output("", "opencode v0.15.2\n")).as_deref(),
+            Some("0.15.2")
+        );
+        assert_eq!(
+            parse_cli_version_output(&output("1.0.30 (Claude Code)\n", "warning: v9.9.9"))
+                .as_deref(),
+            Some("1.0.30")
+        );
+        assert_eq!(parse_cli_version_output(&output("", "")), None);
+    }
+}
@@ -0,0 +1,98 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+/// Overrides the per-file size cap for session files, in bytes.
+pub const MAX_SESSION_FILE_BYTES_ENV: &str = "AGENTDOCK_MAX_SESSION_FILE_BYTES";
+pub const DEFAULT_MAX_SESSION_FILE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// A session file left unread because it is larger than the size cap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OversizedFile {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub max_bytes: u64,
+}
+
+impl fmt::Display for OversizedFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Skipped {}: {} bytes exceeds the {} byte session file limit",
+            self.path.display(),
+            self.size_bytes,
+            self.max_bytes
+        )
+    }
+}
+
+/// `AGENTDOCK_MAX_SESSION_FILE_BYTES` when set to a positive integer, otherwise 64MB.
+pub fn default_max_session_file_bytes() -> u64 {
+    env::var(MAX_SESSION_FILE_BYTES_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_MAX_SESSION_FILE_BYTES)
+}
+
+/// Splits `files` into those within `max_bytes` and the oversized rest. Files whose size
+/// cannot be read are kept so the caller's usual open error handling applies.
+pub fn partition_oversized_files(
+    files: Vec<PathBuf>,
+    max_bytes: u64,
+) -> (Vec<PathBuf>, Vec<OversizedFile>) {
+    let mut kept = Vec::with_capacity(files.len());
+    let mut oversized = Vec::new();
+    for path in files {
+        match fs::metadata(&path) {
+            Ok(metadata) if metadata.len() > max_bytes => oversized.push(OversizedFile {
+                path,
+                size_bytes: metadata.len(),
+                max_bytes,
+            }),
+            _ => kept.push(path),
+        }
+    }
+    (kept, oversized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn partition_oversized_files_skips_files_over_the_cap() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or(0);
+        let dir = env::temp_dir().join(format!(
+            "agentdock-file-limits-{}-{nanos}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).expect("temp dir should be created");
+        let small = dir.join("small.jsonl");
+        let large = dir.join("large.jsonl");
+        let missing = dir.join("missing.jsonl");
+        fs::write(&small, "{}\n").expect("file should be written");
+        fs::write(&large, "x".repeat(64)).expect("file should be written");
+
+        let (kept, oversized) =
+            partition_oversized_files(vec![small.clone(), large.clone(), missing.clone()], 16);
+
+        assert_eq!(kept, vec![small, missing]);
+        assert_eq!(
+            oversized,
+            vec![OversizedFile {
+                path: large,
+                size_bytes: 64,
+                max_bytes: 16,
+            }]
+        );
+        assert!(oversized[0].to_string().contains("exceeds the 16 byte"));
+        let _ = fs::remove_dir_all(dir);
+    }
+}
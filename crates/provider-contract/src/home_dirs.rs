@@ -0,0 +1,136 @@
+use std::env;
+use std::path::PathBuf;
+
+/// The user's home directory. `HOME` wins when set so tests and MSYS shells can
+/// redirect it; on Windows the profile known folder comes next, with `USERPROFILE` and
+/// `HOMEDRIVE`+`HOMEPATH` kept as fallbacks for when the known-folder lookup fails.
+pub fn default_home_dir() -> Option<PathBuf> {
+    resolve_home_dir(known_home_dir(), env_var)
+}
+
+/// `%LOCALAPPDATA%` on Windows, preferring the known folder over the env var.
+pub fn local_app_data_dir() -> Option<PathBuf> {
+    known_local_app_data_dir().or_else(|| env_dir(env_var, "LOCALAPPDATA"))
+}
+
+/// `%APPDATA%` (roaming) on Windows, preferring the known folder over the env var.
+pub fn roaming_app_data_dir() -> Option<PathBuf> {
+    known_roaming_app_data_dir().or_else(|| env_dir(env_var, "APPDATA"))
+}
+
+fn resolve_home_dir<F>(known_home: Option<PathBuf>, env_var: F) -> Option<PathBuf>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if let Some(home) = env_dir(&env_var, "HOME") {
+        return Some(home);
+    }
+    if let Some(home) = known_home {
+        return Some(home);
+    }
+    if let Some(home) = env_dir(&env_var, "USERPROFILE") {
+        return Some(home);
+    }
+
+    let combined = format!("{}{}", env_var("HOMEDRIVE")?, env_var("HOMEPATH")?);
+    if combined.trim().is_empty() {
+        return None;
+    }
+    Some(PathBuf::from(combined))
+}
+
+fn env_dir<F>(env_var: F, key: &str) -> Option<PathBuf>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    env_var(key)
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+fn env_var(key: &str) -> Option<String> {
+    env::var(key).ok()
+}
+
+#[cfg(target_os = "windows")]
+fn known_home_dir() -> Option<PathBuf> {
+    dirs::home_dir()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn known_home_dir() -> Option<PathBuf> {
+    None
+}
+
+#[cfg(target_os = "windows")]
+fn known_local_app_data_dir() -> Option<PathBuf> {
+    dirs::data_local_dir()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn known_local_app_data_dir() -> Option<PathBuf> {
+    None
+}
+
+#[cfg(target_os = "windows")]
+fn known_roaming_app_data_dir() -> Option<PathBuf> {
+    dirs::data_dir()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn known_roaming_app_data_dir() -> Option<PathBuf> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    fn env_from(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let values: HashMap<String, String> = pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        move |key| values.get(key).cloned()
+    }
+
+    #[test]
+    fn resolve_home_dir_prefers_home_then_known_folder_then_env_fallbacks() {
+        let known = Some(PathBuf::from("C:\\Users\\known"));
+        let all = env_from(&[
+            ("HOME", "/home/override"),
+            ("USERPROFILE", "C:\\Users\\profile"),
+            ("HOMEDRIVE", "D:"),
+            ("HOMEPATH", "\\Users\\drive"),
+        ]);
+        assert_eq!(
+            resolve_home_dir(known.clone(), &all),
+            Some(PathBuf::from("/home/override"))
+        );
+
+        let without_home = env_from(&[
+            ("HOME", "  "),
+            ("USERPROFILE", "C:\\Users\\profile"),
+            ("HOMEDRIVE", "D:"),
+            ("HOMEPATH", "\\Users\\drive"),
+        ]);
+        assert_eq!(resolve_home_dir(known.clone(), &without_home), known);
+        assert_eq!(
+            resolve_home_dir(None, &without_home),
+            Some(PathBuf::from("C:\\Users\\profile"))
+        );
+
+        let drive_only = env_from(&[("HOMEDRIVE", "D:"), ("HOMEPATH", "\\Users\\drive")]);
+        assert_eq!(
+            resolve_home_dir(None, &drive_only),
+            Some(PathBuf::from("D:\\Users\\drive"))
+        );
+        assert_eq!(
+            resolve_home_dir(None, env_from(&[("HOMEDRIVE", "D:")])),
+            None
+        );
+    }
+}
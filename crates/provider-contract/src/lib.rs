@@ -1,9 +1,40 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+mod cli_discovery;
+mod file_limits;
+mod home_dirs;
+mod project_path;
+mod redaction;
+mod session_scan;
+mod worktree;
+
+pub use cli_discovery::{
+    default_cli_search_dirs, find_binary_in_dirs, find_cli_binary_path, locate_cli_binary,
+    parse_cli_version, parse_cli_version_output, resolve_cli_binary, CLI_SEARCH_PATH_ENV,
+};
+pub use file_limits::{
+    default_max_session_file_bytes, partition_oversized_files, OversizedFile,
+    DEFAULT_MAX_SESSION_FILE_BYTES, MAX_SESSION_FILE_BYTES_ENV,
+};
+pub use home_dirs::{default_home_dir, local_app_data_dir, roaming_app_data_dir};
+pub use project_path::{build_thread_tags, resolve_project_path, UNRESOLVED_PROJECT_PATH_TAG};
+pub use redaction::{redact_secrets, REDACTED_PLACEHOLDER};
+pub use session_scan::{
+    collect_files_with_extension, default_scan_timeout, file_stem_lossy, find_last_line_map,
+    verify_session_file, ScanDeadline, ThreadScan, SCAN_TIMEOUT_MS_ENV,
+};
+pub use worktree::{git_worktree_root, group_project_path, same_git_worktree};
 
 pub type ProviderResult<T> = Result<T, ProviderError>;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum ProviderId {
     Codex,
@@ -30,6 +61,7 @@ pub enum ProviderErrorCode {
     PermissionDenied,
     Timeout,
     UpstreamUnavailable,
+    RateLimited,
     InvalidResponse,
     NotImplemented,
     Unknown,
@@ -51,6 +83,49 @@ impl ProviderError {
             retryable: false,
         }
     }
+
+    /// Error for a provider CLI that ran but failed. Rate-limit output (Claude
+    /// `overloaded_error`, HTTP 429 phrasing) maps to `RateLimited`; anything else to
+    /// `UpstreamUnavailable`. Both are retryable.
+    pub fn from_cli_failure(context: impl Into<String>, stderr: &str) -> Self {
+        let context = context.into();
+        let stderr = stderr.trim();
+        let code = if is_rate_limit_output(stderr) {
+            ProviderErrorCode::RateLimited
+        } else {
+            ProviderErrorCode::UpstreamUnavailable
+        };
+        Self {
+            code,
+            message: if stderr.is_empty() {
+                context
+            } else {
+                format!("{context}: {stderr}")
+            },
+            retryable: true,
+        }
+    }
+}
+
+const RATE_LIMIT_MARKERS: &[&str] = &[
+    "overloaded_error",
+    "rate_limit_error",
+    "rate limit",
+    "rate-limit",
+    "ratelimit",
+    "too many requests",
+    "status 429",
+    "status code 429",
+    "http 429",
+    "error 429",
+    "\"status\":429",
+];
+
+fn is_rate_limit_output(output: &str) -> bool {
+    let normalized = output.to_ascii_lowercase();
+    RATE_LIMIT_MARKERS
+        .iter()
+        .any(|marker| normalized.contains(marker))
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -61,6 +136,75 @@ pub enum ProviderHealthStatus {
     Offline,
 }
 
+/// Milliseconds since the Unix epoch. Serialized as a plain JSON number.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    /// Builds a timestamp from a Unix epoch value in seconds or milliseconds.
+    pub fn from_epoch(raw: i64) -> Self {
+        if raw.abs() < 1_000_000_000_000 {
+            Self(raw.saturating_mul(1000))
+        } else {
+            Self(raw)
+        }
+    }
+
+    pub fn from_epoch_millis(millis: i64) -> Self {
+        Self(millis)
+    }
+
+    pub fn from_rfc3339(value: &str) -> Option<Self> {
+        let parsed = OffsetDateTime::parse(value.trim(), &Rfc3339).ok()?;
+        Some(Self((parsed.unix_timestamp_nanos() / 1_000_000) as i64))
+    }
+
+    pub fn now() -> Self {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or(0);
+        Self(millis)
+    }
+
+    pub fn as_millis(self) -> i64 {
+        self.0
+    }
+}
+
+/// Source of the current time for the agent activity window in runtime state. Adapters
+/// default to `SystemClock`; tests can pass a `FixedClock` through `with_clock`.
+pub trait Clock: fmt::Debug + Send + Sync {
+    fn now(&self) -> Timestamp;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        Timestamp::now()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub Timestamp);
+
+impl Clock for FixedClock {
+    fn now(&self) -> Timestamp {
+        self.0
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.0)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ProviderHealthCheckRequest {
     pub profile_name: String,
@@ -71,7 +215,7 @@ pub struct ProviderHealthCheckRequest {
 pub struct ProviderHealthCheckResult {
     pub provider_id: ProviderId,
     pub status: ProviderHealthStatus,
-    pub checked_at: String,
+    pub checked_at: Timestamp,
     pub message: Option<String>,
 }
 
@@ -81,16 +225,113 @@ pub struct ThreadSummary {
     pub provider_id: ProviderId,
     pub account_id: Option<String>,
     pub project_path: String,
+    /// Recorded working directory when `project_path` was normalized to something else,
+    /// such as the enclosing git repository root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_cwd: Option<String>,
+    /// Model recorded for the session, when the provider stores one (OpenCode).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Upstream model provider behind the session, e.g. `anthropic` for OpenCode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_backend: Option<String>,
     pub title: String,
     pub tags: Vec<String>,
-    pub last_active_at: String,
+    pub last_active_at: Timestamp,
 }
 
+impl ThreadSummary {
+    /// Directory the session was recorded in, which is where its provider looks it up on
+    /// resume: `original_cwd` when `project_path` was normalized, and `None` when no
+    /// directory was recorded (`.`).
+    pub fn session_cwd(&self) -> Option<&str> {
+        Some(self.original_cwd.as_deref().unwrap_or(&self.project_path)).filter(|path| *path != ".")
+    }
+}
+
+/// One classified session event (`user_message`, `agent_tool`, ...) in file order.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ThreadEvent {
+    pub kind: String,
+    pub at_ms: Option<i64>,
+    /// Original timestamp string (e.g. RFC3339 with offset) when the provider wrote one, so
+    /// the UI can show the original timezone and precision.
+    #[serde(default)]
+    pub timestamp_raw: Option<String>,
+}
+
+/// Per-call options for the adapters' `list_thread_overviews_with_options`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ThreadOverviewOptions {
+    /// Also classify each thread's latest event, at the cost of another read per session.
+    pub include_runtime_state: bool,
+    /// Also count each thread's visible messages, another pass over every session.
+    pub include_message_count: bool,
+}
+
+/// Message count, time span and edited files of one session, used to compare threads.
+/// Adapters also compute `message_count` on its own, in a lighter pass that skips the
+/// rest of the activity.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ThreadActivity {
+    pub message_count: usize,
+    pub started_at_ms: Option<i64>,
+    pub ended_at_ms: Option<i64>,
+    /// Files written by edit tools, in first-touched order without duplicates.
+    pub touched_files: Vec<String>,
+}
+
+impl ThreadActivity {
+    pub fn duration_ms(&self) -> Option<i64> {
+        Some(self.ended_at_ms? - self.started_at_ms?)
+    }
+
+    pub fn record_timestamp(&mut self, at_ms: Option<i64>) {
+        let Some(at_ms) = at_ms else {
+            return;
+        };
+        self.started_at_ms = Some(self.started_at_ms.map_or(at_ms, |start| start.min(at_ms)));
+        self.ended_at_ms = Some(self.ended_at_ms.map_or(at_ms, |end| end.max(at_ms)));
+    }
+
+    pub fn record_touched_file(&mut self, path: &str) {
+        let path = path.trim();
+        if !path.is_empty() && !self.touched_files.iter().any(|touched| touched == path) {
+            self.touched_files.push(path.to_string());
+        }
+    }
+}
+
+/// Locations an adapter resolved from its builder overrides, environment and defaults.
+/// Auth is reported by where it comes from, never by value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderConfigSummary {
+    pub config_dir: PathBuf,
+    pub sessions_dir: PathBuf,
+    /// CLI name or path the adapter spawns.
+    pub cli_binary: String,
+    /// File `cli_binary` resolves to, when it exists.
+    pub cli_binary_path: Option<PathBuf>,
+    /// Env var or file holding credentials, e.g. `ANTHROPIC_API_KEY` or `auth.json`.
+    pub auth_source: Option<String>,
+}
+
+impl ProviderConfigSummary {
+    pub fn config_dir_exists(&self) -> bool {
+        self.config_dir.is_dir()
+    }
+
+    pub fn sessions_dir_exists(&self) -> bool {
+        self.sessions_dir.is_dir()
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ResumeThreadRequest {
     pub thread_id: String,
     pub project_path: Option<String>,
+    #[serde(default)]
+    pub context_summary: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -108,5 +349,118 @@ pub trait ProviderAdapter: Send + Sync {
     ) -> ProviderResult<ProviderHealthCheckResult>;
     fn list_threads(&self, project_path: Option<&str>) -> ProviderResult<Vec<ThreadSummary>>;
     fn resume_thread(&self, request: ResumeThreadRequest) -> ProviderResult<ResumeThreadResult>;
+    /// Runs the CLI's `--version` once. `None` when it ran but printed no recognizable
+    /// version.
+    fn provider_version(&self) -> ProviderResult<Option<String>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    #[test]
+    fn session_cwd_prefers_original_cwd_and_skips_unknown_paths() {
+        let mut summary = ThreadSummary {
+            id: "a".to_string(),
+            provider_id: ProviderId::Codex,
+            account_id: None,
+            project_path: "/repo".to_string(),
+            original_cwd: Some("/repo/packages/web".to_string()),
+            model: None,
+            provider_backend: None,
+            title: "A".to_string(),
+            tags: Vec::new(),
+            last_active_at: Timestamp::from_epoch_millis(0),
+        };
+        assert_eq!(summary.session_cwd(), Some("/repo/packages/web"));
+
+        summary.original_cwd = None;
+        assert_eq!(summary.session_cwd(), Some("/repo"));
+        summary.project_path = ".".to_string();
+        assert_eq!(summary.session_cwd(), None);
+    }
+
+    #[test]
+    fn timestamp_serializes_as_number() {
+        let value = serde_json::to_value(Timestamp::from_epoch_millis(1_700_000_000_123))
+            .expect("timestamp should serialize");
+        assert_eq!(value, serde_json::json!(1_700_000_000_123_i64));
+
+        let parsed: Timestamp =
+            serde_json::from_value(value).expect("timestamp should deserialize");
+        assert_eq!(parsed.as_millis(), 1_700_000_000_123);
+    }
+
+    #[test]
+    fn timestamp_from_epoch_normalizes_seconds() {
+        assert_eq!(
+            Timestamp::from_epoch(1_700_000_000).as_millis(),
+            1_700_000_000_000
+        );
+        assert_eq!(
+            Timestamp::from_epoch(1_700_000_000_123).as_millis(),
+            1_700_000_000_123
+        );
+    }
+
+    #[test]
+    fn timestamp_round_trips_from_rfc3339_and_epoch() {
+        let from_rfc3339 =
+            Timestamp::from_rfc3339("2023-11-14T22:13:20.123Z").expect("valid rfc3339");
+        let from_epoch = Timestamp::from_epoch(1_700_000_000_123);
+        assert_eq!(from_rfc3339, from_epoch);
+
+        let encoded = serde_json::to_string(&from_rfc3339).expect("timestamp should serialize");
+        let decoded: Timestamp = serde_json::from_str(&encoded).expect("should deserialize");
+        assert_eq!(decoded, from_epoch);
+        assert!(Timestamp::from_rfc3339("not a timestamp").is_none());
+    }
+
+    #[test]
+    fn cli_failure_classifies_rate_limit_output() {
+        let overloaded = ProviderError::from_cli_failure(
+            "Claude Code CLI exited with status 1",
+            r#"API Error: {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#,
+        );
+        assert_eq!(overloaded.code, ProviderErrorCode::RateLimited);
+        assert!(overloaded.retryable);
+
+        let http = ProviderError::from_cli_failure("codex exited", "HTTP 429 Too Many Requests");
+        assert_eq!(http.code, ProviderErrorCode::RateLimited);
+    }
+
+    #[test]
+    fn cli_failure_defaults_to_upstream_unavailable() {
+        let error = ProviderError::from_cli_failure(
+            "Codex CLI exited with status 2",
+            "error: unexpected argument '--bogus' found\n",
+        );
+
+        assert_eq!(error.code, ProviderErrorCode::UpstreamUnavailable);
+        assert!(error.retryable);
+        assert_eq!(
+            error.message,
+            "Codex CLI exited with status 2: error: unexpected argument '--bogus' found"
+        );
+    }
+
+    #[test]
+    fn thread_activity_tracks_span_and_dedupes_touched_files() {
+        let mut activity = ThreadActivity::default();
+        assert_eq!(activity.duration_ms(), None);
+
+        activity.record_timestamp(Some(2_000));
+        activity.record_timestamp(None);
+        activity.record_timestamp(Some(1_000));
+        activity.record_timestamp(Some(5_000));
+        activity.record_touched_file("src/lib.rs");
+        activity.record_touched_file(" ");
+        activity.record_touched_file("README.md");
+        activity.record_touched_file("src/lib.rs");
+
+        assert_eq!(activity.started_at_ms, Some(1_000));
+        assert_eq!(activity.ended_at_ms, Some(5_000));
+        assert_eq!(activity.duration_ms(), Some(4_000));
+        assert_eq!(activity.touched_files, vec!["src/lib.rs", "README.md"]);
+    }
 }
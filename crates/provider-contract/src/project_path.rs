@@ -0,0 +1,80 @@
+use std::path::{Component, Path, PathBuf};
+
+/// Tag added to threads whose session never recorded a usable cwd.
+pub const UNRESOLVED_PROJECT_PATH_TAG: &str = "unresolved_project_path";
+
+/// Project path of a session cwd. A missing or blank cwd becomes `.`, and a relative one
+/// is joined onto `project_path_base` when given.
+pub fn resolve_project_path(cwd: Option<String>, project_path_base: Option<&Path>) -> String {
+    let cwd = match cwd {
+        Some(cwd) if !cwd.trim().is_empty() => cwd,
+        _ => return ".".to_string(),
+    };
+    if Path::new(&cwd).is_absolute() {
+        return cwd;
+    }
+
+    match project_path_base {
+        Some(base) => normalize_path_lexically(&base.join(cwd.trim()))
+            .to_string_lossy()
+            .into_owned(),
+        None => cwd,
+    }
+}
+
+/// Provider tag, plus `UNRESOLVED_PROJECT_PATH_TAG` for the `.` project path.
+pub fn build_thread_tags(provider_tag: &str, project_path: &str) -> Vec<String> {
+    let mut tags = vec![provider_tag.to_string()];
+    if project_path == "." {
+        tags.push(UNRESOLVED_PROJECT_PATH_TAG.to_string());
+    }
+    tags
+}
+
+fn normalize_path_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_project_path_joins_relative_cwds_onto_the_base() {
+        let base = Path::new("/workspace/base");
+
+        assert_eq!(resolve_project_path(None, Some(base)), ".");
+        assert_eq!(
+            resolve_project_path(Some("  ".to_string()), Some(base)),
+            "."
+        );
+        assert_eq!(
+            resolve_project_path(Some("/abs/path".to_string()), Some(base)),
+            "/abs/path"
+        );
+        assert_eq!(
+            resolve_project_path(Some("./app/../api".to_string()), Some(base)),
+            Path::new("/workspace/base/api").to_string_lossy()
+        );
+        assert_eq!(resolve_project_path(Some("app".to_string()), None), "app");
+    }
+
+    #[test]
+    fn build_thread_tags_flags_unresolved_project_paths() {
+        assert_eq!(build_thread_tags("codex", "/workspace/app"), vec!["codex"]);
+        assert_eq!(
+            build_thread_tags("codex", "."),
+            vec!["codex", UNRESOLVED_PROJECT_PATH_TAG]
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::env;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Bounds a whole session scan, in milliseconds. Scans are unbounded when unset.
+pub const SCAN_TIMEOUT_MS_ENV: &str = "AGENTDOCK_SCAN_TIMEOUT_MS";
+// Upper bound on directories visited in one session-file scan.
+const MAX_SCANNED_DIRS: usize = 10_000;
+const TAIL_CHUNK_BYTES: u64 = 64 * 1024;
+
+/// `AGENTDOCK_SCAN_TIMEOUT_MS` when set to a positive integer.
+pub fn default_scan_timeout() -> Option<Duration> {
+    env::var(SCAN_TIMEOUT_MS_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .map(Duration::from_millis)
+}
+
+/// Deadline shared by the directory walk and the per-file parsing of one scan. Once it
+/// has passed, `expired` keeps returning true and `timed_out` reports the cut-off.
+#[derive(Debug)]
+pub struct ScanDeadline {
+    at: Option<Instant>,
+    timed_out: Cell<bool>,
+}
+
+impl ScanDeadline {
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            at: timeout.map(|timeout| Instant::now() + timeout),
+            timed_out: Cell::new(false),
+        }
+    }
+
+    pub fn unbounded() -> Self {
+        Self::new(None)
+    }
+
+    pub fn expired(&self) -> bool {
+        if !self.timed_out.get() && self.at.is_some_and(|at| Instant::now() >= at) {
+            self.timed_out.set(true);
+        }
+        self.timed_out.get()
+    }
+
+    pub fn timed_out(&self) -> bool {
+        self.timed_out.get()
+    }
+}
+
+/// Threads from one scan. `timed_out` marks a partial list cut short by the scan timeout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadScan<T> {
+    pub threads: Vec<T>,
+    pub timed_out: bool,
+}
+
+/// File stem of `path`, decoded lossily so a session file with a non-UTF-8 name still
+/// yields an id instead of being dropped.
+pub fn file_stem_lossy(path: &Path) -> Option<String> {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+}
+
+/// Checks, right before offering a resume command, that the session file located for a
+/// thread is still on disk and not empty. The reason names `provider_name`.
+pub fn verify_session_file(provider_name: &str, path: Option<&Path>) -> Result<(), String> {
+    let Some(path) = path else {
+        return Err(format!("{provider_name} session file no longer exists"));
+    };
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() && metadata.len() > 0 => Ok(()),
+        Ok(_) => Err(format!(
+            "{provider_name} session file is empty: {}",
+            path.display()
+        )),
+        Err(_) => Err(format!(
+            "{provider_name} session file no longer exists: {}",
+            path.display()
+        )),
+    }
+}
+
+/// Scans a JSONL file from the end and returns the first line `map` turns into `Some`,
+/// reading backwards in fixed-size chunks so large sessions are not read in full.
+pub fn find_last_line_map<T, F>(path: &Path, mut map: F) -> Option<T>
+where
+    F: FnMut(&str) -> Option<T>,
+{
+    let mut file = File::open(path).ok()?;
+    let mut position = file.metadata().ok()?.len();
+    let mut pending: Vec<u8> = Vec::new();
+
+    while position > 0 {
+        let read_size = TAIL_CHUNK_BYTES.min(position);
+        position -= read_size;
+        file.seek(SeekFrom::Start(position)).ok()?;
+        let mut chunk = vec![0_u8; read_size as usize];
+        file.read_exact(&mut chunk).ok()?;
+        chunk.extend_from_slice(&pending);
+        pending = chunk;
+
+        while let Some(index) = pending.iter().rposition(|byte| *byte == b'\n') {
+            let line = pending.split_off(index + 1);
+            pending.truncate(index);
+            if let Some(value) = map_tail_line(&line, &mut map) {
+                return Some(value);
+            }
+        }
+    }
+
+    map_tail_line(&pending, &mut map)
+}
+
+fn map_tail_line<T, F>(line: &[u8], map: &mut F) -> Option<T>
+where
+    F: FnMut(&str) -> Option<T>,
+{
+    let line = String::from_utf8_lossy(line);
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    map(trimmed)
+}
+
+/// Files ending in `.{extension}` under `root`, stopping with what was found so far once
+/// `deadline` expires. Directories are tracked by canonical path so a symlink cycle is
+/// walked only once.
+pub fn collect_files_with_extension(
+    root: &Path,
+    extension: &str,
+    deadline: &ScanDeadline,
+) -> Vec<PathBuf> {
+    let mut output = Vec::new();
+    collect_with_reader(
+        &FsDirReader,
+        root,
+        extension,
+        deadline,
+        &mut output,
+        &mut HashSet::new(),
+    );
+    output
+}
+
+trait DirReader {
+    fn canonicalize(&self, dir: &Path) -> Option<PathBuf>;
+    /// Entries of `dir` with whether each one is a directory.
+    fn entries(&self, dir: &Path) -> Vec<(PathBuf, bool)>;
+}
+
+struct FsDirReader;
+
+impl DirReader for FsDirReader {
+    fn canonicalize(&self, dir: &Path) -> Option<PathBuf> {
+        fs::canonicalize(dir).ok()
+    }
+
+    fn entries(&self, dir: &Path) -> Vec<(PathBuf, bool)> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        entries
+            .flatten()
+            .map(|entry| {
+                let path = entry.path();
+                let is_dir = path.is_dir();
+                (path, is_dir)
+            })
+            .collect()
+    }
+}
+
+fn collect_with_reader<R: DirReader>(
+    reader: &R,
+    root: &Path,
+    extension: &str,
+    deadline: &ScanDeadline,
+    output: &mut Vec<PathBuf>,
+    visited_dirs: &mut HashSet<PathBuf>,
+) {
+    if deadline.expired() {
+        return;
+    }
+    let Some(canonical_root) = reader.canonicalize(root) else {
+        return;
+    };
+    if visited_dirs.len() >= MAX_SCANNED_DIRS || !visited_dirs.insert(canonical_root) {
+        return;
+    }
+
+    for (path, is_dir) in reader.entries(root) {
+        if is_dir {
+            collect_with_reader(reader, &path, extension, deadline, output, visited_dirs);
+            if deadline.timed_out() {
+                return;
+            }
+            continue;
+        }
+        if path.extension().is_some_and(|value| value == extension) {
+            output.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn unique_temp_dir(label: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or(0);
+        let dir = env::temp_dir().join(format!(
+            "agentdock-session-scan-{label}-{}-{nanos}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).expect("temp dir should be created");
+        dir
+    }
+
+    struct SlowDirReader {
+        delay: Duration,
+    }
+
+    // `/sessions` holds `day-0` .. `day-4`, each with one session file; listing a day
+    // directory takes `delay`.
+    impl DirReader for SlowDirReader {
+        fn canonicalize(&self, dir: &Path) -> Option<PathBuf> {
+            Some(dir.to_path_buf())
+        }
+
+        fn entries(&self, dir: &Path) -> Vec<(PathBuf, bool)> {
+            if dir == Path::new("/sessions") {
+                return (0..5)
+                    .map(|day| (dir.join(format!("day-{day}")), true))
+                    .collect();
+            }
+            thread::sleep(self.delay);
+            vec![(dir.join("session.jsonl"), false)]
+        }
+    }
+
+    fn collect_slow(deadline: &ScanDeadline) -> Vec<PathBuf> {
+        let mut output = Vec::new();
+        collect_with_reader(
+            &SlowDirReader {
+                delay: Duration::from_millis(40),
+            },
+            Path::new("/sessions"),
+            "jsonl",
+            deadline,
+            &mut output,
+            &mut HashSet::new(),
+        );
+        output
+    }
+
+    #[test]
+    fn collect_stops_with_partial_results_once_deadline_passes() {
+        let deadline = ScanDeadline::new(Some(Duration::from_millis(100)));
+
+        let files = collect_slow(&deadline);
+
+        assert!(deadline.timed_out());
+        assert!(!files.is_empty());
+        assert!(files.len() < 5, "scan should stop early, got {files:?}");
+        assert_eq!(files[0], PathBuf::from("/sessions/day-0/session.jsonl"));
+    }
+
+    #[test]
+    fn collect_without_deadline_walks_every_directory() {
+        let deadline = ScanDeadline::unbounded();
+
+        assert_eq!(collect_slow(&deadline).len(), 5);
+        assert!(!deadline.timed_out());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn collect_files_with_extension_terminates_on_symlink_cycle() {
+        let root = unique_temp_dir("symlink-cycle").join("sessions");
+        let nested = root.join("nested");
+        fs::create_dir_all(&nested).expect("nested dir should be creatable");
+        fs::write(nested.join("session.jsonl"), "{}\n").expect("session file should be writable");
+        std::os::unix::fs::symlink(&root, nested.join("loop"))
+            .expect("symlink should be creatable");
+
+        let files = collect_files_with_extension(&root, "jsonl", &ScanDeadline::unbounded());
+
+        assert_eq!(files, vec![nested.join("session.jsonl")]);
+    }
+
+    #[test]
+    fn find_last_line_map_returns_last_match_across_chunks() {
+        let dir = unique_temp_dir("tail");
+        let path = dir.join("session.jsonl");
+        let filler = "x".repeat(TAIL_CHUNK_BYTES as usize);
+        fs::write(&path, format!("match-1\n{filler}\nmatch-2\n{filler}\n\n"))
+            .expect("session file should be written");
+
+        let found = find_last_line_map(&path, |line| line.strip_prefix("match-").map(String::from));
+
+        assert_eq!(found.as_deref(), Some("2"));
+        assert_eq!(find_last_line_map(&path, |_| None::<()>), None);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn verify_session_file_rejects_missing_and_empty_files() {
+        let dir = unique_temp_dir("verify");
+        let present = dir.join("present.jsonl");
+        let empty = dir.join("empty.jsonl");
+        fs::write(&present, "{}\n").expect("session file should be written");
+        fs::write(&empty, "").expect("session file should be written");
+
+        assert!(verify_session_file("Codex", Some(&present)).is_ok());
+        let empty_reason =
+            verify_session_file("Codex", Some(&empty)).expect_err("empty file should fail");
+        assert!(empty_reason.starts_with("Codex session file is empty"));
+        let removed_reason = verify_session_file("Codex", Some(&dir.join("removed.jsonl")))
+            .expect_err("removed file should fail");
+        assert!(removed_reason.contains("no longer exists"));
+        assert!(verify_session_file("Codex", None).is_err());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,104 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Root of the git worktree containing `path`: the nearest ancestor with a `.git` entry.
+/// That entry is a directory in the main worktree and a `gitdir:` file in linked ones, so
+/// a linked worktree nested inside the main checkout still resolves to itself.
+pub fn git_worktree_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| ancestor.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Swaps the project path for its git worktree root, returning the recorded cwd alongside
+/// when they differ. Paths outside a repository pass through unchanged.
+pub fn group_project_path(
+    project_path: String,
+    group_by_git_root: bool,
+) -> (String, Option<String>) {
+    if !group_by_git_root || project_path == "." {
+        return (project_path, None);
+    }
+
+    match git_worktree_root(Path::new(&project_path)) {
+        Some(root) if root.as_path() != Path::new(&project_path) => {
+            (root.to_string_lossy().into_owned(), Some(project_path))
+        }
+        _ => (project_path, None),
+    }
+}
+
+/// Whether `path` lies in the same worktree as `worktree_path`, after resolving symlinks.
+/// Sibling worktrees of one repository do not match. Outside git, `path` has to be
+/// `worktree_path` or below it.
+pub fn same_git_worktree(path: &Path, worktree_path: &Path) -> bool {
+    let path = canonicalize_or_keep(path);
+    let worktree_path = canonicalize_or_keep(worktree_path);
+    match (git_worktree_root(&path), git_worktree_root(&worktree_path)) {
+        (Some(root), Some(worktree_root)) => root == worktree_root,
+        (None, None) => path.starts_with(&worktree_path),
+        _ => false,
+    }
+}
+
+fn canonicalize_or_keep(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn same_git_worktree_tells_main_worktree_from_linked_worktrees() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or(0);
+        let dir =
+            env::temp_dir().join(format!("agentdock-worktree-{}-{nanos}", std::process::id()));
+        let repo = dir.join("repo");
+        let sibling = dir.join("repo-feature");
+        let nested = repo.join(".worktrees").join("fix");
+        fs::create_dir_all(repo.join(".git").join("worktrees")).expect("git dir should exist");
+        for (worktree, name) in [(&sibling, "feature"), (&nested, "fix")] {
+            fs::create_dir_all(worktree.join("src")).expect("worktree should be created");
+            fs::write(
+                worktree.join(".git"),
+                format!(
+                    "gitdir: {}\n",
+                    repo.join(".git/worktrees").join(name).display()
+                ),
+            )
+            .expect("gitdir file should be written");
+        }
+        fs::create_dir_all(repo.join("src")).expect("src dir should be created");
+
+        assert!(same_git_worktree(&repo.join("src"), &repo));
+        assert!(!same_git_worktree(&sibling.join("src"), &repo));
+        assert!(!same_git_worktree(&nested.join("src"), &repo));
+        assert!(same_git_worktree(&nested.join("src"), &nested));
+        assert!(!same_git_worktree(&repo, &nested));
+        assert_eq!(
+            git_worktree_root(&sibling.join("src")),
+            Some(sibling.clone())
+        );
+
+        let sibling_src = sibling.join("src").to_string_lossy().into_owned();
+        assert_eq!(
+            group_project_path(sibling_src.clone(), true),
+            (
+                sibling.to_string_lossy().into_owned(),
+                Some(sibling_src.clone())
+            )
+        );
+        assert_eq!(
+            group_project_path(sibling_src.clone(), false),
+            (sibling_src, None)
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -1,15 +1,20 @@
 use provider_contract::{
-    ProviderAdapter, ProviderError, ProviderErrorCode, ProviderHealthCheckRequest,
+    collect_files_with_extension, default_cli_search_dirs, default_home_dir,
+    default_max_session_file_bytes, default_scan_timeout, file_stem_lossy, local_app_data_dir,
+    locate_cli_binary, parse_cli_version_output, partition_oversized_files, redact_secrets,
+    resolve_cli_binary, verify_session_file, Clock, OversizedFile, ProviderAdapter,
+    ProviderConfigSummary, ProviderError, ProviderErrorCode, ProviderHealthCheckRequest,
     ProviderHealthCheckResult, ProviderHealthStatus, ProviderId, ProviderResult,
-    ResumeThreadRequest, ResumeThreadResult, ThreadSummary,
+    ResumeThreadRequest, ResumeThreadResult, ScanDeadline, ThreadActivity, ThreadEvent,
+    ThreadOverviewOptions, ThreadScan, ThreadSummary, Timestamp,
 };
 use serde_json::Value;
-use std::cmp::Reverse;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
 
 const OPENCODE_DATA_DIR_ENV: &str = "AGENTDOCK_OPENCODE_DATA_DIR";
 const OPENCODE_BINARY_ENV: &str = "AGENTDOCK_OPENCODE_BIN";
@@ -19,13 +24,20 @@ const OPENCODE_AGENT_ACTIVITY_WINDOW_MS: i64 = 120_000;
 struct ThreadRecord {
     summary: ThreadSummary,
     session_id: String,
+    source_path: PathBuf,
     sort_key: i64,
+    /// The session has a `directory`, or a project whose worktree is known, even `.`.
+    has_recorded_cwd: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OpenCodeThreadOverview {
     pub summary: ThreadSummary,
     pub last_message_preview: Option<String>,
+    /// Only set when requested through `ThreadOverviewOptions::include_runtime_state`.
+    pub runtime_state: Option<OpenCodeThreadRuntimeState>,
+    /// Only set when requested through `ThreadOverviewOptions::include_message_count`.
+    pub message_count: Option<usize>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -71,6 +83,12 @@ struct OpenCodeMessageNode {
 pub struct OpenCodeAdapter {
     data_dir_override: Option<PathBuf>,
     cli_binary_override: Option<String>,
+    cli_search_dirs: Option<Vec<PathBuf>>,
+    clock: Option<Arc<dyn Clock>>,
+    max_session_file_bytes: Option<u64>,
+    hide_empty_threads: bool,
+    redaction_disabled: bool,
+    scan_timeout: Option<Duration>,
 }
 
 impl OpenCodeAdapter {
@@ -88,6 +106,50 @@ impl OpenCodeAdapter {
         self
     }
 
+    /// Install directories probed when the CLI is not on PATH. Defaults to
+    /// `default_cli_search_dirs()`.
+    pub fn with_cli_search_dirs<I, P>(mut self, dirs: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.cli_search_dirs = Some(dirs.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Overrides the `Clock`; see there.
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Some(Arc::new(clock));
+        self
+    }
+
+    /// Session files larger than this are skipped instead of read. Defaults to
+    /// `default_max_session_file_bytes()`.
+    pub fn with_max_session_file_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_session_file_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Stops session scans after `timeout`, keeping the threads read so far. Defaults to
+    /// `default_scan_timeout()`.
+    pub fn with_scan_timeout(mut self, timeout: Duration) -> Self {
+        self.scan_timeout = Some(timeout);
+        self
+    }
+
+    /// Omit never-used sessions from `list_threads`: no parseable messages and no
+    /// recorded project directory.
+    pub fn with_hide_empty_threads(mut self, enabled: bool) -> Self {
+        self.hide_empty_threads = enabled;
+        self
+    }
+
+    /// Run message previews through `redact_secrets`. On by default.
+    pub fn with_redaction(mut self, enabled: bool) -> Self {
+        self.redaction_disabled = !enabled;
+        self
+    }
+
     pub fn get_thread_runtime_state(
         &self,
         thread_id: &str,
@@ -96,28 +158,127 @@ impl OpenCodeAdapter {
         Ok(load_thread_runtime_state(
             &self.opencode_storage_dir(),
             thread_id,
+            self.now_ms(),
+        ))
+    }
+
+    /// Checks that a session file listing `thread_id` is still on disk, matched the way
+    /// `list_threads` reads it.
+    pub fn thread_exists(&self, thread_id: &str) -> bool {
+        self.find_session_record(thread_id).is_some()
+    }
+
+    /// Session files left out of thread scans by the size cap.
+    pub fn oversized_session_files(&self) -> Vec<OversizedFile> {
+        self.collect_session_files(&self.scan_deadline()).1
+    }
+
+    pub fn get_thread_activity(&self, thread_id: &str) -> ProviderResult<ThreadActivity> {
+        self.find_thread_record(thread_id)?;
+        Ok(load_thread_activity(
+            &self.opencode_storage_dir(),
+            thread_id,
+        ))
+    }
+
+    /// Last `limit` semantic events of the session, oldest first.
+    pub fn get_thread_event_log(
+        &self,
+        thread_id: &str,
+        limit: usize,
+    ) -> ProviderResult<Vec<ThreadEvent>> {
+        self.find_thread_record(thread_id)?;
+        Ok(load_thread_event_log(
+            &self.opencode_storage_dir(),
+            thread_id,
+            limit,
         ))
     }
 
+    pub fn get_latest_message_preview(&self, thread_id: &str) -> ProviderResult<Option<String>> {
+        self.find_thread_record(thread_id)?;
+        Ok(self.redact(build_latest_message_preview(
+            &self.opencode_storage_dir(),
+            thread_id,
+        )))
+    }
+
+    /// Text of the last assistant reply, skipping trailing tool calls and results. Not
+    /// truncated, unlike the latest message preview.
+    pub fn get_final_assistant_message(&self, thread_id: &str) -> ProviderResult<Option<String>> {
+        self.find_thread_record(thread_id)?;
+        Ok(self.redact(build_final_assistant_message(
+            &self.opencode_storage_dir(),
+            thread_id,
+        )))
+    }
+
+    /// Resolved data dir, sessions dir, CLI binary and credential source.
+    pub fn config_summary(&self) -> ProviderConfigSummary {
+        let cli_binary = self.opencode_binary();
+        let config_dir = self.opencode_data_dir();
+        ProviderConfigSummary {
+            auth_source: config_dir
+                .join("auth.json")
+                .is_file()
+                .then(|| "auth.json".to_string()),
+            sessions_dir: self.opencode_sessions_dir(),
+            cli_binary_path: locate_cli_binary(&cli_binary, &self.cli_search_dirs()),
+            cli_binary,
+            config_dir,
+        }
+    }
+
     pub fn list_thread_overviews(
         &self,
         project_path: Option<&str>,
     ) -> ProviderResult<Vec<OpenCodeThreadOverview>> {
-        let mut records = self.scan_thread_records();
+        self.list_thread_overviews_with_options(project_path, ThreadOverviewOptions::default())
+    }
+
+    pub fn list_thread_overviews_with_options(
+        &self,
+        project_path: Option<&str>,
+        options: ThreadOverviewOptions,
+    ) -> ProviderResult<Vec<OpenCodeThreadOverview>> {
+        Ok(self.scan_thread_overviews(project_path, options)?.threads)
+    }
+
+    /// Like `list_thread_overviews_with_options`, but reports whether the scan timeout cut
+    /// the list short.
+    pub fn scan_thread_overviews(
+        &self,
+        project_path: Option<&str>,
+        options: ThreadOverviewOptions,
+    ) -> ProviderResult<ThreadScan<OpenCodeThreadOverview>> {
+        let now_ms = self.now_ms();
+        let deadline = self.scan_deadline();
+        let mut records = self.scan_thread_records_until(&deadline);
 
         if let Some(filter) = project_path {
             records.retain(|record| record.summary.project_path.starts_with(filter));
         }
 
-        records.sort_by_key(|record| Reverse(record.sort_key));
+        sort_thread_records(&mut records);
         let storage_dir = self.opencode_storage_dir();
-        Ok(records
+        let threads = records
             .into_iter()
             .map(|record| OpenCodeThreadOverview {
-                last_message_preview: build_last_message_preview(&storage_dir, &record.session_id),
+                last_message_preview: self
+                    .redact(build_last_message_preview(&storage_dir, &record.session_id)),
+                runtime_state: options
+                    .include_runtime_state
+                    .then(|| load_thread_runtime_state(&storage_dir, &record.session_id, now_ms)),
+                message_count: options
+                    .include_message_count
+                    .then(|| count_thread_messages(&storage_dir, &record.session_id)),
                 summary: record.summary,
             })
-            .collect())
+            .collect();
+        Ok(ThreadScan {
+            threads,
+            timed_out: deadline.timed_out(),
+        })
     }
 
     fn opencode_binary(&self) -> String {
@@ -130,7 +291,31 @@ impl OpenCodeAdapter {
                 return trimmed.to_string();
             }
         }
-        "opencode".to_string()
+        resolve_cli_binary("opencode", &self.cli_search_dirs())
+    }
+
+    fn cli_search_dirs(&self) -> Vec<PathBuf> {
+        self.cli_search_dirs
+            .clone()
+            .unwrap_or_else(default_cli_search_dirs)
+    }
+
+    fn scan_deadline(&self) -> ScanDeadline {
+        ScanDeadline::new(self.scan_timeout.or_else(default_scan_timeout))
+    }
+
+    fn now_ms(&self) -> i64 {
+        self.clock
+            .as_ref()
+            .map_or_else(Timestamp::now, |clock| clock.now())
+            .as_millis()
+    }
+
+    fn redact(&self, text: Option<String>) -> Option<String> {
+        if self.redaction_disabled {
+            return text;
+        }
+        text.map(|text| redact_secrets(&text).into_owned())
     }
 
     fn opencode_data_dir(&self) -> PathBuf {
@@ -165,8 +350,11 @@ impl OpenCodeAdapter {
     }
 
     fn scan_thread_records(&self) -> Vec<ThreadRecord> {
-        let mut files = Vec::new();
-        collect_json_files_recursive(&self.opencode_sessions_dir(), &mut files);
+        self.scan_thread_records_until(&self.scan_deadline())
+    }
+
+    fn scan_thread_records_until(&self, deadline: &ScanDeadline) -> Vec<ThreadRecord> {
+        let (files, _) = self.collect_session_files(deadline);
 
         let storage_dir = self.opencode_storage_dir();
         let project_map = load_project_worktree_map(&self.opencode_projects_dir());
@@ -177,38 +365,27 @@ impl OpenCodeAdapter {
             }
         }
 
-        records.sort_by_key(|record| Reverse(record.sort_key));
+        sort_thread_records(&mut records);
         records
     }
 
+    fn collect_session_files(&self, deadline: &ScanDeadline) -> (Vec<PathBuf>, Vec<OversizedFile>) {
+        let files = collect_files_with_extension(&self.opencode_sessions_dir(), "json", deadline);
+        let max_bytes = self
+            .max_session_file_bytes
+            .unwrap_or_else(default_max_session_file_bytes);
+        partition_oversized_files(files, max_bytes)
+    }
+
     fn find_thread_record(&self, thread_id: &str) -> ProviderResult<ThreadRecord> {
+        self.find_session_record(thread_id)
+            .ok_or_else(|| thread_not_found_error(thread_id))
+    }
+
+    fn find_session_record(&self, thread_id: &str) -> Option<ThreadRecord> {
         self.scan_thread_records()
             .into_iter()
             .find(|record| record.summary.id == thread_id)
-            .ok_or_else(|| {
-                provider_error(
-                    ProviderErrorCode::InvalidResponse,
-                    format!("OpenCode thread not found: {thread_id}"),
-                    false,
-                )
-            })
-    }
-
-    fn ensure_cli_reachable(&self) -> ProviderResult<()> {
-        let binary = self.opencode_binary();
-        match Command::new(&binary).arg("--version").output() {
-            Ok(_) => Ok(()),
-            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Err(provider_error(
-                ProviderErrorCode::UpstreamUnavailable,
-                format!("OpenCode CLI not found in PATH: {binary}"),
-                false,
-            )),
-            Err(error) => Err(provider_error(
-                ProviderErrorCode::UpstreamUnavailable,
-                format!("Failed to execute OpenCode CLI ({binary}): {error}"),
-                true,
-            )),
-        }
     }
 }
 
@@ -221,27 +398,19 @@ impl ProviderAdapter for OpenCodeAdapter {
         &self,
         request: ProviderHealthCheckRequest,
     ) -> ProviderResult<ProviderHealthCheckResult> {
-        let checked_at = now_unix_millis().to_string();
-        let binary = self.opencode_binary();
+        let checked_at = Timestamp::now();
 
-        match Command::new(&binary).arg("--version").output() {
-            Ok(_) => {}
-            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+        let version = match self.provider_version() {
+            Ok(version) => version,
+            Err(error) => {
                 return Ok(ProviderHealthCheckResult {
                     provider_id: ProviderId::OpenCode,
                     status: ProviderHealthStatus::Offline,
                     checked_at,
-                    message: Some(format!("OpenCode CLI not found in PATH: {binary}")),
+                    message: Some(error.message),
                 });
             }
-            Err(error) => {
-                return Err(provider_error(
-                    ProviderErrorCode::UpstreamUnavailable,
-                    format!("Failed to execute OpenCode CLI ({binary}): {error}"),
-                    true,
-                ));
-            }
-        }
+        };
 
         let sessions_dir = self.opencode_sessions_dir();
         if !sessions_dir.exists() {
@@ -262,26 +431,66 @@ impl ProviderAdapter for OpenCodeAdapter {
             status: ProviderHealthStatus::Healthy,
             checked_at,
             message: Some(format!(
-                "OpenCode CLI reachable, sessions directory loaded ({})",
+                "OpenCode CLI reachable{}, sessions directory loaded ({})",
+                version
+                    .map(|version| format!(" (v{version})"))
+                    .unwrap_or_default(),
                 request.profile_name
             )),
         })
     }
 
+    fn provider_version(&self) -> ProviderResult<Option<String>> {
+        let binary = self.opencode_binary();
+        match Command::new(&binary).arg("--version").output() {
+            Ok(output) if !output.status.success() => Err(ProviderError::from_cli_failure(
+                format!("OpenCode CLI ({binary}) exited with {}", output.status),
+                &String::from_utf8_lossy(&output.stderr),
+            )),
+            Ok(output) => Ok(parse_cli_version_output(&output)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Err(provider_error(
+                ProviderErrorCode::UpstreamUnavailable,
+                format!("OpenCode CLI not found in PATH: {binary}"),
+                false,
+            )),
+            Err(error) => Err(provider_error(
+                ProviderErrorCode::UpstreamUnavailable,
+                format!("Failed to execute OpenCode CLI ({binary}): {error}"),
+                true,
+            )),
+        }
+    }
+
     fn list_threads(&self, project_path: Option<&str>) -> ProviderResult<Vec<ThreadSummary>> {
         let mut records = self.scan_thread_records();
 
         if let Some(filter) = project_path {
             records.retain(|record| record.summary.project_path.starts_with(filter));
         }
+        if self.hide_empty_threads {
+            let storage_dir = self.opencode_storage_dir();
+            records.retain(|record| !is_empty_thread_record(&storage_dir, record));
+        }
 
-        records.sort_by_key(|record| Reverse(record.sort_key));
+        sort_thread_records(&mut records);
         Ok(records.into_iter().map(|record| record.summary).collect())
     }
 
     fn resume_thread(&self, request: ResumeThreadRequest) -> ProviderResult<ResumeThreadResult> {
-        self.ensure_cli_reachable()?;
-        let thread_record = self.find_thread_record(&request.thread_id)?;
+        let thread_record = self.find_session_record(&request.thread_id);
+        let session_path = thread_record
+            .as_ref()
+            .map(|record| record.source_path.as_path());
+        if let Err(reason) = verify_session_file("OpenCode", session_path) {
+            return Ok(ResumeThreadResult {
+                thread_id: request.thread_id,
+                resumed: false,
+                message: Some(reason),
+            });
+        }
+        let thread_record =
+            thread_record.ok_or_else(|| thread_not_found_error(&request.thread_id))?;
+        self.provider_version()?;
 
         let project_path = request
             .project_path
@@ -295,14 +504,12 @@ impl ProviderAdapter for OpenCodeAdapter {
                 }
             });
 
-        let mut command = format!(
-            "{} --session {}",
-            self.opencode_binary(),
-            shell_quote(&request.thread_id)
+        let command = build_resume_command(
+            &self.opencode_binary(),
+            &request.thread_id,
+            project_path.as_deref(),
+            request.context_summary.as_deref(),
         );
-        if let Some(path) = project_path {
-            command = prepend_workdir_to_command(command, &path);
-        }
 
         Ok(ResumeThreadResult {
             thread_id: request.thread_id,
@@ -314,6 +521,32 @@ impl ProviderAdapter for OpenCodeAdapter {
     }
 }
 
+fn build_resume_command(
+    binary: &str,
+    thread_id: &str,
+    project_path: Option<&str>,
+    context_summary: Option<&str>,
+) -> String {
+    let mut command = format!("{binary} --session {}", shell_quote(thread_id));
+    if let Some(summary) = context_summary.and_then(non_empty_trimmed) {
+        command = format!("{command} --prompt {}", shell_quote(summary));
+    }
+    match project_path {
+        Some(path) => prepend_workdir_to_command(command, path),
+        None => command,
+    }
+}
+
+fn sort_thread_records(records: &mut [ThreadRecord]) {
+    records.sort_by(|left, right| {
+        right
+            .sort_key
+            .cmp(&left.sort_key)
+            .then_with(|| left.summary.id.cmp(&right.summary.id))
+            .then_with(|| left.source_path.cmp(&right.source_path))
+    });
+}
+
 fn load_project_worktree_map(projects_dir: &Path) -> HashMap<String, String> {
     if !projects_dir.exists() {
         return HashMap::new();
@@ -330,7 +563,7 @@ fn load_project_worktree_map(projects_dir: &Path) -> HashMap<String, String> {
         if !path.is_file() {
             continue;
         }
-        if path.extension().and_then(|value| value.to_str()) != Some("json") {
+        if path.extension().is_none_or(|value| value != "json") {
             continue;
         }
 
@@ -354,26 +587,19 @@ fn load_project_worktree_map(projects_dir: &Path) -> HashMap<String, String> {
 }
 
 fn collect_json_files_recursive(root: &Path, output: &mut Vec<PathBuf>) {
-    if !root.exists() {
-        return;
-    }
-
-    let entries = match fs::read_dir(root) {
-        Ok(entries) => entries,
-        Err(_) => return,
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.is_dir() {
-            collect_json_files_recursive(&path, output);
-            continue;
-        }
+    output.extend(collect_files_with_extension(
+        root,
+        "json",
+        &ScanDeadline::unbounded(),
+    ));
+}
 
-        if path.extension().and_then(|value| value.to_str()) == Some("json") {
-            output.push(path);
-        }
-    }
+fn thread_not_found_error(thread_id: &str) -> ProviderError {
+    provider_error(
+        ProviderErrorCode::InvalidResponse,
+        format!("OpenCode thread not found: {thread_id}"),
+        false,
+    )
 }
 
 fn parse_session_file(
@@ -398,11 +624,7 @@ fn parse_session_file(
         .get("id")
         .and_then(Value::as_str)
         .map(ToString::to_string)
-        .or_else(|| {
-            path.file_stem()
-                .and_then(|stem| stem.to_str())
-                .map(ToString::to_string)
-        })?;
+        .or_else(|| file_stem_lossy(path))?;
 
     let project_id = parsed.get("projectID").and_then(Value::as_str);
     let project_path = parsed
@@ -413,8 +635,11 @@ fn parse_session_file(
             project_id
                 .and_then(|id| project_map.get(id))
                 .map(ToString::to_string)
-        })
-        .unwrap_or_else(|| ".".to_string());
+        });
+    let has_recorded_cwd = project_path
+        .as_deref()
+        .is_some_and(|directory| !directory.trim().is_empty());
+    let project_path = project_path.unwrap_or_else(|| ".".to_string());
 
     let title = parsed
         .get("title")
@@ -432,26 +657,59 @@ fn parse_session_file(
         .or_else(|| file_last_modified_ms(path))
         .unwrap_or(0);
 
+    let (model, provider_backend) = extract_session_model(&parsed);
+
     let summary = ThreadSummary {
         id: session_id.clone(),
         provider_id: ProviderId::OpenCode,
         account_id: None,
         project_path,
+        original_cwd: None,
+        model,
+        provider_backend,
         title,
-        tags: vec!["opencode".to_string()],
+        tags: vec![ProviderId::OpenCode.as_str().to_string()],
         last_active_at: updated_ms
             .or(created_ms)
-            .unwrap_or_else(now_unix_millis)
-            .to_string(),
+            .map(Timestamp::from_epoch_millis)
+            .unwrap_or_else(Timestamp::now),
     };
 
     Some(ThreadRecord {
         summary,
         session_id,
+        source_path: path.to_path_buf(),
         sort_key,
+        has_recorded_cwd,
     })
 }
 
+/// `(model, provider_backend)` from session metadata, accepting either top-level
+/// `modelID`/`providerID` or a nested `model` object.
+fn extract_session_model(parsed: &Value) -> (Option<String>, Option<String>) {
+    let non_empty = |value: Option<&Value>| {
+        value
+            .and_then(Value::as_str)
+            .and_then(non_empty_trimmed)
+            .map(ToString::to_string)
+    };
+
+    match parsed.get("model") {
+        Some(Value::String(model)) => (
+            non_empty_trimmed(model).map(ToString::to_string),
+            non_empty(parsed.get("providerID")),
+        ),
+        Some(model @ Value::Object(_)) => (
+            non_empty(model.get("modelID").or_else(|| model.get("id"))),
+            non_empty(model.get("providerID")),
+        ),
+        _ => (
+            non_empty(parsed.get("modelID")),
+            non_empty(parsed.get("providerID")),
+        ),
+    }
+}
+
 fn parse_message_file(path: &Path) -> Option<OpenCodeMessageNode> {
     let raw = fs::read_to_string(path).ok()?;
     let parsed: Value = serde_json::from_str(&raw).ok()?;
@@ -460,11 +718,7 @@ fn parse_message_file(path: &Path) -> Option<OpenCodeMessageNode> {
         .get("id")
         .and_then(Value::as_str)
         .map(ToString::to_string)
-        .or_else(|| {
-            path.file_stem()
-                .and_then(|stem| stem.to_str())
-                .map(ToString::to_string)
-        })?;
+        .or_else(|| file_stem_lossy(path))?;
 
     let role = parsed
         .get("role")
@@ -497,6 +751,21 @@ fn parse_message_file(path: &Path) -> Option<OpenCodeMessageNode> {
     })
 }
 
+fn is_empty_thread_record(storage_dir: &Path, record: &ThreadRecord) -> bool {
+    if record.has_recorded_cwd {
+        return false;
+    }
+
+    let mut message_files = Vec::new();
+    collect_json_files_recursive(
+        &storage_dir.join("message").join(&record.session_id),
+        &mut message_files,
+    );
+    !message_files
+        .iter()
+        .any(|path| parse_message_file(path).is_some())
+}
+
 fn build_first_user_thread_title(storage_dir: &Path, session_id: &str) -> Option<String> {
     let message_dir = storage_dir.join("message").join(session_id);
     if !message_dir.exists() {
@@ -530,7 +799,11 @@ fn build_first_user_thread_title(storage_dir: &Path, session_id: &str) -> Option
     None
 }
 
-fn load_thread_runtime_state(storage_dir: &Path, session_id: &str) -> OpenCodeThreadRuntimeState {
+fn load_thread_runtime_state(
+    storage_dir: &Path,
+    session_id: &str,
+    now_ms: i64,
+) -> OpenCodeThreadRuntimeState {
     let message_dir = storage_dir.join("message").join(session_id);
     if !message_dir.exists() {
         return OpenCodeThreadRuntimeState {
@@ -587,7 +860,7 @@ fn load_thread_runtime_state(storage_dir: &Path, session_id: &str) -> OpenCodeTh
     }
 
     let agent_answering = latest_in_progress_assistant_at
-        .map(|ts| now_unix_millis().saturating_sub(ts) <= OPENCODE_AGENT_ACTIVITY_WINDOW_MS)
+        .map(|ts| now_ms.saturating_sub(ts) <= OPENCODE_AGENT_ACTIVITY_WINDOW_MS)
         .unwrap_or(false);
 
     OpenCodeThreadRuntimeState {
@@ -597,6 +870,127 @@ fn load_thread_runtime_state(storage_dir: &Path, session_id: &str) -> OpenCodeTh
     }
 }
 
+fn load_thread_event_log(storage_dir: &Path, session_id: &str, limit: usize) -> Vec<ThreadEvent> {
+    let message_dir = storage_dir.join("message").join(session_id);
+    if !message_dir.exists() {
+        return Vec::new();
+    }
+
+    let mut message_files = Vec::new();
+    collect_json_files_recursive(&message_dir, &mut message_files);
+    let mut nodes = message_files
+        .into_iter()
+        .filter_map(|path| parse_message_file(&path))
+        .collect::<Vec<OpenCodeMessageNode>>();
+    nodes.sort_by_key(|node| node.sort_key);
+
+    let mut events = VecDeque::new();
+    let mut push_event = |event| {
+        events.push_back(event);
+        if events.len() > limit {
+            events.pop_front();
+        }
+    };
+    for node in nodes {
+        let fallback_ts = node.timestamp_ms.or(node.created_ms);
+        if node.role == "user" {
+            push_event((OpenCodeSemanticEventKind::UserMessage, fallback_ts));
+        } else if node.role == "assistant" {
+            let part_events = load_part_event_kinds(storage_dir, &node.id, fallback_ts);
+            if part_events.is_empty() {
+                push_event((OpenCodeSemanticEventKind::AgentMessage, fallback_ts));
+            } else {
+                part_events.into_iter().for_each(&mut push_event);
+            }
+        }
+    }
+
+    events
+        .into_iter()
+        .map(|(kind, at_ms)| ThreadEvent {
+            kind: kind.as_str().to_string(),
+            at_ms,
+            timestamp_raw: None,
+        })
+        .collect()
+}
+
+/// Counts user/assistant messages and collects `filePath` inputs of the `edit`,
+/// `write`, `multiedit` and `patch` tool parts.
+fn load_thread_activity(storage_dir: &Path, session_id: &str) -> ThreadActivity {
+    let mut nodes = load_message_nodes(storage_dir, session_id);
+    nodes.sort_by_key(|node| node.sort_key);
+
+    let mut activity = ThreadActivity::default();
+    for node in &nodes {
+        if !is_counted_message(node) {
+            continue;
+        }
+        activity.message_count += 1;
+        activity.record_timestamp(node.created_ms);
+        activity.record_timestamp(node.completed_ms);
+        if node.role != "assistant" {
+            continue;
+        }
+
+        let mut part_files = Vec::new();
+        collect_json_files_recursive(&storage_dir.join("part").join(&node.id), &mut part_files);
+        part_files.sort();
+        for path in part_files {
+            let Some(part) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+            else {
+                continue;
+            };
+            if let Some(path) = edit_tool_file_path(&part) {
+                activity.record_touched_file(path);
+            }
+        }
+    }
+
+    activity
+}
+
+/// Reads message nodes only, not their part files.
+fn count_thread_messages(storage_dir: &Path, session_id: &str) -> usize {
+    load_message_nodes(storage_dir, session_id)
+        .iter()
+        .filter(|node| is_counted_message(node))
+        .count()
+}
+
+fn load_message_nodes(storage_dir: &Path, session_id: &str) -> Vec<OpenCodeMessageNode> {
+    let mut message_files = Vec::new();
+    collect_json_files_recursive(
+        &storage_dir.join("message").join(session_id),
+        &mut message_files,
+    );
+    message_files
+        .into_iter()
+        .filter_map(|path| parse_message_file(&path))
+        .collect()
+}
+
+fn is_counted_message(node: &OpenCodeMessageNode) -> bool {
+    matches!(node.role.as_str(), "user" | "assistant")
+}
+
+fn edit_tool_file_path(part: &Value) -> Option<&str> {
+    if part.get("type").and_then(Value::as_str) != Some("tool") {
+        return None;
+    }
+    let tool = part.get("tool").and_then(Value::as_str)?;
+    if !matches!(tool, "edit" | "write" | "multiedit" | "patch") {
+        return None;
+    }
+    let input = part.get("state")?.get("input")?;
+    input
+        .get("filePath")
+        .or_else(|| input.get("path"))
+        .and_then(Value::as_str)
+}
+
 fn load_part_event_kinds(
     storage_dir: &Path,
     message_id: &str,
@@ -616,14 +1010,9 @@ fn load_part_event_kinds(
         .flatten()
         .map(|entry| entry.path())
         .filter(|path| path.is_file())
-        .filter(|path| path.extension().and_then(|value| value.to_str()) == Some("json"))
+        .filter(|path| path.extension().is_some_and(|value| value == "json"))
         .collect::<Vec<PathBuf>>();
-    files.sort_by_key(|path| {
-        path.file_name()
-            .and_then(|name| name.to_str())
-            .map(ToString::to_string)
-            .unwrap_or_default()
-    });
+    files.sort_by_key(|path| path.file_name().map(|name| name.to_os_string()));
 
     let mut events = Vec::new();
     for path in files {
@@ -697,6 +1086,57 @@ fn build_last_message_preview(storage_dir: &Path, session_id: &str) -> Option<St
     last_preview.map(|text| truncate_text(&text, 140))
 }
 
+fn build_latest_message_preview(storage_dir: &Path, session_id: &str) -> Option<String> {
+    let message_dir = storage_dir.join("message").join(session_id);
+    if !message_dir.exists() {
+        return None;
+    }
+
+    let mut message_files = Vec::new();
+    collect_json_files_recursive(&message_dir, &mut message_files);
+    let mut nodes = message_files
+        .into_iter()
+        .filter_map(|path| parse_message_file(&path))
+        .collect::<Vec<OpenCodeMessageNode>>();
+    nodes.sort_by_key(|node| node.sort_key);
+
+    // Only open part directories from the newest message backwards until one has text.
+    nodes
+        .iter()
+        .rev()
+        .find_map(|node| {
+            find_last_text_part(storage_dir, &node.id).or_else(|| {
+                if node.role == "user" {
+                    node.summary_title
+                        .as_deref()
+                        .and_then(normalize_preview_text)
+                } else {
+                    None
+                }
+            })
+        })
+        .map(|text| truncate_text(&text, 140))
+}
+
+fn build_final_assistant_message(storage_dir: &Path, session_id: &str) -> Option<String> {
+    let mut message_files = Vec::new();
+    collect_json_files_recursive(
+        &storage_dir.join("message").join(session_id),
+        &mut message_files,
+    );
+    let mut nodes = message_files
+        .into_iter()
+        .filter_map(|path| parse_message_file(&path))
+        .collect::<Vec<OpenCodeMessageNode>>();
+    nodes.sort_by_key(|node| node.sort_key);
+
+    nodes
+        .iter()
+        .rev()
+        .filter(|node| node.role == "assistant")
+        .find_map(|node| find_last_text_part(storage_dir, &node.id))
+}
+
 /// Find the last "text" type part for a message and return its content.
 fn find_last_text_part(storage_dir: &Path, message_id: &str) -> Option<String> {
     let parts_dir = storage_dir.join("part").join(message_id);
@@ -713,14 +1153,9 @@ fn find_last_text_part(storage_dir: &Path, message_id: &str) -> Option<String> {
         .flatten()
         .map(|entry| entry.path())
         .filter(|path| path.is_file())
-        .filter(|path| path.extension().and_then(|value| value.to_str()) == Some("json"))
+        .filter(|path| path.extension().is_some_and(|value| value == "json"))
         .collect::<Vec<PathBuf>>();
-    files.sort_by_key(|path| {
-        path.file_name()
-            .and_then(|name| name.to_str())
-            .map(ToString::to_string)
-            .unwrap_or_default()
-    });
+    files.sort_by_key(|path| path.file_name().map(|name| name.to_os_string()));
 
     let mut last_text: Option<String> = None;
     for path in files {
@@ -762,14 +1197,9 @@ fn find_first_text_part(storage_dir: &Path, message_id: &str) -> Option<String>
         .flatten()
         .map(|entry| entry.path())
         .filter(|path| path.is_file())
-        .filter(|path| path.extension().and_then(|value| value.to_str()) == Some("json"))
+        .filter(|path| path.extension().is_some_and(|value| value == "json"))
         .collect::<Vec<PathBuf>>();
-    files.sort_by_key(|path| {
-        path.file_name()
-            .and_then(|name| name.to_str())
-            .map(ToString::to_string)
-            .unwrap_or_default()
-    });
+    files.sort_by_key(|path| path.file_name().map(|name| name.to_os_string()));
 
     for path in files {
         let raw = match fs::read_to_string(path) {
@@ -867,36 +1297,6 @@ fn path_basename(path: &str) -> Option<&str> {
     Path::new(path).file_name()?.to_str()
 }
 
-fn now_unix_millis() -> i64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|duration| duration.as_millis() as i64)
-        .unwrap_or(0)
-}
-
-fn default_home_dir() -> Option<PathBuf> {
-    if let Ok(home) = std::env::var("HOME") {
-        if !home.trim().is_empty() {
-            return Some(PathBuf::from(home));
-        }
-    }
-
-    if let Ok(home) = std::env::var("USERPROFILE") {
-        if !home.trim().is_empty() {
-            return Some(PathBuf::from(home));
-        }
-    }
-
-    let home_drive = std::env::var("HOMEDRIVE").ok()?;
-    let home_path = std::env::var("HOMEPATH").ok()?;
-    let combined = format!("{home_drive}{home_path}");
-    if combined.trim().is_empty() {
-        return None;
-    }
-
-    Some(PathBuf::from(combined))
-}
-
 fn default_opencode_data_dir() -> Option<PathBuf> {
     if let Ok(xdg_data_home) = std::env::var("XDG_DATA_HOME") {
         let trimmed = xdg_data_home.trim();
@@ -905,11 +1305,8 @@ fn default_opencode_data_dir() -> Option<PathBuf> {
         }
     }
 
-    if let Ok(local_app_data) = std::env::var("LOCALAPPDATA") {
-        let trimmed = local_app_data.trim();
-        if !trimmed.is_empty() {
-            return Some(PathBuf::from(trimmed).join("opencode"));
-        }
+    if let Some(local_app_data) = local_app_data_dir() {
+        return Some(local_app_data.join("opencode"));
     }
 
     default_home_dir().map(|home| home.join(".local").join("share").join("opencode"))
@@ -941,7 +1338,9 @@ fn shell_quote(path: &str) -> String {
 mod tests {
     use super::*;
 
+    use provider_contract::FixedClock;
     use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::SystemTime;
 
     static TEST_DIR_COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -997,6 +1396,63 @@ mod tests {
         assert_eq!(threads[0].provider_id, ProviderId::OpenCode);
         assert_eq!(threads[0].project_path, "/workspace/a");
         assert_eq!(threads[0].title, "Session A");
+        assert!(threads[0].model.is_none());
+        assert!(threads[0].provider_backend.is_none());
+    }
+
+    #[test]
+    fn default_provider_tag_matches_provider_id() {
+        let data_dir = test_temp_dir("provider-tag").join("opencode");
+        write_json(
+            &data_dir.join("storage/session/proj-tag/ses_tag.json"),
+            r#"{"id":"ses_tag","projectID":"proj-tag","directory":"/workspace/a","title":"Tag me","time":{"created":1760000000000,"updated":1760000000999}}"#,
+        );
+        let adapter = OpenCodeAdapter::new().with_data_dir(&data_dir);
+
+        let threads = adapter.list_threads(None).expect("threads should load");
+
+        assert_eq!(threads[0].tags, vec![adapter.provider_id().as_str()]);
+        assert_eq!(threads[0].provider_id, adapter.provider_id());
+    }
+
+    #[test]
+    fn list_threads_reads_session_model_metadata() {
+        let data_dir = test_temp_dir("list-threads-model").join("opencode");
+        write_json(
+            &data_dir
+                .join("storage")
+                .join("session")
+                .join("global")
+                .join("ses_model.json"),
+            r#"{"id":"ses_model","projectID":"global","directory":"/workspace/m","title":"Model","model":{"providerID":"anthropic","modelID":"claude-3-5-sonnet"},"time":{"created":1760000000000,"updated":1760000000999}}"#,
+        );
+        write_json(
+            &data_dir
+                .join("storage")
+                .join("session")
+                .join("global")
+                .join("ses_flat.json"),
+            r#"{"id":"ses_flat","projectID":"global","directory":"/workspace/m","title":"Flat","providerID":"openai","modelID":"gpt-4o","time":{"created":1760000000000,"updated":1760000000500}}"#,
+        );
+
+        let adapter = OpenCodeAdapter::new().with_data_dir(&data_dir);
+        let threads = adapter
+            .list_threads(None)
+            .expect("list_threads should work");
+
+        let nested = threads
+            .iter()
+            .find(|thread| thread.id == "ses_model")
+            .expect("nested model session should be listed");
+        assert_eq!(nested.model.as_deref(), Some("claude-3-5-sonnet"));
+        assert_eq!(nested.provider_backend.as_deref(), Some("anthropic"));
+
+        let flat = threads
+            .iter()
+            .find(|thread| thread.id == "ses_flat")
+            .expect("flat model session should be listed");
+        assert_eq!(flat.model.as_deref(), Some("gpt-4o"));
+        assert_eq!(flat.provider_backend.as_deref(), Some("openai"));
     }
 
     #[test]
@@ -1178,7 +1634,7 @@ mod tests {
     fn runtime_state_marks_in_progress_assistant_as_answering() {
         let data_dir = test_temp_dir("runtime-answering").join("opencode");
         let session_id = "ses_runtime";
-        let now = now_unix_millis();
+        let now = 1_700_000_000_000;
 
         write_json(
             &data_dir
@@ -1216,7 +1672,9 @@ mod tests {
             ),
         );
 
-        let adapter = OpenCodeAdapter::new().with_data_dir(&data_dir);
+        let adapter = OpenCodeAdapter::new()
+            .with_data_dir(&data_dir)
+            .with_clock(FixedClock(Timestamp::from_epoch_millis(now)));
         let state = adapter
             .get_thread_runtime_state(session_id)
             .expect("runtime state should load");
@@ -1229,7 +1687,7 @@ mod tests {
     fn runtime_state_marks_completed_assistant_as_not_answering() {
         let data_dir = test_temp_dir("runtime-idle").join("opencode");
         let session_id = "ses_runtime_done";
-        let now = now_unix_millis();
+        let now = 1_700_000_000_000;
 
         write_json(
             &data_dir
@@ -1268,7 +1726,9 @@ mod tests {
             ),
         );
 
-        let adapter = OpenCodeAdapter::new().with_data_dir(&data_dir);
+        let adapter = OpenCodeAdapter::new()
+            .with_data_dir(&data_dir)
+            .with_clock(FixedClock(Timestamp::from_epoch_millis(now)));
         let state = adapter
             .get_thread_runtime_state(session_id)
             .expect("runtime state should load");
@@ -1276,4 +1736,584 @@ mod tests {
         assert!(!state.agent_answering);
         assert_eq!(state.last_event_kind.as_deref(), Some("agent_message"));
     }
+
+    #[test]
+    fn build_resume_command_passes_context_summary_via_prompt_flag() {
+        let command = build_resume_command(
+            "opencode",
+            "ses_a",
+            None,
+            Some("Objective: it's time to refactor"),
+        );
+        assert_eq!(
+            command,
+            format!(
+                "opencode --session {} --prompt {}",
+                shell_quote("ses_a"),
+                shell_quote("Objective: it's time to refactor")
+            )
+        );
+
+        let without_summary = build_resume_command("opencode", "ses_a", None, None);
+        assert!(!without_summary.contains("--prompt"));
+    }
+
+    #[test]
+    fn latest_message_preview_matches_full_parse() {
+        let data_dir = test_temp_dir("latest-preview").join("opencode");
+        let session_id = "ses_latest";
+        let project_id = "proj-latest";
+        let storage_dir = data_dir.join("storage");
+
+        write_json(
+            &storage_dir
+                .join("session")
+                .join(project_id)
+                .join(format!("{session_id}.json")),
+            &format!(
+                r#"{{"id":"{session_id}","projectID":"{project_id}","directory":"/workspace/latest","title":"Latest","time":{{"created":1760000000000,"updated":1760000000999}}}}"#
+            ),
+        );
+        for (message_id, role, created, text) in [
+            (
+                "msg_001",
+                "user",
+                1760000000001_i64,
+                Some("please refactor"),
+            ),
+            (
+                "msg_002",
+                "assistant",
+                1760000000002,
+                Some("Refactor complete"),
+            ),
+            ("msg_003", "assistant", 1760000000003, None),
+        ] {
+            write_json(
+                &storage_dir
+                    .join("message")
+                    .join(session_id)
+                    .join(format!("{message_id}.json")),
+                &format!(
+                    r#"{{"id":"{message_id}","sessionID":"{session_id}","role":"{role}","time":{{"created":{created}}}}}"#
+                ),
+            );
+            let part = match text {
+                Some(text) => format!(
+                    r#"{{"id":"prt_{message_id}","sessionID":"{session_id}","messageID":"{message_id}","type":"text","text":"{text}"}}"#
+                ),
+                None => format!(
+                    r#"{{"id":"prt_{message_id}","sessionID":"{session_id}","messageID":"{message_id}","type":"tool","tool":"bash"}}"#
+                ),
+            };
+            write_json(
+                &storage_dir
+                    .join("part")
+                    .join(message_id)
+                    .join("prt_001.json"),
+                &part,
+            );
+        }
+
+        let adapter = OpenCodeAdapter::new().with_data_dir(&data_dir);
+        let latest = adapter
+            .get_latest_message_preview(session_id)
+            .expect("latest preview should work");
+
+        assert_eq!(latest.as_deref(), Some("Refactor complete"));
+        assert_eq!(latest, build_last_message_preview(&storage_dir, session_id));
+    }
+
+    #[test]
+    fn resume_thread_reports_removed_session_as_not_resumable() {
+        let data_dir = test_temp_dir("resume-removed").join("opencode");
+        let session_path = data_dir
+            .join("storage")
+            .join("session")
+            .join("proj-resume")
+            .join("ses_resume.json");
+        write_json(
+            &session_path,
+            r#"{"id":"ses_resume","projectID":"proj-resume","directory":"/workspace/resume","title":"Resume","time":{"created":1760000000000,"updated":1760000000999}}"#,
+        );
+
+        let adapter = OpenCodeAdapter::new().with_data_dir(&data_dir);
+        assert!(adapter.thread_exists("ses_resume"));
+
+        fs::remove_file(&session_path).expect("session file should be removable");
+        let removed = adapter
+            .resume_thread(ResumeThreadRequest {
+                thread_id: "ses_resume".to_string(),
+                project_path: None,
+                context_summary: None,
+            })
+            .expect("resume should report instead of failing");
+        assert!(!removed.resumed);
+        assert!(removed
+            .message
+            .expect("reason should exist")
+            .contains("no longer exists"));
+    }
+
+    #[test]
+    fn list_threads_orders_equal_sort_keys_by_thread_id() {
+        let data_dir = test_temp_dir("stable-order").join("opencode");
+        for session_id in ["ses_c", "ses_a", "ses_b"] {
+            write_json(
+                &data_dir
+                    .join("storage")
+                    .join("session")
+                    .join("proj-order")
+                    .join(format!("{session_id}.json")),
+                &format!(
+                    r#"{{"id":"{session_id}","projectID":"proj-order","directory":"/workspace/order","title":"{session_id}","time":{{"created":1760000000000,"updated":1760000000999}}}}"#
+                ),
+            );
+        }
+
+        let adapter = OpenCodeAdapter::new().with_data_dir(&data_dir);
+        for _ in 0..3 {
+            let ids = adapter
+                .list_threads(None)
+                .expect("list_threads should work")
+                .into_iter()
+                .map(|thread| thread.id)
+                .collect::<Vec<_>>();
+            assert_eq!(ids, vec!["ses_a", "ses_b", "ses_c"]);
+        }
+    }
+
+    #[test]
+    fn runtime_state_activity_window_includes_exact_edge() {
+        let data_dir = test_temp_dir("runtime-window-edge").join("opencode");
+        let session_id = "ses_runtime_edge";
+        let created_at = 1_700_000_000_000;
+
+        write_json(
+            &data_dir
+                .join("storage")
+                .join("session")
+                .join("global")
+                .join(format!("{session_id}.json")),
+            &format!(
+                r#"{{"id":"{session_id}","projectID":"global","directory":"/workspace/e","title":"Runtime","time":{{"created":{created_at},"updated":{created_at}}}}}"#
+            ),
+        );
+        write_json(
+            &data_dir
+                .join("storage")
+                .join("message")
+                .join(session_id)
+                .join("msg_assistant.json"),
+            &format!(
+                r#"{{"id":"msg_assistant","sessionID":"{session_id}","role":"assistant","time":{{"created":{created_at}}}}}"#
+            ),
+        );
+
+        let state_at = |now: i64| {
+            OpenCodeAdapter::new()
+                .with_data_dir(&data_dir)
+                .with_clock(FixedClock(Timestamp::from_epoch_millis(now)))
+                .get_thread_runtime_state(session_id)
+                .expect("runtime state should load")
+        };
+
+        assert!(state_at(created_at + OPENCODE_AGENT_ACTIVITY_WINDOW_MS).agent_answering);
+        assert!(!state_at(created_at + OPENCODE_AGENT_ACTIVITY_WINDOW_MS + 1).agent_answering);
+    }
+
+    #[test]
+    fn thread_event_log_returns_last_events_in_order() {
+        let data_dir = test_temp_dir("event-log").join("opencode");
+        let storage_dir = data_dir.join("storage");
+        let session_id = "ses_events";
+
+        write_json(
+            &storage_dir
+                .join("session")
+                .join("global")
+                .join(format!("{session_id}.json")),
+            &format!(
+                r#"{{"id":"{session_id}","projectID":"global","directory":"/workspace/e","title":"Events","time":{{"created":1700000000000,"updated":1700000005000}}}}"#
+            ),
+        );
+        write_json(
+            &storage_dir
+                .join("message")
+                .join(session_id)
+                .join("msg_user.json"),
+            &format!(
+                r#"{{"id":"msg_user","sessionID":"{session_id}","role":"user","time":{{"created":1700000001000}}}}"#
+            ),
+        );
+        write_json(
+            &storage_dir
+                .join("message")
+                .join(session_id)
+                .join("msg_assistant.json"),
+            &format!(
+                r#"{{"id":"msg_assistant","sessionID":"{session_id}","role":"assistant","time":{{"created":1700000002000,"completed":1700000005000}}}}"#
+            ),
+        );
+        for (part_id, part_type, end) in [
+            ("prt_001", "reasoning", 1_700_000_002_000_i64),
+            ("prt_002", "tool", 1_700_000_003_000),
+            ("prt_003", "tool", 1_700_000_004_000),
+            ("prt_004", "text", 1_700_000_005_000),
+        ] {
+            write_json(
+                &storage_dir
+                    .join("part")
+                    .join("msg_assistant")
+                    .join(format!("{part_id}.json")),
+                &format!(
+                    r#"{{"id":"{part_id}","sessionID":"{session_id}","messageID":"msg_assistant","type":"{part_type}","time":{{"start":{end},"end":{end}}}}}"#
+                ),
+            );
+        }
+
+        let adapter = OpenCodeAdapter::new().with_data_dir(&data_dir);
+        let events = adapter
+            .get_thread_event_log(session_id, 10)
+            .expect("event log should load");
+        let kinds: Vec<&str> = events.iter().map(|event| event.kind.as_str()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "user_message",
+                "agent_reasoning",
+                "agent_tool",
+                "agent_tool",
+                "agent_message"
+            ]
+        );
+        assert_eq!(events[0].at_ms, Some(1_700_000_001_000));
+
+        let tail = adapter
+            .get_thread_event_log(session_id, 2)
+            .expect("event log should load");
+        let kinds: Vec<&str> = tail.iter().map(|event| event.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["agent_tool", "agent_message"]);
+    }
+
+    #[test]
+    fn thread_exists_tracks_session_files_on_disk() {
+        let data_dir = test_temp_dir("thread-exists").join("opencode");
+        let session_file = data_dir
+            .join("storage")
+            .join("session")
+            .join("global")
+            .join("ses_exists.json");
+        write_json(
+            &session_file,
+            r#"{"id":"ses_exists","projectID":"global","directory":"/workspace/e","title":"Exists","time":{"created":1700000000000,"updated":1700000000000}}"#,
+        );
+        let adapter = OpenCodeAdapter::new().with_data_dir(&data_dir);
+
+        assert!(adapter.thread_exists("ses_exists"));
+        assert!(!adapter.thread_exists("ses_missing"));
+
+        fs::remove_file(&session_file).expect("session file should be removed");
+        assert!(!adapter.thread_exists("ses_exists"));
+    }
+
+    #[test]
+    fn thread_exists_matches_listed_session_ids() {
+        let data_dir = test_temp_dir("thread-exists-listing").join("opencode");
+        let session_dir = data_dir.join("storage").join("session").join("global");
+        write_json(
+            &session_dir.join("renamed.json"),
+            r#"{"id":"ses_renamed","directory":"/workspace/r","title":"Renamed","time":{"created":1700000000000}}"#,
+        );
+        write_json(
+            &session_dir.join("ses_large.json"),
+            &format!(
+                r#"{{"id":"ses_large","directory":"/workspace/l","title":"{}","time":{{"created":1700000000000}}}}"#,
+                "x".repeat(512)
+            ),
+        );
+        let adapter = OpenCodeAdapter::new()
+            .with_data_dir(&data_dir)
+            .with_max_session_file_bytes(256);
+
+        let listed = adapter.list_threads(None).expect("threads should load");
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, "ses_renamed");
+        assert!(adapter.thread_exists("ses_renamed"));
+        assert!(!adapter.thread_exists("renamed"));
+        assert!(!adapter.thread_exists("ses_large"));
+    }
+
+    #[test]
+    fn list_threads_hides_empty_placeholder_threads_when_enabled() {
+        let data_dir = test_temp_dir("hide-empty").join("opencode");
+        let storage_dir = data_dir.join("storage");
+        write_json(
+            &storage_dir
+                .join("session")
+                .join("global")
+                .join("ses_real.json"),
+            r#"{"id":"ses_real","title":"Real","time":{"created":1760000000000,"updated":1760000000999}}"#,
+        );
+        write_json(
+            &storage_dir
+                .join("message")
+                .join("ses_real")
+                .join("msg_user_1.json"),
+            r#"{"id":"msg_user_1","sessionID":"ses_real","role":"user","time":{"created":1760000000001}}"#,
+        );
+        write_json(
+            &storage_dir
+                .join("session")
+                .join("global")
+                .join("ses_empty.json"),
+            r#"{"id":"ses_empty","time":{"created":1760000000000}}"#,
+        );
+        write_json(
+            &storage_dir
+                .join("session")
+                .join("global")
+                .join("ses_dot.json"),
+            r#"{"id":"ses_dot","directory":".","time":{"created":1760000000000}}"#,
+        );
+
+        let all = OpenCodeAdapter::new()
+            .with_data_dir(&data_dir)
+            .list_threads(None)
+            .expect("list_threads should work");
+        assert_eq!(all.len(), 3);
+
+        let threads = OpenCodeAdapter::new()
+            .with_data_dir(&data_dir)
+            .with_hide_empty_threads(true)
+            .list_threads(None)
+            .expect("list_threads should work");
+        let mut ids = threads
+            .iter()
+            .map(|thread| thread.id.as_str())
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        assert_eq!(ids, vec!["ses_dot", "ses_real"]);
+    }
+
+    #[test]
+    fn thread_activity_collects_edit_tool_files() {
+        let data_dir = test_temp_dir("thread-activity").join("opencode");
+        let session_id = "ses_activity";
+        let storage_dir = data_dir.join("storage");
+        write_json(
+            &storage_dir
+                .join("session")
+                .join("proj-activity")
+                .join(format!("{session_id}.json")),
+            &format!(
+                r#"{{"id":"{session_id}","projectID":"proj-activity","directory":"/workspace/a","title":"Activity","time":{{"created":1760000000000,"updated":1760000009000}}}}"#
+            ),
+        );
+        for (message_id, role, created, completed) in [
+            ("msg_001", "user", 1760000000000_i64, 1760000000000_i64),
+            ("msg_002", "assistant", 1760000001000, 1760000009000),
+        ] {
+            write_json(
+                &storage_dir
+                    .join("message")
+                    .join(session_id)
+                    .join(format!("{message_id}.json")),
+                &format!(
+                    r#"{{"id":"{message_id}","sessionID":"{session_id}","role":"{role}","time":{{"created":{created},"completed":{completed}}}}}"#
+                ),
+            );
+        }
+        let parts_dir = storage_dir.join("part").join("msg_002");
+        write_json(
+            &parts_dir.join("prt_001.json"),
+            r#"{"id":"prt_001","type":"tool","tool":"read","state":{"input":{"filePath":"/workspace/a/README.md"}}}"#,
+        );
+        write_json(
+            &parts_dir.join("prt_002.json"),
+            r#"{"id":"prt_002","type":"tool","tool":"edit","state":{"input":{"filePath":"/workspace/a/src/main.ts"}}}"#,
+        );
+        write_json(
+            &parts_dir.join("prt_003.json"),
+            r#"{"id":"prt_003","type":"tool","tool":"write","state":{"input":{"filePath":"/workspace/a/src/util.ts"}}}"#,
+        );
+        write_json(
+            &parts_dir.join("prt_004.json"),
+            r#"{"id":"prt_004","type":"text","text":"Updated main and added a util."}"#,
+        );
+
+        let adapter = OpenCodeAdapter::new().with_data_dir(&data_dir);
+        let activity = adapter
+            .get_thread_activity(session_id)
+            .expect("activity should load");
+
+        assert_eq!(
+            activity.touched_files,
+            vec!["/workspace/a/src/main.ts", "/workspace/a/src/util.ts"]
+        );
+        assert_eq!(activity.message_count, 2);
+        assert_eq!(activity.duration_ms(), Some(9_000));
+        assert_eq!(
+            adapter
+                .get_final_assistant_message(session_id)
+                .expect("final assistant message should load")
+                .as_deref(),
+            Some("Updated main and added a util.")
+        );
+    }
+
+    #[test]
+    fn overviews_include_runtime_state_only_when_requested() {
+        let data_dir = test_temp_dir("overview-runtime").join("opencode");
+        let storage_dir = data_dir.join("storage");
+        let session_id = "ses_overview";
+        let now = 1_700_000_000_000;
+        write_json(
+            &storage_dir.join(format!("session/global/{session_id}.json")),
+            &format!(
+                r#"{{"id":"{session_id}","projectID":"global","directory":"/workspace/c","title":"Overview","time":{{"created":{now},"updated":{now}}}}}"#
+            ),
+        );
+        write_json(
+            &storage_dir.join(format!("message/{session_id}/msg_assistant.json")),
+            &format!(
+                r#"{{"id":"msg_assistant","sessionID":"{session_id}","role":"assistant","time":{{"created":{}}}}}"#,
+                now - 2_000
+            ),
+        );
+        write_json(
+            &storage_dir.join("part/msg_assistant/prt_001.json"),
+            &format!(
+                r#"{{"id":"prt_001","sessionID":"{session_id}","messageID":"msg_assistant","type":"reasoning","text":"thinking","time":{{"start":{},"end":{}}}}}"#,
+                now - 1_500,
+                now - 1_000
+            ),
+        );
+        let adapter = OpenCodeAdapter::new()
+            .with_data_dir(&data_dir)
+            .with_clock(FixedClock(Timestamp::from_epoch_millis(now)));
+
+        let plain = adapter
+            .list_thread_overviews(None)
+            .expect("overviews should load");
+        assert_eq!(plain[0].runtime_state, None);
+
+        let overviews = adapter
+            .list_thread_overviews_with_options(
+                None,
+                ThreadOverviewOptions {
+                    include_runtime_state: true,
+                    ..ThreadOverviewOptions::default()
+                },
+            )
+            .expect("overviews should load");
+        let standalone = adapter
+            .get_thread_runtime_state(session_id)
+            .expect("runtime state should load");
+        assert_eq!(overviews[0].runtime_state.as_ref(), Some(&standalone));
+        assert_eq!(
+            standalone.last_event_kind.as_deref(),
+            Some("agent_reasoning")
+        );
+    }
+
+    #[test]
+    fn config_summary_reflects_builder_overrides() {
+        let data_dir = test_temp_dir("config-summary");
+        write_json(
+            &data_dir.join("auth.json"),
+            r#"{"anthropic":{"key":"sk-secret"}}"#,
+        );
+
+        let summary = OpenCodeAdapter::new()
+            .with_data_dir(&data_dir)
+            .with_cli_binary("missing-opencode-binary-123")
+            .config_summary();
+
+        assert_eq!(summary.config_dir, data_dir);
+        assert_eq!(
+            summary.sessions_dir,
+            data_dir.join("storage").join("session")
+        );
+        assert!(!summary.sessions_dir_exists());
+        assert_eq!(summary.cli_binary, "missing-opencode-binary-123");
+        assert_eq!(summary.cli_binary_path, None);
+        assert_eq!(summary.auth_source.as_deref(), Some("auth.json"));
+    }
+
+    #[test]
+    fn overviews_include_message_count_only_when_requested() {
+        let data_dir = test_temp_dir("overview-message-count").join("opencode");
+        let session_id = "ses_count";
+        let storage_dir = data_dir.join("storage");
+        write_json(
+            &storage_dir
+                .join("session")
+                .join("proj-count")
+                .join(format!("{session_id}.json")),
+            &format!(
+                r#"{{"id":"{session_id}","projectID":"proj-count","directory":"/workspace/a","title":"Count","time":{{"created":1760000000000,"updated":1760000009000}}}}"#
+            ),
+        );
+        for (message_id, role) in [
+            ("msg_001", "user"),
+            ("msg_002", "assistant"),
+            ("msg_003", "system"),
+            ("msg_004", "user"),
+        ] {
+            write_json(
+                &storage_dir
+                    .join("message")
+                    .join(session_id)
+                    .join(format!("{message_id}.json")),
+                &format!(
+                    r#"{{"id":"{message_id}","sessionID":"{session_id}","role":"{role}","time":{{"created":1760000000000}}}}"#
+                ),
+            );
+        }
+        let adapter = OpenCodeAdapter::new().with_data_dir(&data_dir);
+
+        let plain = adapter
+            .list_thread_overviews(None)
+            .expect("overviews should load");
+        assert_eq!(plain[0].message_count, None);
+
+        let overviews = adapter
+            .list_thread_overviews_with_options(
+                None,
+                ThreadOverviewOptions {
+                    include_message_count: true,
+                    ..ThreadOverviewOptions::default()
+                },
+            )
+            .expect("overviews should load");
+        let activity = adapter
+            .get_thread_activity(session_id)
+            .expect("activity should load");
+        assert_eq!(overviews[0].message_count, Some(activity.message_count));
+        assert_eq!(activity.message_count, 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn list_threads_keeps_sessions_with_non_utf8_file_names() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let data_dir = test_temp_dir("non-utf8-name").join("opencode");
+        write_json(
+            &data_dir
+                .join("storage/session/proj-exotic")
+                .join(OsStr::from_bytes(b"ses_\xff.json")),
+            r#"{"projectID":"proj-exotic","directory":"/workspace/a","title":"Exotic file name","time":{"created":1760000000000,"updated":1760000001000}}"#,
+        );
+        let adapter = OpenCodeAdapter::new().with_data_dir(&data_dir);
+
+        let threads = adapter.list_threads(None).expect("threads should load");
+
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].id, "ses_\u{fffd}");
+        assert_eq!(threads[0].title, "Exotic file name");
+        assert!(adapter.thread_exists("ses_\u{fffd}"));
+    }
 }
@@ -6,6 +6,7 @@ export type ProviderErrorCode =
   | "permission_denied"
   | "timeout"
   | "upstream_unavailable"
+  | "rate_limited"
   | "invalid_response"
   | "not_implemented"
   | "unknown";
@@ -26,7 +27,7 @@ export interface ProviderHealthCheckRequest {
 export interface ProviderHealthCheckResult {
   providerId: ProviderId;
   status: ProviderHealthStatus;
-  checkedAt: string;
+  checkedAt: number;
   message?: string;
 }
 
@@ -35,14 +36,18 @@ export interface ThreadSummary {
   providerId: ProviderId;
   accountId?: string;
   projectPath: string;
+  originalCwd?: string;
+  model?: string;
+  providerBackend?: string;
   title: string;
   tags: string[];
-  lastActiveAt: string;
+  lastActiveAt: number;
 }
 
 export interface ResumeThreadRequest {
   threadId: string;
   projectPath?: string;
+  contextSummary?: string;
 }
 
 export interface ResumeThreadResult {
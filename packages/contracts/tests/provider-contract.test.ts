@@ -14,7 +14,7 @@ describe("provider contract", () => {
     const payload: ProviderHealthCheckResult = {
       providerId: "codex" as ProviderId,
       status: "healthy",
-      checkedAt: "2026-02-11T00:00:00Z",
+      checkedAt: 1_770_768_000_000,
     };
 
     expect(payload.providerId).toBe("codex");